@@ -0,0 +1,162 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::models::{configured_challenges_dir, count_challenges, config, Difficulty};
+
+/// Clones or pulls the configured challenge pack, saving `url` as the pack
+/// first if one is given.
+pub fn sync_challenges(url: Option<String>) {
+    let mut user_config = config::load_config();
+    if let Some(url) = url {
+        user_config.challenges_repo = Some(url);
+        if let Err(e) = config::save_config(&user_config) {
+            eprintln!("Failed to save config: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let Some(repo) = user_config.challenges_repo.clone() else {
+        eprintln!("No challenge pack configured. Run: codle challenges sync --url <GIT_URL>");
+        std::process::exit(1);
+    };
+
+    if Command::new("git").arg("--version").output().is_err() {
+        eprintln!("'git' is not installed. Please install it and try again.");
+        std::process::exit(1);
+    }
+
+    let target = configured_challenges_dir().unwrap_or_else(config::get_pack_dir);
+    let before = count_challenges(&target);
+
+    if let Err(e) = sync_pack(&repo, &target) {
+        eprintln!("Failed to sync challenges from {}: {}", repo, e);
+        std::process::exit(1);
+    }
+
+    println!("Synced {} into {}", repo, target.display());
+    for line in report_lines(&before, &count_challenges(&target)) {
+        println!("{}", line);
+    }
+}
+
+/// Pulls `target` if it is already a checkout, otherwise clones `repo` into it.
+fn sync_pack(repo: &str, target: &Path) -> Result<(), String> {
+    let mut command = Command::new("git");
+    if target.join(".git").exists() {
+        command.arg("-C").arg(target).args(["pull", "--ff-only"]);
+    } else if target.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(format!(
+            "{} already has files but isn't a git checkout; move them or set challenges_dir elsewhere",
+            target.display()
+        ));
+    } else {
+        command.args(["clone", repo]).arg(target);
+    }
+
+    let output = command.output().map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+/// One line per tier with its count after the sync and the change, then the
+/// total.
+fn report_lines(before: &[(Difficulty, usize)], after: &[(Difficulty, usize)]) -> Vec<String> {
+    let previous = |difficulty: Difficulty| {
+        before
+            .iter()
+            .find(|(d, _)| *d == difficulty)
+            .map_or(0, |(_, count)| *count)
+    };
+    let change = |now: usize, then: usize| match now as i64 - then as i64 {
+        0 => String::new(),
+        delta => format!(" ({:+})", delta),
+    };
+
+    let mut lines: Vec<String> = after
+        .iter()
+        .map(|&(difficulty, count)| {
+            format!(
+                "  {:<8} {:>3}{}",
+                format!("{}:", difficulty.display_name()),
+                count,
+                change(count, previous(difficulty))
+            )
+        })
+        .collect();
+
+    let total: usize = after.iter().map(|(_, count)| count).sum();
+    let total_before: usize = before.iter().map(|(_, count)| count).sum();
+    lines.push(format!("  {:<8} {:>3}{}", "Total:", total, change(total, total_before)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=codle", "-c", "user.email=codle@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn test_sync_reports_updated_counts() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let root = std::env::temp_dir().join(format!("codle-sync-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let pack = root.join("pack");
+        let target = root.join("challenges");
+        fs::create_dir_all(pack.join("easy")).unwrap();
+        fs::create_dir_all(pack.join("hard")).unwrap();
+        fs::write(pack.join("easy/a.json"), "{}").unwrap();
+        fs::write(pack.join("hard/b.json"), "{}").unwrap();
+        git(&pack, &["init", "-q"]);
+        git(&pack, &["add", "."]);
+        git(&pack, &["commit", "-qm", "Add challenges"]);
+
+        let url = pack.to_string_lossy().to_string();
+        let empty = count_challenges(&target);
+        sync_pack(&url, &target).unwrap();
+        let cloned = count_challenges(&target);
+
+        fs::write(pack.join("easy/c.json"), "{}").unwrap();
+        git(&pack, &["add", "."]);
+        git(&pack, &["commit", "-qm", "Add another"]);
+        sync_pack(&url, &target).unwrap();
+        let pulled = count_challenges(&target);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(
+            report_lines(&empty, &cloned),
+            vec![
+                "  Easy:      1 (+1)",
+                "  Medium:    0",
+                "  Hard:      1 (+1)",
+                "  Extreme:   0",
+                "  Total:     2 (+2)",
+            ]
+        );
+        assert_eq!(
+            report_lines(&cloned, &pulled),
+            vec![
+                "  Easy:      2 (+1)",
+                "  Medium:    0",
+                "  Hard:      1",
+                "  Extreme:   0",
+                "  Total:     3 (+1)",
+            ]
+        );
+    }
+}
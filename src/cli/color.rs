@@ -0,0 +1,56 @@
+use std::io::IsTerminal;
+
+/// Whether to color stdout. `--no-color` always wins, then the `color`
+/// config setting; otherwise color is off when `NO_COLOR` is set or stdout
+/// isn't a terminal, so piped output stays plain.
+pub fn enabled(no_color: bool, configured: Option<bool>) -> bool {
+    resolve(
+        no_color,
+        configured,
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+        std::io::stdout().is_terminal(),
+    )
+}
+
+fn resolve(no_color: bool, configured: Option<bool>, no_color_env: bool, terminal: bool) -> bool {
+    if no_color {
+        return false;
+    }
+    configured.unwrap_or(!no_color_env && terminal)
+}
+
+pub fn green(text: &str, color: bool) -> String {
+    paint(text, "32", color)
+}
+
+pub fn red(text: &str, color: bool) -> String {
+    paint(text, "31", color)
+}
+
+pub fn dim(text: &str, color: bool) -> String {
+    paint(text, "2", color)
+}
+
+fn paint(text: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_precedence() {
+        assert!(resolve(false, None, false, true));
+        assert!(!resolve(false, None, false, false));
+        assert!(!resolve(false, None, true, true));
+        assert!(resolve(false, Some(true), true, false));
+        assert!(!resolve(true, Some(true), false, true));
+        assert_eq!(green("ok", false), "ok");
+        assert_eq!(red("no", true), "\x1b[31mno\x1b[0m");
+    }
+}
@@ -1,17 +1,32 @@
-use crate::models::{Difficulty, config};
+use crate::models::{count_challenges, get_challenges_dir, Difficulty, config};
+
+pub fn handle_difficulty(level: Option<Difficulty>, list: bool) {
+    if list {
+        list_challenge_counts();
+        return;
+    }
 
-pub fn handle_difficulty(level: Option<Difficulty>) {
     let mut user_config = config::load_config();
 
     match level {
         None => {
             println!("Current difficulty: {}", user_config.difficulty.display_name());
+            let effective = config::load_effective_config();
+            if effective.difficulty != user_config.difficulty {
+                println!(
+                    "  (overridden to {} by .codlerc in this directory)",
+                    effective.difficulty.display_name()
+                );
+            }
             println!("BOSS Score: {}", user_config.boss_score);
             println!("Challenges completed: {}", user_config.challenges_completed);
             println!("Current streak: {} day(s)", user_config.current_streak);
             println!("Longest streak: {} day(s)", user_config.longest_streak);
+            for line in super::language_count_lines(&user_config) {
+                println!("{}", line);
+            }
             println!();
-            println!("BOSS Score = challenge_difficulty + tier_bonus + streak_bonus");
+            println!("BOSS Score = challenge_difficulty + tier_bonus + streak_bonus - hints_revealed (min 1)");
             println!();
             println!("Tier bonuses:");
             println!("  Easy:    +0");
@@ -20,8 +35,11 @@ pub fn handle_difficulty(level: Option<Difficulty>) {
             println!("  Extreme: +3");
             println!();
             println!("Streak bonus: +1 per consecutive day (max +5)");
+            println!("Hint penalty: -1 per hint revealed");
+            println!("Bonus challenges score half, without the streak bonus (min 1)");
             println!();
             println!("To change: codle difficulty <level>");
+            println!("To see how many challenges each tier has: codle difficulty --list");
         }
         Some(new_level) => {
             let old_level = user_config.difficulty;
@@ -48,3 +66,14 @@ pub fn handle_difficulty(level: Option<Difficulty>) {
         }
     }
 }
+
+fn list_challenge_counts() {
+    let current = config::load_effective_config().difficulty;
+    let challenges_dir = get_challenges_dir();
+
+    println!("Challenges in {}:", challenges_dir.display());
+    for (difficulty, count) in count_challenges(&challenges_dir) {
+        let marker = if difficulty == current { "  (current)" } else { "" };
+        println!("  {:<8} {:>3}{}", format!("{}:", difficulty.display_name()), count, marker);
+    }
+}
@@ -0,0 +1,89 @@
+use std::env;
+
+use crate::models::{find_challenge, get_challenges_dir, project};
+
+/// Reveals the next hint for the challenge of the project in the current
+/// directory, after repeating the ones already shown. Each new hint is saved
+/// to `.codle.json` and costs a BOSS point on submit.
+pub fn show_hint() {
+    let current_dir = env::current_dir().unwrap_or_else(|e| {
+        eprintln!("Failed to get current directory: {}", e);
+        std::process::exit(1);
+    });
+
+    let mut metadata = match project::load(&current_dir) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let Some(challenge) = find_challenge(&get_challenges_dir(), &metadata.challenge_name) else {
+        eprintln!("Challenge '{}' not found in the challenge set", metadata.challenge_name);
+        std::process::exit(1);
+    };
+
+    let revealed = metadata.hints_revealed as usize;
+    let reveal_next = revealed < challenge.hints.len();
+    if reveal_next {
+        metadata.hints_revealed += 1;
+        if let Err(e) = project::save(&current_dir, &metadata) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+
+    for line in hint_lines(&challenge.hints, revealed, reveal_next) {
+        println!("{}", line);
+    }
+}
+
+/// The hints shown so far, then the next one if `reveal_next`, or a note
+/// that there are none (left).
+fn hint_lines(hints: &[String], revealed: usize, reveal_next: bool) -> Vec<String> {
+    if hints.is_empty() {
+        return vec!["No hints available for this challenge. You've got this!".to_string()];
+    }
+
+    let shown = (revealed + reveal_next as usize).min(hints.len());
+    let mut lines: Vec<String> = hints[..shown]
+        .iter()
+        .enumerate()
+        .map(|(i, hint)| format!("Hint {}/{}: {}", i + 1, hints.len(), hint))
+        .collect();
+    lines.push(String::new());
+    if reveal_next {
+        lines.push(format!(
+            "{} hint(s) revealed; each costs 1 BOSS point when you submit.",
+            shown
+        ));
+    } else {
+        lines.push("That's every hint for this challenge.".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints_revealed_one_at_a_time() {
+        let hints = vec!["Sort first.".to_string(), "Use two pointers.".to_string()];
+        assert_eq!(
+            hint_lines(&hints, 0, true),
+            vec![
+                "Hint 1/2: Sort first.",
+                "",
+                "1 hint(s) revealed; each costs 1 BOSS point when you submit."
+            ]
+        );
+        assert_eq!(hint_lines(&hints, 1, true)[..2], ["Hint 1/2: Sort first.", "Hint 2/2: Use two pointers."]);
+        assert_eq!(hint_lines(&hints, 2, false).last().unwrap(), "That's every hint for this challenge.");
+        assert_eq!(
+            hint_lines(&[], 0, false),
+            vec!["No hints available for this challenge. You've got this!"]
+        );
+    }
+}
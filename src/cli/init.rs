@@ -1,12 +1,44 @@
-use std::path::PathBuf;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
 
-use crate::models::{load_daily_challenge, parse_signature, Language, config};
-use crate::lang::generate_scaffold;
+use zip::write::SimpleFileOptions;
 
-pub fn init_challenge(language: Language) {
-    let user_config = config::load_config();
+use crate::models::{
+    project, get_challenges_dir, load_daily_challenge_at, parse_signature, validate_challenge, Challenge, Difficulty,
+    FunctionSignature, Language, ProjectMetadata, config,
+};
+use crate::lang::{
+    check_language_support, generate_from_template, generate_scaffold, last_setup_step,
+    output_with_timeout, regenerate_tests, run_tests, write_setup_files, ScaffoldOptions, TestSummary,
+};
 
-    let challenge = match load_daily_challenge(user_config.difficulty) {
+use super::{ExistingDir, InitMode, InitTarget};
+
+/// Index written at the root of an `init all` workspace.
+const WORKSPACE_INDEX: &str = "README.md";
+
+pub fn init_challenge(
+    target: InitTarget,
+    mode: InitMode,
+    template: Option<&Path>,
+    options: ScaffoldOptions,
+    output_dir: Option<PathBuf>,
+    existing: ExistingDir,
+    difficulty: Option<Difficulty>,
+) {
+    let mut user_config = config::load_effective_config();
+    // `--difficulty` is for this run only; the project's metadata records it
+    // so submit scores the tier the challenge came from.
+    if let Some(difficulty) = difficulty {
+        user_config.difficulty = difficulty;
+    }
+
+    let challenge = match load_daily_challenge_at(user_config.difficulty, options.challenge_index, &user_config.history) {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Failed to load challenge: {}", e);
@@ -22,36 +54,126 @@ pub fn init_challenge(language: Language) {
         }
     };
 
+    if let Err(e) = validate_challenge(&challenge, &sig) {
+        eprintln!("Invalid challenge '{}': {}", challenge.name, e);
+        std::process::exit(1);
+    }
+
     let dir_name = challenge
         .name
         .to_lowercase()
         .replace(' ', "_")
         .replace(|c: char| !c.is_alphanumeric() && c != '_', "");
-    let output_dir = PathBuf::from(&dir_name);
 
-    if output_dir.exists() {
-        eprintln!(
-            "Directory '{}' already exists. Remove it first or use a different location.",
-            dir_name
-        );
+    let options = ScaffoldOptions {
+        idiomatic_names: user_config.idiomatic_names,
+        ..options
+    };
+
+    let language = match target {
+        InitTarget::Language(language) => language,
+        InitTarget::All => {
+            if mode != InitMode::Scaffold || existing == ExistingDir::UpdateTests {
+                eprintln!("--zip, --run, --run-tests and --overwrite-tests-only need a single language, not 'all'");
+                std::process::exit(1);
+            }
+            let workspace = output_dir.unwrap_or_else(|| PathBuf::from(&dir_name));
+            if let Err(e) = check_output_dir(&workspace, existing == ExistingDir::Replace) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            init_workspace_dir(&challenge, &sig, user_config.difficulty, template, &options, &workspace);
+            return;
+        }
+    };
+
+    if let Err(e) = check_language_support(&sig, language) {
+        eprintln!("Cannot scaffold '{}': {}", challenge.name, e);
         std::process::exit(1);
     }
 
-    if let Err(e) = std::fs::create_dir_all(&output_dir) {
-        eprintln!("Failed to create directory '{}': {}", dir_name, e);
+    // A zip is often built for another machine, and refreshing tests doesn't
+    // run setup.sh, so only warn when this machine is about to need the tools.
+    if mode != InitMode::Zip && existing != ExistingDir::UpdateTests {
+        let missing = missing_commands(language.required_commands(), &env::var_os("PATH").unwrap_or_default());
+        if !missing.is_empty() {
+            eprintln!(
+                "Warning: {} not found on your PATH; setup.sh needs it for {}. Scaffolding anyway.",
+                missing.join(", "),
+                language.display_name()
+            );
+            eprintln!();
+        }
+    }
+
+    if existing == ExistingDir::UpdateTests {
+        let project_dir = output_dir.unwrap_or_else(|| PathBuf::from(&dir_name));
+        match overwrite_tests(&challenge, &sig, language, &project_dir) {
+            Ok(()) => println!(
+                "Updated the {} tests for '{}' in {}/; your solution was left as is",
+                language.display_name(),
+                challenge.name,
+                project_dir.display()
+            ),
+            Err(e) => {
+                eprintln!("Failed to update tests: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if mode == InitMode::VerifyReference {
+        let setup_timeout = user_config.setup_timeout_secs.map(Duration::from_secs);
+        verify_reference_solution(
+            &challenge,
+            &sig,
+            language,
+            user_config.difficulty,
+            setup_timeout,
+            &dir_name,
+        );
+        return;
+    }
+
+    if mode == InitMode::Zip {
+        init_zip(&challenge, &sig, language, user_config.difficulty, template, &options, &dir_name);
+        return;
+    }
+
+    let output_dir = output_dir.unwrap_or_else(|| PathBuf::from(&dir_name));
+    let dir_name = output_dir.display().to_string();
+    if let Err(e) = check_output_dir(&output_dir, existing == ExistingDir::Replace) {
+        eprintln!("{}", e);
         std::process::exit(1);
     }
 
-    match generate_scaffold(&challenge, &sig, language, user_config.difficulty, &output_dir) {
+    let difficulty = user_config.difficulty;
+    let generated = generate_into(&output_dir, |staging| {
+        scaffold(&challenge, &sig, language, difficulty, template, &options, staging)
+    });
+    match generated {
         Ok(()) => {
             println!(
-                "Initialized {} scaffold for '{}' in ./{}/",
+                "Initialized {} scaffold for '{}' in {}/",
                 language.display_name(),
                 challenge.name,
                 dir_name
             );
+            if options.challenge_index > 0 {
+                println!("This is a bonus challenge: it scores half and doesn't count towards your streak.");
+            }
             println!();
-            print_run_instructions(language, &dir_name);
+            if mode == InitMode::ScaffoldAndSetup {
+                if let Err((message, code)) = run_setup(&output_dir) {
+                    eprintln!("{}", message);
+                    std::process::exit(code);
+                }
+                println!();
+                println!("Now write your solution in {}/{}", dir_name, language.solution_filename());
+            } else {
+                print_run_instructions(language, &dir_name);
+            }
         }
         Err(e) => {
             eprintln!("Failed to generate scaffold: {}", e);
@@ -60,9 +182,961 @@ pub fn init_challenge(language: Language) {
     }
 }
 
-fn print_run_instructions(_language: Language, dir_name: &str) {
+/// The `commands` with no file of that name in any directory of `path`.
+fn missing_commands<'a>(commands: &[&'a str], path: &OsStr) -> Vec<&'a str> {
+    commands
+        .iter()
+        .copied()
+        .filter(|cmd| !env::split_paths(path).any(|dir| dir.join(cmd).is_file()))
+        .collect()
+}
+
+/// Checks that a new scaffold can go in `dir`. An existing `dir` is an error
+/// unless `replace`, and even then only an empty directory or one codle
+/// generated is replaced, never one holding the current directory.
+fn check_output_dir(dir: &Path, replace: bool) -> Result<(), String> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    if !replace {
+        return Err(format!(
+            "Directory '{}' already exists. Remove it first, use a different location, or pass --force.",
+            dir.display()
+        ));
+    }
+    let empty = fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none());
+    if !empty && !is_codle_output(dir) {
+        return Err(format!(
+            "Refusing to replace '{}': it isn't empty and has no {}, so codle didn't create it",
+            dir.display(),
+            project::METADATA_FILE
+        ));
+    }
+    let holds_cwd = match (dir.canonicalize(), env::current_dir()) {
+        (Ok(dir), Ok(cwd)) => cwd.starts_with(dir),
+        _ => false,
+    };
+    if holds_cwd {
+        return Err(format!("Refusing to delete '{}': it holds the current directory", dir.display()));
+    }
+    Ok(())
+}
+
+/// Whether `dir` is a project or an `init all` workspace of them.
+fn is_codle_output(dir: &Path) -> bool {
+    dir.join(project::METADATA_FILE).is_file()
+        || Language::all()
+            .iter()
+            .any(|language| dir.join(language.extension()).join(project::METADATA_FILE).is_file())
+}
+
+/// Runs `generate` on a staging directory next to `dir` and only moves the
+/// result into place, replacing any `dir` that `check_output_dir` allowed,
+/// once it succeeds, so a failed scaffold leaves `dir` as it was.
+fn generate_into<T>(dir: &Path, generate: impl FnOnce(&Path) -> Result<T, String>) -> Result<T, String> {
+    let name = dir
+        .file_name()
+        .ok_or_else(|| format!("'{}' isn't a directory name codle can create", dir.display()))?;
+    let parent = dir.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+    let staging = parent.join(format!(".{}.codle-staging", name.to_string_lossy()));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir(&staging)
+        .map_err(|e| format!("Failed to create directory '{}': {}", staging.display(), e))?;
+
+    let value = match generate(&staging) {
+        Ok(value) => value,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(|e| format!("Failed to remove '{}': {}", dir.display(), e))?;
+    }
+    fs::rename(&staging, dir).map_err(|e| {
+        format!("Failed to move the scaffold from '{}' into '{}': {}", staging.display(), dir.display(), e)
+    })?;
+    Ok(value)
+}
+
+fn scaffold(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+    template: Option<&Path>,
+    options: &ScaffoldOptions,
+    output_dir: &Path,
+) -> Result<(), String> {
+    match template {
+        Some(dir) => generate_from_template(challenge, sig, language, difficulty, dir, output_dir),
+        None => generate_scaffold(challenge, sig, language, difficulty, output_dir, options),
+    }
+}
+
+/// Regenerates the tests and metadata of the project in `project_dir` after
+/// checking it was made for this challenge and language.
+fn overwrite_tests(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    project_dir: &Path,
+) -> Result<(), String> {
+    let mut metadata = project::load(project_dir)?;
+    if metadata.challenge_name != challenge.name || metadata.language != language {
+        return Err(format!(
+            "{} holds {} ({}), not {} ({})",
+            project_dir.display(),
+            metadata.challenge_name,
+            metadata.language.display_name(),
+            challenge.name,
+            language.display_name()
+        ));
+    }
+
+    // Keep the function name the project was generated with, which may have
+    // been renamed by `idiomatic_names`.
+    let sig = FunctionSignature {
+        name: metadata.function_name.clone(),
+        ..sig.clone()
+    };
+    regenerate_tests(challenge, &sig, language, project_dir)?;
+
+    metadata.challenge_difficulty = challenge.difficulty;
+    project::save(project_dir, &metadata)
+}
+
+/// `codle init --lang-from-metadata`: regenerates setup.sh and the tests of
+/// the project in the current directory from the challenge and language its
+/// `.codle.json` records, for a deleted file or an updated challenge.
+pub fn rescaffold_from_metadata(options: ScaffoldOptions, force: bool) {
+    let project_dir = Path::new(".");
+    match rescaffold(project_dir, &get_challenges_dir(), options, force) {
+        Ok((metadata, tests_written)) => {
+            let language = metadata.language;
+            println!(
+                "Regenerated setup.sh{} for '{}' ({}) from {}; your solution was left as is",
+                if tests_written { " and the tests" } else { "" },
+                metadata.challenge_name,
+                language.display_name(),
+                project::METADATA_FILE
+            );
+            if !tests_written {
+                println!(
+                    "{} is missing: run ./setup.sh to recreate it, which also rewrites {}",
+                    language.test_filename(),
+                    language.solution_filename()
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to re-scaffold: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Rewrites setup.sh, the tests and `.codle.json` of the project in
+/// `project_dir`, keeping its function name, tier, bonus index and when it
+/// was initialized. Returns the updated metadata and whether the tests were
+/// written; they aren't when the file they go in is gone (for Rust, the
+/// solution itself). Refuses if the solution has uncommitted changes, since
+/// rewriting Rust's tests or rerunning setup.sh would lose them, unless `force`.
+fn rescaffold(
+    project_dir: &Path,
+    challenges_dir: &Path,
+    options: ScaffoldOptions,
+    force: bool,
+) -> Result<(ProjectMetadata, bool), String> {
+    let mut metadata = project::load(project_dir)?;
+    let language = metadata.language;
+    let challenge = metadata.challenge(challenges_dir)?;
+    let sig = parse_signature(&challenge.function_signature)
+        .map_err(|e| format!("Failed to parse function signature: {}", e))?;
+    validate_challenge(&challenge, &sig).map_err(|e| format!("Invalid challenge '{}': {}", challenge.name, e))?;
+    check_language_support(&sig, language)?;
+
+    if !force && has_uncommitted_changes(project_dir, language.solution_filename()) {
+        return Err(format!(
+            "{} has uncommitted changes. Commit them or pass --force.",
+            language.solution_filename()
+        ));
+    }
+
+    let sig = FunctionSignature {
+        name: metadata.function_name.clone(),
+        ..sig
+    };
+    let options = ScaffoldOptions {
+        idiomatic_names: false,
+        challenge_index: metadata.challenge_index,
+        initialized_at: metadata.initialized_at.clone(),
+        ..options
+    };
+    generate_scaffold(&challenge, &sig, language, metadata.difficulty, project_dir, &options)?;
+
+    let tests_path = project_dir.join(language.test_filename());
+    let tests_written = if language == Language::Rs {
+        tests_path.is_file()
+    } else {
+        tests_path.parent().is_some_and(Path::is_dir)
+    };
+    if tests_written {
+        regenerate_tests(&challenge, &sig, language, project_dir)?;
+    }
+
+    metadata.challenge_difficulty = challenge.difficulty;
+    project::save(project_dir, &metadata)?;
+    Ok((metadata, tests_written))
+}
+
+/// Whether git reports `file` in `dir` as changed since the last commit.
+/// Outside a git repository there is nothing to compare against, so no.
+fn has_uncommitted_changes(dir: &Path, file: &str) -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain", "--", file])
+        .current_dir(dir)
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
+fn init_workspace_dir(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    template: Option<&Path>,
+    options: &ScaffoldOptions,
+    workspace: &Path,
+) {
+    match generate_into(workspace, |staging| init_workspace(challenge, sig, difficulty, template, options, staging)) {
+        Ok(skipped) => {
+            println!(
+                "Initialized a comparison workspace for '{}' in {}/",
+                challenge.name,
+                workspace.display()
+            );
+            for (language, reason) in &skipped {
+                println!("  Skipped {}: {}", language.display_name(), reason);
+            }
+            println!();
+            println!("See {} for how to set up and run each language.", workspace.join(WORKSPACE_INDEX).display());
+        }
+        Err(e) => {
+            eprintln!("Failed to generate workspace: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Scaffolds one project per language into `<workspace>/<extension>/` and
+/// writes an index of their run commands. Languages that can't express the
+/// signature (or the requested options) are left out and returned with the
+/// reason.
+fn init_workspace(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    template: Option<&Path>,
+    options: &ScaffoldOptions,
+    workspace: &Path,
+) -> Result<Vec<(Language, String)>, String> {
+    let mut generated = Vec::new();
+    let mut skipped = Vec::new();
+
+    for &language in Language::all() {
+        let dir = workspace.join(language.extension());
+        let result = check_language_support(sig, language)
+            .and_then(|_| {
+                fs::create_dir_all(&dir)
+                    .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))
+            })
+            .and_then(|_| scaffold(challenge, sig, language, difficulty, template, options, &dir));
+        match result {
+            Ok(()) => generated.push(language),
+            Err(e) => {
+                let _ = fs::remove_dir_all(&dir);
+                skipped.push((language, e));
+            }
+        }
+    }
+
+    if generated.is_empty() {
+        return Err("no language can scaffold this challenge".to_string());
+    }
+
+    let index = workspace.join(WORKSPACE_INDEX);
+    fs::write(&index, workspace_index(challenge, &generated, &skipped))
+        .map_err(|e| format!("Failed to write {}: {}", index.display(), e))?;
+    Ok(skipped)
+}
+
+fn workspace_index(
+    challenge: &Challenge,
+    generated: &[Language],
+    skipped: &[(Language, String)],
+) -> String {
+    let mut out = format!("# {}\n\n{}\n\n", challenge.name, challenge.short_description);
+    out.push_str("Each directory holds the same challenge in one language. ");
+    out.push_str("Run `./setup.sh` inside it once, then edit the solution file.\n\n");
+    out.push_str("| Language | Directory | Solution | Run | Test |\n");
+    out.push_str("|----------|-----------|----------|-----|------|\n");
+    for language in generated {
+        let (program, args) = language.test_command();
+        out.push_str(&format!(
+            "| {} | `{}/` | `{}` | `{}` | `{} {}` |\n",
+            language.display_name(),
+            language.extension(),
+            language.solution_filename(),
+            language.run_command(),
+            program,
+            args.join(" ")
+        ));
+    }
+    if !skipped.is_empty() {
+        out.push_str("\nNot generated:\n\n");
+        for (language, reason) in skipped {
+            out.push_str(&format!("- {}: {}\n", language.display_name(), reason));
+        }
+    }
+    out
+}
+
+fn verify_reference_solution(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+    setup_timeout: Option<Duration>,
+    dir_name: &str,
+) {
+    let staging = std::env::temp_dir().join(format!("codle-verify-{}-{}", dir_name, std::process::id()));
+    let result = fs::create_dir_all(&staging)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))
+        .and_then(|_| {
+            check_reference_solution(challenge, sig, language, difficulty, setup_timeout, &staging)
+        });
+    let _ = fs::remove_dir_all(&staging);
+
+    let summary = match result {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to verify '{}': {}", challenge.name, e);
+            std::process::exit(1);
+        }
+    };
+
+    if summary.total > 0 && summary.failed == 0 {
+        println!(
+            "Reference {} solution for '{}' passes all {} tests",
+            language.display_name(),
+            challenge.name,
+            summary.total
+        );
+        return;
+    }
+
+    eprintln!(
+        "Reference {} solution for '{}' passed {}/{} tests",
+        language.display_name(),
+        challenge.name,
+        summary.passed,
+        summary.total
+    );
+    let failures: Vec<&str> = summary
+        .output
+        .lines()
+        .filter(|line| line.contains("FAIL"))
+        .collect();
+    if let Some(diagnosis) = &summary.diagnosis {
+        eprintln!("  {}", diagnosis);
+    }
+    if let Some(errors) = &summary.compile_error {
+        eprintln!("  The reference solution doesn't compile:");
+        for line in errors.lines() {
+            eprintln!("    {}", line);
+        }
+        std::process::exit(1);
+    }
+    if failures.is_empty() {
+        eprintln!();
+        eprintln!("{}", summary.output.trim());
+    } else {
+        for line in failures {
+            eprintln!("  {}", line.trim());
+        }
+    }
+    std::process::exit(1);
+}
+
+/// Scaffolds `challenge` into `dir` with its reference solution for `language`
+/// as the function body, runs `setup.sh`, then runs the tests.
+fn check_reference_solution(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+    setup_timeout: Option<Duration>,
+    dir: &Path,
+) -> Result<TestSummary, String> {
+    let body = challenge.reference_solutions.get(&language).ok_or_else(|| {
+        format!("no reference solution for {}", language.display_name())
+    })?;
+    let options = ScaffoldOptions {
+        solution_body: Some(body.clone()),
+        ..ScaffoldOptions::default()
+    };
+    scaffold_and_setup(challenge, sig, language, difficulty, &options, setup_timeout, dir)?;
+
+    let timeout = super::resolve_timeout(None, challenge.time_limit_ms, difficulty, language);
+    run_tests(language, dir, Some(timeout))
+}
+
+/// Scaffolds `challenge` into `dir` and runs its `setup.sh`.
+pub(super) fn scaffold_and_setup(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+    options: &ScaffoldOptions,
+    setup_timeout: Option<Duration>,
+    dir: &Path,
+) -> Result<(), String> {
+    generate_scaffold(challenge, sig, language, difficulty, dir, options)?;
+
+    let setup = output_with_timeout(Command::new("bash").arg("setup.sh").current_dir(dir), setup_timeout)
+        .map_err(|e| format!("Failed to run setup.sh: {}", e))?;
+    if !setup.status.success() {
+        let stdout = String::from_utf8_lossy(&setup.stdout);
+        let step = last_setup_step(&stdout)
+            .map(|step| format!(" at step '{}'", step))
+            .unwrap_or_default();
+        return Err(format!(
+            "setup.sh failed{}:\n{}{}",
+            step,
+            stdout,
+            String::from_utf8_lossy(&setup.stderr)
+        ));
+    }
+    Ok(())
+}
+
+fn print_run_instructions(language: Language, dir_name: &str) {
     println!("To get started:");
     println!();
     println!("  cd {}", dir_name);
     println!("  ./setup.sh");
+    println!();
+    println!("Then write your solution in {}", language.solution_filename());
+}
+
+/// Runs the scaffold's setup.sh with its output going straight to the
+/// terminal. If it fails, returns the message and the exit code to fail
+/// with. Without bash to run it, it explains how to finish the setup by hand
+/// instead.
+fn run_setup(output_dir: &Path) -> Result<(), (String, i32)> {
+    let dir = output_dir.display();
+    if !output_dir.join("setup.sh").is_file() {
+        return Err((format!("--run: the scaffold in {}/ has no setup.sh to run", dir), 1));
+    }
+    if !cfg!(unix) {
+        eprintln!("--run needs bash to run setup.sh, which this platform can't do directly.");
+        eprintln!("Run it yourself from Git Bash or WSL:");
+        eprintln!();
+        eprintln!("  cd {}", dir);
+        eprintln!("  bash setup.sh");
+        return Ok(());
+    }
+
+    println!("Running {}/setup.sh", dir);
+    println!();
+    let status = Command::new("bash")
+        .arg("setup.sh")
+        .current_dir(output_dir)
+        .status()
+        .map_err(|e| (format!("Failed to run setup.sh: {}", e), 1))?;
+    if !status.success() {
+        return Err((
+            format!("\nsetup.sh failed ({}); fix the problem above and rerun it in {}/", status, dir),
+            status.code().unwrap_or(1),
+        ));
+    }
+    Ok(())
+}
+
+fn init_zip(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+    template: Option<&Path>,
+    options: &ScaffoldOptions,
+    dir_name: &str,
+) {
+    let archive_path = PathBuf::from(format!("{}.zip", dir_name));
+    if archive_path.exists() {
+        eprintln!(
+            "'{}' already exists. Remove it first or use a different location.",
+            archive_path.display()
+        );
+        std::process::exit(1);
+    }
+
+    let staging = std::env::temp_dir().join(format!("codle-{}-{}", dir_name, std::process::id()));
+    let output_dir = staging.join(dir_name);
+
+    let result = fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))
+        .and_then(|_| scaffold(challenge, sig, language, difficulty, template, options, &output_dir))
+        .and_then(|_| match template {
+            // A template writes its files directly, without a setup.sh.
+            Some(_) => Ok(()),
+            None => write_setup_files(&output_dir, language, sig),
+        })
+        .and_then(|_| zip_directory(&output_dir, dir_name, &archive_path));
+    let _ = fs::remove_dir_all(&staging);
+
+    match result {
+        Ok(()) => {
+            println!(
+                "Packed {} scaffold for '{}' into ./{}",
+                language.display_name(),
+                challenge.name,
+                archive_path.display()
+            );
+            println!();
+            println!("To get started:");
+            println!();
+            println!("  unzip {}", archive_path.display());
+            println!("  cd {}", dir_name);
+            println!("  ./setup.sh");
+            println!();
+            println!("Then write your solution in {}", language.solution_filename());
+        }
+        Err(e) => {
+            eprintln!("Failed to generate archive: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Packs every file under `dir` into `archive_path`, nested under `prefix/`.
+/// A partly written archive is removed, so it doesn't block the next try.
+fn zip_directory(dir: &Path, prefix: &str, archive_path: &Path) -> Result<(), String> {
+    let file = fs::File::create(archive_path)
+        .map_err(|e| format!("Failed to create {}: {}", archive_path.display(), e))?;
+    let result = write_archive(zip::ZipWriter::new(file), dir, prefix);
+    if result.is_err() {
+        let _ = fs::remove_file(archive_path);
+    }
+    result
+}
+
+fn write_archive(mut writer: zip::ZipWriter<fs::File>, dir: &Path, prefix: &str) -> Result<(), String> {
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(&current)
+            .map_err(|e| format!("Failed to read {}: {}", current.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+
+        for path in entries {
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+
+            let relative = path.strip_prefix(dir).unwrap_or(&path);
+            let name = format!("{}/{}", prefix, relative.to_string_lossy().replace('\\', "/"));
+
+            #[allow(unused_mut)]
+            let mut options = SimpleFileOptions::default();
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if let Ok(meta) = fs::metadata(&path) {
+                    options = options.unix_permissions(meta.permissions().mode());
+                }
+            }
+
+            let contents = fs::read(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            writer
+                .start_file(name, options)
+                .map_err(|e| format!("Failed to add {} to archive: {}", path.display(), e))?;
+            writer
+                .write_all(&contents)
+                .map_err(|e| format!("Failed to add {} to archive: {}", path.display(), e))?;
+        }
+    }
+
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectMetadata;
+
+    #[test]
+    fn test_zip_contains_rust_scaffold() {
+        let staging = std::env::temp_dir().join(format!("codle-zip-test-{}", std::process::id()));
+        let output_dir = staging.join("two_sum");
+        fs::create_dir_all(&output_dir).unwrap();
+
+        let challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "Two Sum",
+            "difficulty": 2,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>",
+            "tests": [{"input": {"nums": [2, 7], "target": 9}, "expected": [0, 1]}],
+        }))
+        .unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        generate_scaffold(
+            &challenge,
+            &sig,
+            Language::Rs,
+            Difficulty::Easy,
+            &output_dir,
+            &ScaffoldOptions::default(),
+        )
+        .unwrap();
+
+        write_setup_files(&output_dir, Language::Rs, &sig).unwrap();
+
+        let archive_path = staging.join("two_sum.zip");
+        zip_directory(&output_dir, "two_sum", &archive_path).unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&archive_path).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        let mut main_rs = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("two_sum/src/main.rs").unwrap(), &mut main_rs).unwrap();
+        let mut cargo_toml = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("two_sum/Cargo.toml").unwrap(), &mut cargo_toml)
+            .unwrap();
+        let metadata = project::load(&output_dir);
+        let _ = fs::remove_dir_all(&staging);
+
+        assert_eq!(
+            names,
+            vec!["two_sum/.codle.json", "two_sum/Cargo.toml", "two_sum/setup.sh", "two_sum/src/main.rs"]
+        );
+        assert!(main_rs.starts_with("fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize> {\n"));
+        assert!(main_rs.contains("#[cfg(test)]\nmod tests {"));
+        assert!(cargo_toml.starts_with("[package]\nname = \"two_sum\"\n"));
+        assert_eq!(metadata.unwrap().challenge_name, "Two Sum");
+    }
+
+    #[test]
+    fn test_failed_zip_leaves_no_archive() {
+        let staging = std::env::temp_dir().join(format!("codle-zip-fail-test-{}", std::process::id()));
+        fs::create_dir_all(&staging).unwrap();
+        let archive_path = staging.join("missing.zip");
+
+        let result = zip_directory(&staging.join("missing"), "missing", &archive_path);
+        let left_behind = archive_path.exists();
+        let _ = fs::remove_dir_all(&staging);
+
+        assert!(result.is_err());
+        assert!(!left_behind);
+    }
+
+    #[test]
+    fn test_workspace_has_every_supported_language() {
+        let workspace = std::env::temp_dir().join(format!("codle-workspace-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&workspace);
+        let challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "Reverse Words",
+            "difficulty": 1,
+            "short_description": "Reverse the word order",
+            "description": "",
+            "function_signature": "fn reverse_words(words: Vec<String>) -> Vec<String>",
+            "tests": [{"input": {"words": ["a", "b"]}, "expected": ["b", "a"]}],
+        }))
+        .unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+
+        let skipped = init_workspace(
+            &challenge,
+            &sig,
+            Difficulty::Easy,
+            None,
+            &ScaffoldOptions::default(),
+            &workspace,
+        )
+        .unwrap();
+        let index = fs::read_to_string(workspace.join(WORKSPACE_INDEX)).unwrap();
+        let scripts: Vec<(Language, Option<String>)> = Language::all()
+            .iter()
+            .map(|lang| (*lang, fs::read_to_string(workspace.join(lang.extension()).join("setup.sh")).ok()))
+            .collect();
+        let _ = fs::remove_dir_all(&workspace);
+
+        // Fortran has no fixed-length string arrays.
+        let skipped: Vec<Language> = skipped.iter().map(|(lang, _)| *lang).collect();
+        assert_eq!(skipped, vec![Language::Fortran]);
+        assert!(index.contains("- Fortran: "));
+        for (language, script) in scripts {
+            if language == Language::Fortran {
+                assert!(script.is_none());
+                continue;
+            }
+            let script = script.unwrap_or_else(|| panic!("no setup.sh for {}", language.display_name()));
+            assert!(script.contains(&format!("cat > {} ", language.solution_filename())));
+            assert!(index.contains(&format!("| `{}/` | `{}` |", language.extension(), language.solution_filename())));
+        }
+    }
+
+    fn has_command(cmd: &str) -> bool {
+        Command::new(cmd).arg("--version").output().is_ok()
+    }
+
+    fn reference_fixture(body: &str) -> Challenge {
+        serde_json::from_value(serde_json::json!({
+            "name": "Add",
+            "difficulty": 1,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn add(a: i32, b: i32) -> i32",
+            "tests": [
+                {"input": {"a": 1, "b": 2}, "expected": 3},
+                {"input": {"a": -4, "b": 4}, "expected": 0},
+            ],
+            "reference_solutions": {"c": body},
+        }))
+        .unwrap()
+    }
+
+    fn check_fixture(name: &str, body: &str) -> TestSummary {
+        let dir = std::env::temp_dir().join(format!("codle-reference-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let challenge = reference_fixture(body);
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let summary = check_reference_solution(&challenge, &sig, Language::C, Difficulty::Easy, None, &dir);
+        let _ = fs::remove_dir_all(&dir);
+        summary.unwrap()
+    }
+
+    #[test]
+    fn test_reference_solution_checks() {
+        if !has_command("gcc") || !has_command("make") {
+            return;
+        }
+
+        let correct = check_fixture("correct", "return a + b;");
+        assert_eq!((correct.passed, correct.failed), (2, 0));
+
+        let wrong = check_fixture("wrong", "return a * b;");
+        assert_eq!((wrong.passed, wrong.failed), (0, 2));
+        assert!(wrong.output.contains("Test 1: FAIL"));
+    }
+
+    #[test]
+    fn test_missing_reference_solution() {
+        let challenge = reference_fixture("return a + b;");
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let err = check_reference_solution(
+            &challenge,
+            &sig,
+            Language::Py,
+            Difficulty::Easy,
+            None,
+            Path::new("."),
+        )
+        .unwrap_err();
+        assert_eq!(err, "no reference solution for Python");
+    }
+
+    #[test]
+    fn test_missing_commands_scans_path() {
+        let bin = std::env::temp_dir().join(format!("codle-path-scan-{}", std::process::id()));
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join("gcc"), "").unwrap();
+        let path = env::join_paths([bin.join("missing"), bin.clone()]).unwrap();
+
+        assert_eq!(missing_commands(Language::C.required_commands(), &path), vec!["make"]);
+        assert_eq!(missing_commands(&["gradle"], OsStr::new("")), vec!["gradle"]);
+        let _ = fs::remove_dir_all(&bin);
+    }
+
+    #[test]
+    fn test_output_dir_replaced_only_when_forced_and_generated() {
+        let dir = std::env::temp_dir().join(format!("codle-prepare-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        check_output_dir(&dir, false).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        check_output_dir(&dir, true).unwrap();
+        fs::write(dir.join("notes.txt"), "mine").unwrap();
+
+        assert!(check_output_dir(&dir, false).unwrap_err().contains("already exists"));
+        assert!(check_output_dir(&dir, true).unwrap_err().starts_with("Refusing to replace"));
+
+        fs::write(dir.join(project::METADATA_FILE), "{}").unwrap();
+        check_output_dir(&dir, true).unwrap();
+
+        // A failed scaffold leaves the old project alone.
+        let err = generate_into(&dir, |staging| {
+            fs::write(staging.join("half.txt"), "").unwrap();
+            Err::<(), _>("boom".to_string())
+        });
+        assert_eq!(err.unwrap_err(), "boom");
+        assert!(dir.join("notes.txt").exists());
+
+        generate_into(&dir, |staging| fs::write(staging.join("new.txt"), "").map_err(|e| e.to_string())).unwrap();
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        let staging = std::env::temp_dir().join(format!(".codle-prepare-test-{}.codle-staging", std::process::id()));
+        let staging_left = staging.exists();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(entries, vec!["new.txt"]);
+        assert!(!staging_left);
+    }
+
+    #[test]
+    fn test_run_setup_reports_the_output_dir() {
+        let dir = std::env::temp_dir().join(format!("codle-run-setup-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let (message, code) = run_setup(&dir).unwrap_err();
+        assert_eq!(message, format!("--run: the scaffold in {}/ has no setup.sh to run", dir.display()));
+        assert_eq!(code, 1);
+
+        fs::write(dir.join("setup.sh"), "touch ran\n").unwrap();
+        let ran = run_setup(&dir);
+        let created = dir.join("ran").exists();
+        fs::write(dir.join("setup.sh"), "exit 3\n").unwrap();
+        let failed = run_setup(&dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(ran, Ok(()));
+        assert!(created);
+        let (message, code) = failed.unwrap_err();
+        assert!(message.ends_with(&format!("rerun it in {}/", dir.display())), "{}", message);
+        assert_eq!(code, 3);
+    }
+
+    #[test]
+    fn test_overwrite_tests_keeps_solution() {
+        let dir = std::env::temp_dir().join(format!("codle-overwrite-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let mut challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "Add Two",
+            "difficulty": 1,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn add(a: i32, b: i32) -> i32",
+            "tests": [{"input": {"a": 1, "b": 2}, "expected": 3}],
+        }))
+        .unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let solution = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+        fs::write(
+            dir.join("src/main.rs"),
+            format!("{}\n#[cfg(test)]\nmod tests {{\n    #[test]\n    fn test_1() {{}}\n}}\n", solution),
+        )
+        .unwrap();
+        let metadata = ProjectMetadata::new(
+            challenge.name.clone(),
+            Language::Rs,
+            Difficulty::Easy,
+            sig.name.clone(),
+            Some("2024-01-01T00:00:00+00:00".to_string()),
+            1,
+        );
+        project::save(&dir, &metadata).unwrap();
+
+        challenge.difficulty = 2;
+        challenge
+            .tests
+            .push(serde_json::from_value(serde_json::json!({"input": {"a": -4, "b": 4}, "expected": 0})).unwrap());
+        overwrite_tests(&challenge, &sig, Language::Rs, &dir).unwrap();
+        let wrong_language = overwrite_tests(&challenge, &sig, Language::Py, &dir);
+
+        let main_rs = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+        let updated = project::load(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(main_rs.starts_with(solution));
+        assert!(main_rs.contains("fn test_2()"));
+        assert_eq!(main_rs.matches("mod tests").count(), 1);
+        assert_eq!(updated.challenge_difficulty, 2);
+        assert_eq!(updated.initialized_at, metadata.initialized_at);
+        assert!(wrong_language.unwrap_err().contains("not Add Two (Python)"));
+    }
+
+    #[test]
+    fn test_rescaffold_from_metadata() {
+        let root = std::env::temp_dir().join(format!("codle-rescaffold-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (challenges, dir) = (root.join("challenges"), root.join("project"));
+        fs::create_dir_all(challenges.join("easy")).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        let challenge = serde_json::json!({
+            "name": "Add Two",
+            "difficulty": 2,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn add(a: i32, b: i32) -> i32",
+            "tests": [
+                {"input": {"a": 1, "b": 2}, "expected": 3},
+                {"input": {"a": -4, "b": 4}, "expected": 0},
+            ],
+        });
+        fs::write(challenges.join("easy/add_two.json"), challenge.to_string()).unwrap();
+
+        let solution = "def add(a: int, b: int) -> int:\n    return a + b\n";
+        fs::write(dir.join("solution.py"), solution).unwrap();
+        fs::write(dir.join("test_solution.py"), "def test_1():\n    pass\n").unwrap();
+        let initialized_at = "2024-01-01T00:00:00+00:00";
+        let mut metadata = ProjectMetadata::new(
+            "Add Two".to_string(),
+            Language::Py,
+            Difficulty::Medium,
+            "add".to_string(),
+            Some(initialized_at.to_string()),
+            1,
+        );
+        metadata.hints_revealed = 1;
+        project::save(&dir, &metadata).unwrap();
+
+        let (updated, tests_written) = rescaffold(&dir, &challenges, ScaffoldOptions::default(), false).unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let tests = fs::read_to_string(dir.join("test_solution.py")).unwrap();
+        let kept = fs::read_to_string(dir.join("solution.py")).unwrap();
+        let saved = project::load(&dir).unwrap();
+
+        let git_guard = has_command("git").then(|| {
+            Command::new("git").args(["init", "-q"]).current_dir(&dir).output().unwrap();
+            let refused = rescaffold(&dir, &challenges, ScaffoldOptions::default(), false).map(|_| ());
+            let forced = rescaffold(&dir, &challenges, ScaffoldOptions::default(), true).map(|_| ());
+            (refused, forced)
+        });
+        metadata.challenge_name = "Gone".to_string();
+        project::save(&dir, &metadata).unwrap();
+        let missing = rescaffold(&dir, &challenges, ScaffoldOptions::default(), true).map(|_| ());
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(tests_written);
+        assert_eq!(updated.challenge_difficulty, 2);
+        assert!(script.contains(&format!("\"initialized_at\": \"{}\"", initialized_at)));
+        assert!(script.contains("\"difficulty\": \"medium\""));
+        assert!(tests.contains("def test_2():"));
+        assert_eq!(kept, solution);
+        assert_eq!((saved.initialized_at.as_deref(), saved.hints_revealed), (Some(initialized_at), 1));
+        if let Some((refused, forced)) = git_guard {
+            assert!(refused.unwrap_err().contains("solution.py has uncommitted changes"));
+            forced.unwrap();
+        }
+        assert!(missing.unwrap_err().contains("The challenge 'Gone' in .codle.json isn't in"));
+    }
 }
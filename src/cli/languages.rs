@@ -0,0 +1,40 @@
+use clap::ValueEnum;
+
+use crate::models::Language;
+
+/// `codle languages`: every backend with the name `codle init` takes and
+/// the tools to install before scaffolding it.
+pub fn list_languages() {
+    for line in language_lines() {
+        println!("{}", line);
+    }
+}
+
+fn language_lines() -> Vec<String> {
+    let mut lines = vec![format!("{:<11} {:<8} {:<10} Requires", "Language", "Value", "Extension")];
+    for lang in Language::all() {
+        let value = lang.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default();
+        lines.push(format!(
+            "{:<11} {:<8} {:<10} {}",
+            lang.display_name(),
+            value,
+            format!(".{}", lang.extension()),
+            lang.required_commands().join(", ")
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_every_language() {
+        let lines = language_lines();
+        assert_eq!(lines.len(), Language::all().len() + 1);
+        assert_eq!(lines[1], "Rust        rs       .rs        cargo");
+        assert!(lines.contains(&"C++         cpp      .cpp       g++, make".to_string()));
+        assert!(lines.contains(&"JavaScript  js       .js        node, npm".to_string()));
+    }
+}
@@ -0,0 +1,174 @@
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process::{Command, Stdio};
+
+use clap::ValueEnum;
+
+use crate::models::{Difficulty, Language};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MenuChoice {
+    Show,
+    Init,
+    Test,
+    Submit,
+    Stats,
+    Difficulty,
+    Quit,
+}
+
+const MENU: &[(MenuChoice, &str)] = &[
+    (MenuChoice::Show, "Show today's challenge"),
+    (MenuChoice::Init, "Start a project for it"),
+    (MenuChoice::Test, "Run the tests"),
+    (MenuChoice::Submit, "Submit your solution"),
+    (MenuChoice::Stats, "Show your stats"),
+    (MenuChoice::Difficulty, "Change difficulty"),
+    (MenuChoice::Quit, "Quit"),
+];
+
+/// Maps the user's answer to a menu entry: its number, or `q` to quit.
+fn parse_choice(input: &str) -> Option<MenuChoice> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("q") {
+        return Some(MenuChoice::Quit);
+    }
+    let number: usize = input.parse().ok()?;
+    MENU.get(number.checked_sub(1)?).map(|(choice, _)| *choice)
+}
+
+/// The `codle` arguments that run `choice`, given the language or difficulty
+/// the user picked for it. `None` for `Quit`.
+fn command_args(choice: MenuChoice, value: Option<&str>) -> Option<Vec<String>> {
+    let command = match choice {
+        // A bare `codle` without a terminal on stdin shows the challenge.
+        MenuChoice::Show => vec![],
+        MenuChoice::Init => vec!["init".to_string()],
+        MenuChoice::Test => vec!["test".to_string()],
+        MenuChoice::Submit => vec!["submit".to_string()],
+        MenuChoice::Stats => vec!["stats".to_string()],
+        MenuChoice::Difficulty => vec!["difficulty".to_string()],
+        MenuChoice::Quit => return None,
+    };
+    Some(command.into_iter().chain(value.map(str::to_string)).collect())
+}
+
+/// Numbered menu over the everyday commands, for a bare `codle` in a terminal.
+/// Each choice runs as its own `codle` process, so a command that fails only
+/// ends itself, not the menu. Runs until the user quits or stdin closes.
+pub fn run_menu() {
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("Failed to find the codle executable: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        println!();
+        for (i, (_, label)) in MENU.iter().enumerate() {
+            println!("  {}. {}", i + 1, label);
+        }
+        let Some(answer) = prompt(&mut lines, "Choose an option: ") else {
+            return;
+        };
+        let Some(choice) = parse_choice(&answer) else {
+            println!("Enter a number from 1 to {}, or q to quit.", MENU.len());
+            continue;
+        };
+        println!();
+
+        let value = match choice {
+            MenuChoice::Init => {
+                let Some(language) = prompt_value::<Language>(&mut lines, "Language") else {
+                    return;
+                };
+                Some(language)
+            }
+            MenuChoice::Difficulty => {
+                let Some(level) = prompt_value::<Difficulty>(&mut lines, "Difficulty") else {
+                    return;
+                };
+                Some(level)
+            }
+            _ => None,
+        };
+        let Some(args) = command_args(choice, value.as_deref()) else {
+            return;
+        };
+
+        let stdin = if choice == MenuChoice::Show { Stdio::null() } else { Stdio::inherit() };
+        if let Err(e) = Command::new(&exe).args(&args).stdin(stdin).status() {
+            eprintln!("Failed to run codle {}: {}", args.join(" "), e);
+        }
+    }
+}
+
+fn prompt(lines: &mut impl Iterator<Item = io::Result<String>>, message: &str) -> Option<String> {
+    print!("{}", message);
+    let _ = io::stdout().flush();
+    lines.next()?.ok()
+}
+
+/// Asks for one of `T`'s command-line names until the answer parses, and
+/// returns that name. `None` when stdin closes.
+fn prompt_value<T: ValueEnum>(
+    lines: &mut impl Iterator<Item = io::Result<String>>,
+    what: &str,
+) -> Option<String> {
+    let names: Vec<String> = T::value_variants()
+        .iter()
+        .filter_map(|value| value.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    loop {
+        let answer = prompt(lines, &format!("{} ({}): ", what, names.join(", ")))?;
+        match T::from_str(answer.trim(), true) {
+            Ok(value) => return value.to_possible_value().map(|value| value.get_name().to_string()),
+            Err(_) => println!("Unknown {}: '{}'", what.to_lowercase(), answer.trim()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_choice_dispatch() {
+        let choices: Vec<Option<MenuChoice>> =
+            ["1", "2", " 3 ", "4", "5", "6", "7", "q", "Q"].iter().map(|s| parse_choice(s)).collect();
+        assert_eq!(
+            choices,
+            vec![
+                Some(MenuChoice::Show),
+                Some(MenuChoice::Init),
+                Some(MenuChoice::Test),
+                Some(MenuChoice::Submit),
+                Some(MenuChoice::Stats),
+                Some(MenuChoice::Difficulty),
+                Some(MenuChoice::Quit),
+                Some(MenuChoice::Quit),
+                Some(MenuChoice::Quit),
+            ]
+        );
+        assert_eq!(parse_choice("0"), None);
+        assert_eq!(parse_choice("8"), None);
+        assert_eq!(parse_choice("init"), None);
+    }
+
+    #[test]
+    fn test_command_args() {
+        assert_eq!(command_args(MenuChoice::Show, None), Some(vec![]));
+        assert_eq!(command_args(MenuChoice::Init, Some("py")), Some(vec!["init".to_string(), "py".to_string()]));
+        assert_eq!(command_args(MenuChoice::Submit, None), Some(vec!["submit".to_string()]));
+        assert_eq!(
+            command_args(MenuChoice::Difficulty, Some("hard")),
+            Some(vec!["difficulty".to_string(), "hard".to_string()])
+        );
+        assert_eq!(command_args(MenuChoice::Quit, None), None);
+    }
+}
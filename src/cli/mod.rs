@@ -3,16 +3,36 @@ mod init;
 mod difficulty_cmd;
 mod test;
 mod submit;
+mod stats;
+mod menu;
+mod challenges;
+mod self_test;
+mod hint;
+mod verify;
+mod color;
+mod languages;
+mod today;
 
-use chrono::Local;
-use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::models::{Difficulty, Language, config};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::lang::{failed_tests, ScaffoldOptions, TestSummary};
+use crate::models::{find_challenge, get_challenges_dir, Difficulty, Language, ProjectMetadata, config};
 
 #[derive(Parser)]
 #[command(name = "codle")]
 #[command(about = "Daily coding challenges", long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    /// Print today's challenge as JSON instead of the formatted view
+    #[arg(long, alias = "json")]
+    pub raw: bool,
+    /// Show the challenge from this tier instead of your configured one
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    pub difficulty: Option<Difficulty>,
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -20,32 +40,359 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     Init {
-        #[arg(value_enum)]
-        language: Language,
+        /// Language to scaffold, or `all` for one project per language
+        #[arg(value_parser = parse_init_target, required_unless_present = "lang_from_metadata")]
+        language: Option<InitTarget>,
+        /// Pack the scaffold into <challenge>.zip instead of a directory
+        #[arg(long)]
+        zip: bool,
+        /// Render the scaffold from <PATH>/<extension>/ instead of the built-in generator
+        #[arg(long, value_name = "PATH")]
+        template: Option<PathBuf>,
+        /// Scaffold with the challenge's reference solution and run its tests
+        #[arg(long, conflicts_with_all = ["zip", "template"])]
+        run_tests: bool,
+        /// Run the generated setup.sh once the scaffold is written
+        #[arg(long, conflicts_with_all = ["zip", "run_tests", "overwrite_tests_only"])]
+        run: bool,
+        /// Also write an .editorconfig matching the generated code's style
+        #[arg(long, conflicts_with = "template")]
+        editorconfig: bool,
+        /// Also generate a benchmark harness that runs the largest test input
+        #[arg(long, conflicts_with = "template")]
+        with_bench: bool,
+        /// Write the scaffold (or the `all` workspace) into <PATH>
+        #[arg(long, alias = "path", value_name = "PATH", conflicts_with_all = ["zip", "run_tests"])]
+        output_dir: Option<PathBuf>,
+        /// Replace the scaffold directory if it already exists and codle made
+        /// it, once the new scaffold is generated; with
+        /// --lang-from-metadata, re-scaffold despite uncommitted solution changes
+        #[arg(long, conflicts_with_all = ["zip", "run_tests", "overwrite_tests_only"])]
+        force: bool,
+        /// Refresh the tests and .codle.json of an existing project, keeping the solution
+        #[arg(
+            long,
+            conflicts_with_all = ["zip", "template", "run_tests", "editorconfig", "with_bench"]
+        )]
+        overwrite_tests_only: bool,
+        /// Scaffold today's Nth bonus challenge instead of the daily one (0);
+        /// it scores half and doesn't count towards the streak
+        #[arg(long, value_name = "N", default_value_t = 0, conflicts_with = "template")]
+        index: usize,
+        /// Use this tier's challenge for this project without changing your configured difficulty
+        #[arg(long, value_enum, value_name = "LEVEL")]
+        difficulty: Option<Difficulty>,
+        /// Regenerate setup.sh and the tests of the project in this directory
+        /// from the challenge and language in its .codle.json
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "language", "zip", "template", "run_tests", "run", "output_dir", "overwrite_tests_only", "index",
+                "difficulty",
+            ]
+        )]
+        lang_from_metadata: bool,
     },
     Difficulty {
         #[arg(value_enum)]
         level: Option<Difficulty>,
+        /// Show how many challenges each tier has
+        #[arg(long, conflicts_with = "level")]
+        list: bool,
+    },
+    Test {
+        /// Print only `passed/total`; the exit code reports success
+        #[arg(long)]
+        quiet: bool,
+        /// Test every project in the subdirectories of the current directory
+        #[arg(long, conflicts_with = "quiet")]
+        all: bool,
+        /// Show how long you've been working since `codle init`
+        #[arg(long, conflicts_with_all = ["quiet", "all"])]
+        since_init: bool,
+        /// Kill the tests after this many seconds instead of the tier's default
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+        /// Don't color the results, even on a terminal
+        #[arg(long)]
+        no_color: bool,
+    },
+    Submit {
+        /// Print how the BOSS score for this submission is computed
+        #[arg(long)]
+        explain: bool,
+        /// Submit even if this project isn't today's daily challenge
+        #[arg(long)]
+        force: bool,
+        /// Score this as practice, leaving the daily streak and BOSS score alone
+        #[arg(long)]
+        practice: bool,
+        /// Kill the tests after this many seconds instead of the tier's default
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+        /// Don't color the results, even on a terminal
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Reveal the next hint for this project's challenge; each costs a BOSS point
+    Hint,
+    /// Completions, average time, streaks and a streak calendar from your submission history
+    Stats {
+        /// Only count submissions in this language
+        #[arg(long, value_enum)]
+        language: Option<Language>,
     },
-    Test,
-    Submit,
+    /// Manage the challenge set
+    Challenges {
+        #[command(subcommand)]
+        command: ChallengesCommand,
+    },
+    /// Check that every challenge builds and its reference solutions pass
+    SelfTest {
+        /// Check every challenge in this language instead of its reference languages
+        #[arg(long, value_enum)]
+        language: Option<Language>,
+    },
+    /// Check a challenge JSON file and that every language can scaffold it
+    Verify {
+        /// The challenge file to check
+        path: PathBuf,
+    },
+    /// List the supported languages and the tools each one needs installed
+    Languages,
+    /// Today's challenge, whether it's done, and your streak and BOSS score
+    Today,
     Info,
 }
 
+#[derive(Subcommand)]
+pub enum ChallengesCommand {
+    /// Clone or pull the configured git repository of challenges
+    Sync {
+        /// Git URL of the challenge pack; saved for later syncs
+        #[arg(long)]
+        url: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum InitTarget {
+    Language(Language),
+    All,
+}
+
+/// What `codle init` produces once the challenge is loaded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InitMode {
+    Scaffold,
+    /// `--run`: scaffold, then run its setup.sh.
+    ScaffoldAndSetup,
+    /// `--zip`: pack the scaffold into an archive.
+    Zip,
+    /// `--run-tests`: check the reference solution passes the tests.
+    VerifyReference,
+}
+
+/// What `codle init` does with a project directory that already exists.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExistingDir {
+    Refuse,
+    /// `--force`: scaffold afresh and swap it in, if codle made the old one.
+    Replace,
+    /// `--overwrite-tests-only`: regenerate its tests, keeping the solution.
+    UpdateTests,
+}
+
+fn parse_init_target(value: &str) -> Result<InitTarget, String> {
+    if value.eq_ignore_ascii_case("all") {
+        return Ok(InitTarget::All);
+    }
+    Language::from_str(value, true).map(InitTarget::Language).map_err(|_| {
+        let names: Vec<String> = Language::value_variants()
+            .iter()
+            .filter_map(|lang| lang.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect();
+        format!("expected one of: {}, all", names.join(", "))
+    })
+}
+
 pub fn run(cli: Cli) {
     match cli.command {
-        None => show::show_challenge(),
-        Some(Commands::Init { language }) => init::init_challenge(language),
-        Some(Commands::Difficulty { level }) => difficulty_cmd::handle_difficulty(level),
-        Some(Commands::Test) => test::test_solution(),
-        Some(Commands::Submit) => submit::submit_solution(),
+        None if cli.raw || cli.difficulty.is_some() => show::show_challenge(cli.raw, cli.difficulty),
+        None if std::io::stdin().is_terminal() => menu::run_menu(),
+        None => show::show_challenge(false, None),
+        Some(Commands::Init { lang_from_metadata: true, editorconfig, with_bench, force, .. }) => {
+            init::rescaffold_from_metadata(
+                ScaffoldOptions { editorconfig, with_bench, ..ScaffoldOptions::default() },
+                force,
+            )
+        }
+        Some(Commands::Init { language: None, .. }) => unreachable!("clap requires a language here"),
+        Some(Commands::Init {
+            language: Some(language),
+            zip,
+            template,
+            run_tests,
+            run,
+            editorconfig,
+            with_bench,
+            output_dir,
+            force,
+            overwrite_tests_only,
+            index,
+            difficulty,
+            lang_from_metadata: false,
+        }) => init::init_challenge(
+            language,
+            if zip {
+                InitMode::Zip
+            } else if run_tests {
+                InitMode::VerifyReference
+            } else if run {
+                InitMode::ScaffoldAndSetup
+            } else {
+                InitMode::Scaffold
+            },
+            template.as_deref(),
+            ScaffoldOptions {
+                editorconfig,
+                with_bench,
+                challenge_index: index,
+                ..ScaffoldOptions::default()
+            },
+            output_dir,
+            if overwrite_tests_only {
+                ExistingDir::UpdateTests
+            } else if force {
+                ExistingDir::Replace
+            } else {
+                ExistingDir::Refuse
+            },
+            difficulty,
+        ),
+        Some(Commands::Difficulty { level, list }) => difficulty_cmd::handle_difficulty(level, list),
+        Some(Commands::Test { all: true, timeout, no_color, .. }) => test::test_all(timeout, no_color),
+        Some(Commands::Test { quiet, since_init, timeout, no_color, .. }) => {
+            test::test_solution(quiet, since_init, timeout, no_color)
+        }
+        Some(Commands::Submit { explain, force, practice, timeout, no_color }) => {
+            submit::submit_solution(explain, force, practice, timeout, no_color)
+        }
+        Some(Commands::Hint) => hint::show_hint(),
+        Some(Commands::Stats { language }) => stats::show_stats(language),
+        Some(Commands::Challenges { command: ChallengesCommand::Sync { url } }) => {
+            challenges::sync_challenges(url)
+        }
+        Some(Commands::SelfTest { language }) => self_test::self_test(language),
+        Some(Commands::Verify { path }) => verify::verify_challenge(&path),
+        Some(Commands::Languages) => languages::list_languages(),
+        Some(Commands::Today) => today::show_today(),
         Some(Commands::Info) => generic_info(),
     }
 }
 
+/// How long a project's tests may run: `--timeout` if given, then the
+/// challenge's `time_limit_ms`, then the default for the project's tier. The
+/// last two also get the language's startup allowance.
+fn test_timeout(metadata: &ProjectMetadata, timeout_secs: Option<u64>) -> Duration {
+    let time_limit_ms = find_challenge(&get_challenges_dir(), &metadata.challenge_name)
+        .and_then(|challenge| challenge.time_limit_ms);
+    resolve_timeout(timeout_secs, time_limit_ms, metadata.difficulty, metadata.language)
+}
+
+fn resolve_timeout(
+    timeout_secs: Option<u64>,
+    time_limit_ms: Option<u64>,
+    tier: Difficulty,
+    language: Language,
+) -> Duration {
+    match (timeout_secs, time_limit_ms) {
+        (Some(secs), _) => Duration::from_secs(secs),
+        (None, Some(ms)) => Duration::from_millis(ms) + language.test_startup_allowance(),
+        (None, None) => tier.default_test_timeout() + language.test_startup_allowance(),
+    }
+}
+
+/// Frames a test run's result.
+const BAR: &str = "========================================";
+
+/// The framed result of a test run: green when every test passed, red
+/// otherwise, with the failed tests listed by number or name.
+fn result_banner(summary: &TestSummary, language: Language, color: bool) -> Vec<String> {
+    let paint = if summary.total > 0 && summary.failed == 0 { color::green } else { color::red };
+    let mut lines = vec![paint(BAR, color)];
+    if summary.total == 0 {
+        lines.extend(no_results_lines(summary));
+    } else if summary.failed == 0 {
+        lines.push(paint(&format!("{}/{} tests passed", summary.passed, summary.total), color));
+    } else {
+        lines.push(paint(
+            &format!("{}/{} tests passed - {} failed", summary.passed, summary.total, summary.failed),
+            color,
+        ));
+        let failed = failed_tests(language, &summary.output);
+        if !failed.is_empty() {
+            lines.push(format!("Failed: {}", failed.join(", ")));
+        }
+    }
+    lines.push(paint(BAR, color));
+    lines
+}
+
+/// Why a run produced no test results: the diagnosis if there is one, then
+/// the compiler's errors, then a pointer to the raw output.
+fn no_results_lines(summary: &TestSummary) -> Vec<String> {
+    if let Some(diagnosis) = &summary.diagnosis {
+        return vec![diagnosis.clone()];
+    }
+    match &summary.compile_error {
+        Some(errors) => {
+            let mut lines = vec!["Your code doesn't compile:".to_string(), String::new()];
+            lines.extend(errors.lines().map(|line| format!("  {}", line)));
+            lines
+        }
+        None => vec!["No test results found. Check the output above for errors.".to_string()],
+    }
+}
+
+/// Accepted submissions per language, in `Language::all()` order, leaving
+/// out languages never submitted in. Empty if there are none.
+fn language_count_lines(user_config: &config::UserConfig) -> Vec<String> {
+    let counts: Vec<(Language, u32)> = Language::all()
+        .iter()
+        .filter_map(|lang| user_config.language_counts.get(lang).map(|&count| (*lang, count)))
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    if counts.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec!["Completions by language:".to_string()];
+    for (lang, count) in counts {
+        lines.push(format!("  {:<10} {}", lang.display_name(), count));
+    }
+    lines
+}
+
+/// `1h 2m 3s`, `2m 3s` or `3s`.
+fn format_duration(total_secs: i64) -> String {
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
 fn generic_info() {
     let user_config = config::load_config();
-    let today = Local::now().format("%Y-%m-%d").to_string();
+    let today = crate::models::today().format("%Y-%m-%d").to_string();
 
     if let Some(ref last_date) = user_config.last_completed_date {
         if last_date == &today {
@@ -59,4 +406,77 @@ fn generic_info() {
     println!("Challenges completed: {}", user_config.challenges_completed);
     println!("Current streak: {}", user_config.current_streak);
     println!("Longest streak: {}", user_config.longest_streak);
+    println!(
+        "Practice score: {} ({} solved)",
+        user_config.practice_score, user_config.practice_completed
+    );
+    let by_language = language_count_lines(&user_config);
+    if !by_language.is_empty() {
+        println!();
+        for line in by_language {
+            println!("{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeout_precedence() {
+        assert_eq!(resolve_timeout(None, None, Difficulty::Easy, Language::C), Duration::from_secs(5));
+        assert_eq!(resolve_timeout(None, None, Difficulty::Extreme, Language::C), Duration::from_secs(60));
+        assert_eq!(resolve_timeout(None, Some(2500), Difficulty::Easy, Language::C), Duration::from_millis(2500));
+        assert_eq!(resolve_timeout(Some(90), Some(2500), Difficulty::Easy, Language::C), Duration::from_secs(90));
+
+        // Cold toolchains get time to start on top of the limit, but not past `--timeout`.
+        assert_eq!(resolve_timeout(None, None, Difficulty::Easy, Language::Kt), Duration::from_secs(35));
+        assert_eq!(resolve_timeout(None, Some(2500), Difficulty::Easy, Language::Cs), Duration::from_millis(17500));
+        assert_eq!(resolve_timeout(Some(3), None, Difficulty::Easy, Language::Java), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_language_counts_listed_in_language_order() {
+        let mut user_config = config::UserConfig::default();
+        assert!(language_count_lines(&user_config).is_empty());
+
+        user_config.language_counts.insert(Language::Js, 1);
+        user_config.language_counts.insert(Language::Rs, 4);
+        user_config.language_counts.insert(Language::Go, 0);
+        assert_eq!(
+            language_count_lines(&user_config),
+            vec!["Completions by language:", "  Rust       4", "  JavaScript 1"]
+        );
+    }
+
+    #[test]
+    fn test_difficulty_override_parses() {
+        let cli = Cli::try_parse_from(["codle", "--difficulty", "hard"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.difficulty, Some(Difficulty::Hard));
+
+        let cli = Cli::try_parse_from(["codle", "init", "rs", "--difficulty", "extreme"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Init { difficulty: Some(Difficulty::Extreme), .. })));
+        assert!(Cli::try_parse_from(["codle", "--difficulty", "hard", "init", "rs"]).is_err());
+    }
+
+    #[test]
+    fn test_lang_from_metadata_needs_no_language() {
+        let cli = Cli::try_parse_from(["codle", "init", "--lang-from-metadata", "--force"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Init { language: None, lang_from_metadata: true, force: true, .. })));
+        assert!(Cli::try_parse_from(["codle", "init", "--lang-from-metadata", "py"]).is_err());
+        assert!(Cli::try_parse_from(["codle", "init"]).is_err());
+    }
+
+    #[test]
+    fn test_format_duration_boundaries() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(59), "59s");
+        assert_eq!(format_duration(60), "1m 0s");
+        assert_eq!(format_duration(754), "12m 34s");
+        assert_eq!(format_duration(3599), "59m 59s");
+        assert_eq!(format_duration(3600), "1h 0m 0s");
+        assert_eq!(format_duration(3661), "1h 1m 1s");
+    }
 }
@@ -0,0 +1,327 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use super::init::scaffold_and_setup;
+use crate::lang::{check_language_support, run_tests, ScaffoldOptions, TestSummary};
+use crate::models::{
+    get_challenges_dir, load_all_challenges, parse_signature, validate_challenge, Challenge,
+    Difficulty, FunctionSignature, Language,
+};
+
+/// Scaffolds and tests every challenge in the challenges directory, with its
+/// reference solutions where it has them, and prints a table of the results.
+pub fn self_test(language: Option<Language>) {
+    let challenges_dir = get_challenges_dir();
+    println!("Checking challenges in {}", challenges_dir.display());
+    println!();
+
+    let rows = run_self_test(&challenges_dir, language);
+    for line in table_lines(&rows) {
+        println!("{}", line);
+    }
+
+    let failed = rows
+        .iter()
+        .filter(|row| matches!(row.outcome, Outcome::Failed(_)))
+        .count();
+    let skipped = rows
+        .iter()
+        .filter(|row| matches!(row.outcome, Outcome::Skipped(_)))
+        .count();
+    println!();
+    println!(
+        "{} passed, {} failed, {} skipped",
+        rows.len() - failed - skipped,
+        failed,
+        skipped
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Passed(String),
+    Failed(String),
+    Skipped(String),
+}
+
+#[derive(Debug)]
+struct Row {
+    challenge: String,
+    language: Option<Language>,
+    outcome: Outcome,
+}
+
+/// One row per challenge and language checked. A challenge is checked in
+/// `language` if given, otherwise in each language it has a reference
+/// solution for, or in Rust if it has none. Without a reference solution only
+/// the generated project has to build.
+fn run_self_test(challenges_dir: &Path, language: Option<Language>) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for (difficulty, source, challenge) in load_all_challenges(challenges_dir) {
+        let challenge = match challenge {
+            Ok(challenge) => challenge,
+            Err(e) => {
+                rows.push(Row {
+                    challenge: source,
+                    language: None,
+                    outcome: Outcome::Failed(format!("invalid JSON: {}", e)),
+                });
+                continue;
+            }
+        };
+
+        let checked = match parse_signature(&challenge.function_signature) {
+            Err(e) => Err(format!("bad signature: {}", e)),
+            Ok(sig) => validate_challenge(&challenge, &sig)
+                .map(|_| sig)
+                .map_err(|e| format!("invalid tests: {}", e)),
+        };
+        let sig = match checked {
+            Ok(sig) => sig,
+            Err(e) => {
+                rows.push(Row {
+                    challenge: challenge.name.clone(),
+                    language: None,
+                    outcome: Outcome::Failed(e),
+                });
+                continue;
+            }
+        };
+
+        let languages: Vec<Language> = match language {
+            Some(language) => vec![language],
+            None => {
+                let with_reference: Vec<Language> = Language::all()
+                    .iter()
+                    .copied()
+                    .filter(|l| challenge.reference_solutions.contains_key(l))
+                    .collect();
+                if with_reference.is_empty() {
+                    vec![Language::Rs]
+                } else {
+                    with_reference
+                }
+            }
+        };
+
+        for language in languages {
+            let outcome = match check_language_support(&sig, language) {
+                Err(e) => Outcome::Skipped(e),
+                Ok(()) => check_challenge(&challenge, &sig, language, difficulty),
+            };
+            rows.push(Row {
+                challenge: challenge.name.clone(),
+                language: Some(language),
+                outcome,
+            });
+        }
+    }
+    rows
+}
+
+fn check_challenge(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+) -> Outcome {
+    let reference = challenge.reference_solutions.get(&language);
+    let dir = std::env::temp_dir().join(format!(
+        "codle-self-test-{}-{}-{}",
+        sig.name,
+        language.extension(),
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    let options = ScaffoldOptions {
+        solution_body: reference.cloned(),
+        ..ScaffoldOptions::default()
+    };
+    let result = fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))
+        .and_then(|_| {
+            scaffold_and_setup(challenge, sig, language, difficulty, &options, None, &dir)
+        })
+        .and_then(|_| match reference {
+            Some(_) => {
+                let timeout = super::resolve_timeout(None, challenge.time_limit_ms, difficulty, language);
+                run_tests(language, &dir, Some(timeout)).map(reference_outcome)
+            }
+            None => Ok(build_outcome(language, &dir)),
+        });
+    let _ = fs::remove_dir_all(&dir);
+
+    match result {
+        Ok(outcome) => outcome,
+        // setup.sh's own check for the toolchain, not a problem with the challenge.
+        Err(e) if e.contains("is not installed") => Outcome::Skipped(format!(
+            "{} toolchain not installed",
+            language.display_name()
+        )),
+        Err(e) => Outcome::Failed(e.lines().next().unwrap_or_default().to_string()),
+    }
+}
+
+fn reference_outcome(summary: TestSummary) -> Outcome {
+    if let Some(errors) = &summary.compile_error {
+        let first = errors.lines().next().unwrap_or_default().trim();
+        return Outcome::Failed(format!("doesn't compile: {}", first));
+    }
+    if summary.total == 0 {
+        return Outcome::Failed(
+            summary
+                .diagnosis
+                .unwrap_or_else(|| "no test results".to_string()),
+        );
+    }
+    let detail = format!("reference passes {}/{}", summary.passed, summary.total);
+    if summary.failed == 0 {
+        Outcome::Passed(detail)
+    } else {
+        Outcome::Failed(detail)
+    }
+}
+
+/// The stub body fails or crashes the tests, so without a reference solution
+/// only the build is checked.
+fn build_outcome(language: Language, dir: &Path) -> Outcome {
+    let Some((program, args)) = language.build_command() else {
+        return Outcome::Passed("set up".to_string());
+    };
+    match Command::new(program).args(args).current_dir(dir).output() {
+        Ok(output) if output.status.success() => Outcome::Passed("builds".to_string()),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let error = stderr
+                .lines()
+                .find(|line| line.contains("error"))
+                .unwrap_or("build failed");
+            Outcome::Failed(format!("doesn't build: {}", error.trim()))
+        }
+        Err(e) => Outcome::Failed(format!("Failed to run {}: {}", program, e)),
+    }
+}
+
+fn table_lines(rows: &[Row]) -> Vec<String> {
+    let cells: Vec<(String, &str, String)> = rows
+        .iter()
+        .map(|row| {
+            let outcome = match &row.outcome {
+                Outcome::Passed(detail) => format!("ok ({})", detail),
+                Outcome::Failed(detail) => format!("FAIL: {}", detail),
+                Outcome::Skipped(detail) => format!("skipped: {}", detail),
+            };
+            let language = row.language.map_or("-", |l| l.display_name());
+            (row.challenge.clone(), language, outcome)
+        })
+        .collect();
+
+    let name_width = cells
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .fold("Challenge".len(), usize::max);
+    let language_width = cells
+        .iter()
+        .map(|(_, language, _)| language.len())
+        .fold("Language".len(), usize::max);
+
+    let mut lines = vec![format!(
+        "{:<name_width$}  {:<language_width$}  Result",
+        "Challenge", "Language"
+    )];
+    lines.extend(cells.iter().map(|(name, language, outcome)| {
+        format!(
+            "{:<name_width$}  {:<language_width$}  {}",
+            name, language, outcome
+        )
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_reports_broken_challenge() {
+        if Command::new("gcc").arg("--version").output().is_err()
+            || Command::new("make").arg("--version").output().is_err()
+        {
+            return;
+        }
+        let dir =
+            std::env::temp_dir().join(format!("codle-self-test-fixture-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("easy")).unwrap();
+        fs::create_dir_all(dir.join("medium")).unwrap();
+        let challenge = |name: &str, signature: &str, body: &str| {
+            serde_json::json!({
+                "name": name,
+                "difficulty": 1,
+                "short_description": "",
+                "description": "",
+                "function_signature": signature,
+                "tests": [{"input": {"a": 1, "b": 2}, "expected": 3}],
+                "reference_solutions": {"c": body},
+            })
+            .to_string()
+        };
+        fs::write(
+            dir.join("easy/add.json"),
+            challenge("Add", "fn add(a: i32, b: i32) -> i32", "return a + b;"),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("easy/sub.json"),
+            challenge("Sub", "fn sub(a: i32, b: i32) -> i32", "return a - b;"),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("medium/bad.json"),
+            challenge("Bad", "fn bad(a: i32, b: Map) -> i32", "return a;"),
+        )
+        .unwrap();
+        fs::write(dir.join("medium/broken.json"), "{").unwrap();
+
+        let rows = run_self_test(&dir, None);
+        let _ = fs::remove_dir_all(&dir);
+
+        let summary: Vec<(&str, Option<Language>, &Outcome)> = rows
+            .iter()
+            .map(|row| (row.challenge.as_str(), row.language, &row.outcome))
+            .collect();
+        assert_eq!(summary.len(), 4);
+        assert_eq!(
+            summary[0],
+            (
+                "Add",
+                Some(Language::C),
+                &Outcome::Passed("reference passes 1/1".to_string())
+            )
+        );
+        assert_eq!(
+            summary[1],
+            (
+                "Sub",
+                Some(Language::C),
+                &Outcome::Failed("reference passes 0/1".to_string())
+            )
+        );
+        assert_eq!(summary[2].0, "Bad");
+        assert!(matches!(summary[2].2, Outcome::Failed(e) if e.starts_with("bad signature")));
+        assert!(summary[3].0.ends_with("broken.json"));
+        assert!(matches!(summary[3].2, Outcome::Failed(e) if e.starts_with("invalid JSON")));
+
+        let table = table_lines(&rows);
+        assert_eq!(
+            table[0].split_whitespace().collect::<Vec<_>>(),
+            ["Challenge", "Language", "Result"]
+        );
+        assert!(table[1].starts_with("Add "));
+        assert!(table[1].ends_with("  C         ok (reference passes 1/1)"));
+    }
+}
@@ -1,10 +1,36 @@
-use crate::models::{load_daily_challenge, config};
+use serde::Serialize;
+
+use crate::models::{load_daily_challenge, config, Challenge, Difficulty, TestCase};
 use crate::display::display_challenge;
 
-pub fn show_challenge() {
-    let user_config = config::load_config();
+/// What `--raw` prints: the challenge without the reference solutions,
+/// explanation and hints, which would spoil it.
+#[derive(Serialize)]
+struct RawChallenge<'a> {
+    name: &'a str,
+    difficulty: u8,
+    short_description: &'a str,
+    description: &'a str,
+    function_signature: &'a str,
+    tests: &'a [TestCase],
+    chars_as_string: bool,
+    c_string_len: bool,
+    time_limit_ms: Option<u64>,
+}
+
+/// Today's challenge at `difficulty`, or at the configured tier if `None`.
+pub fn show_challenge(raw: bool, difficulty: Option<Difficulty>) {
+    let user_config = config::load_effective_config();
+    let difficulty = difficulty.unwrap_or(user_config.difficulty);
 
-    match load_daily_challenge(user_config.difficulty) {
+    match load_daily_challenge(difficulty, &user_config.history) {
+        Ok(challenge) if raw => match raw_json(&challenge) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize challenge: {}", e);
+                std::process::exit(1);
+            }
+        },
         Ok(challenge) => {
             display_challenge(&challenge);
         }
@@ -14,3 +40,47 @@ pub fn show_challenge() {
         }
     }
 }
+
+fn raw_json(challenge: &Challenge) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&RawChallenge {
+        name: &challenge.name,
+        difficulty: challenge.difficulty,
+        short_description: &challenge.short_description,
+        description: &challenge.description,
+        function_signature: &challenge.function_signature,
+        tests: &challenge.tests,
+        chars_as_string: challenge.chars_as_string,
+        c_string_len: challenge.c_string_len,
+        time_limit_ms: challenge.time_limit_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_json_leaves_out_spoilers() {
+        let challenge: Challenge = serde_json::from_str(
+            r#"{
+                "name": "Two Sum",
+                "difficulty": 2,
+                "short_description": "",
+                "description": "",
+                "function_signature": "fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>",
+                "tests": [{"input": {"nums": [2, 7], "target": 9}, "expected": [0, 1]}],
+                "reference_solutions": {"py": "    return [0, 1]"},
+                "explanation": "Use a hash map.",
+                "hints": ["Think about complements."]
+            }"#,
+        )
+        .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&raw_json(&challenge).unwrap()).unwrap();
+        assert_eq!(json["name"], "Two Sum");
+        assert_eq!(json["tests"][0]["expected"], serde_json::json!([0, 1]));
+        for field in ["reference_solutions", "explanation", "hints"] {
+            assert!(json.get(field).is_none(), "{}", field);
+        }
+    }
+}
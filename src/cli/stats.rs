@@ -0,0 +1,220 @@
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, Days, NaiveDate};
+
+use crate::models::config::{self, Completion};
+use crate::models::{today, Difficulty, Language};
+
+/// How many weeks the streak calendar covers, ending with the current one.
+const CALENDAR_WEEKS: u64 = 8;
+
+pub fn show_stats(language: Option<Language>) {
+    let user_config = config::load_effective_config();
+    let today = today();
+    let stats = compute_stats(&user_config.history, language, today);
+    let color = super::color::enabled(false, user_config.color);
+
+    match language {
+        Some(lang) => println!("Stats for {}", lang.display_name()),
+        None => println!("Stats for all languages"),
+    }
+    println!();
+
+    if stats.completions == 0 {
+        println!("No completed challenges yet.");
+    } else {
+        let average = stats
+            .average_secs
+            .map_or_else(|| "unknown".to_string(), super::format_duration);
+        println!("Completions:    {}", stats.completions);
+        println!("Average time:   {}", average);
+        println!("Current streak: {} day(s)", stats.current_streak);
+        println!("Best streak:    {} day(s)", stats.best_streak);
+        println!();
+        for line in calendar_lines(&stats.daily_dates, today, color) {
+            println!("{}", line);
+        }
+        println!();
+        println!("By difficulty:");
+        for (difficulty, count) in &stats.by_difficulty {
+            println!("  {:<8} {}", difficulty.display_name(), count);
+        }
+    }
+
+    // Practice isn't in the history, so it can't be split by language.
+    if language.is_none() && user_config.practice_completed > 0 {
+        println!();
+        println!(
+            "Practice:       {} solved, {} points",
+            user_config.practice_completed, user_config.practice_score
+        );
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Stats {
+    completions: usize,
+    /// Mean of the recorded solve times; `None` if none were recorded.
+    average_secs: Option<i64>,
+    /// Longest run of consecutive days with a daily completion.
+    best_streak: u32,
+    /// The run ending today, or yesterday if today isn't done yet.
+    current_streak: u32,
+    by_difficulty: Vec<(Difficulty, usize)>,
+    /// Days with a daily (not bonus) completion.
+    daily_dates: BTreeSet<NaiveDate>,
+}
+
+fn compute_stats(history: &[Completion], language: Option<Language>, today: NaiveDate) -> Stats {
+    let entries: Vec<&Completion> = history
+        .iter()
+        .filter(|c| language.is_none_or(|lang| c.language == lang))
+        .collect();
+
+    let durations: Vec<i64> = entries.iter().filter_map(|c| c.duration_secs).collect();
+    let average_secs = (!durations.is_empty())
+        .then(|| durations.iter().sum::<i64>() / durations.len() as i64);
+
+    let by_difficulty = Difficulty::all()
+        .iter()
+        .map(|&difficulty| {
+            let count = entries.iter().filter(|c| c.difficulty == difficulty).count();
+            (difficulty, count)
+        })
+        .collect();
+
+    let daily_dates: BTreeSet<NaiveDate> = entries
+        .iter()
+        .filter(|c| !c.bonus)
+        .filter_map(|c| NaiveDate::parse_from_str(&c.date, "%Y-%m-%d").ok())
+        .collect();
+    Stats {
+        completions: entries.len(),
+        average_secs,
+        best_streak: longest_daily_run(&daily_dates),
+        current_streak: current_daily_run(&daily_dates, today),
+        by_difficulty,
+        daily_dates,
+    }
+}
+
+fn longest_daily_run(dates: &BTreeSet<NaiveDate>) -> u32 {
+    let mut best = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &date in dates {
+        run = match previous {
+            Some(prev) if prev.succ_opt() == Some(date) => run + 1,
+            _ => 1,
+        };
+        best = best.max(run);
+        previous = Some(date);
+    }
+    best
+}
+
+fn current_daily_run(dates: &BTreeSet<NaiveDate>, today: NaiveDate) -> u32 {
+    let mut day = if dates.contains(&today) { Some(today) } else { today.pred_opt() };
+    let mut run = 0;
+    while let Some(date) = day.filter(|d| dates.contains(d)) {
+        run += 1;
+        day = date.pred_opt();
+    }
+    run
+}
+
+/// A GitHub-style grid of the last `CALENDAR_WEEKS` weeks: a row per weekday
+/// and a column per week, with `■` for days with a daily completion and `·`
+/// for days without. Days after `today` are left blank.
+fn calendar_lines(dates: &BTreeSet<NaiveDate>, today: NaiveDate, color: bool) -> Vec<String> {
+    let monday = today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+    let start = monday - Days::new((CALENDAR_WEEKS - 1) * 7);
+    let done = super::color::green("■", color);
+    let missed = super::color::dim("·", color);
+
+    let mut lines = vec![format!("Last {} weeks:", CALENDAR_WEEKS)];
+    for (weekday, label) in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].iter().enumerate() {
+        let cells: Vec<&str> = (0..CALENDAR_WEEKS)
+            .map(|week| start + Days::new(week * 7 + weekday as u64))
+            .map(|date| match date {
+                _ if date > today => " ",
+                _ if dates.contains(&date) => done.as_str(),
+                _ => missed.as_str(),
+            })
+            .collect();
+        lines.push(format!("  {} {}", label, cells.join(" ").trim_end()));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completion(date: &str, language: Language, difficulty: Difficulty, secs: Option<i64>) -> Completion {
+        Completion {
+            date: date.to_string(),
+            challenge_name: "Fixture".to_string(),
+            language,
+            difficulty,
+            points: 3,
+            duration_secs: secs,
+            bonus: false,
+        }
+    }
+
+    #[test]
+    fn test_filter_by_language() {
+        let history = vec![
+            completion("2026-03-01", Language::Rs, Difficulty::Easy, Some(60)),
+            completion("2026-03-02", Language::Py, Difficulty::Medium, Some(600)),
+            completion("2026-03-03", Language::Rs, Difficulty::Medium, Some(180)),
+            completion("2026-03-04", Language::Rs, Difficulty::Hard, None),
+            completion("2026-03-06", Language::C, Difficulty::Hard, Some(30)),
+        ];
+
+        let today = NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        let rust = compute_stats(&history, Some(Language::Rs), today);
+        assert_eq!(
+            rust,
+            Stats {
+                completions: 3,
+                average_secs: Some(120),
+                best_streak: 2,
+                current_streak: 0,
+                by_difficulty: vec![
+                    (Difficulty::Easy, 1),
+                    (Difficulty::Medium, 1),
+                    (Difficulty::Hard, 1),
+                    (Difficulty::Extreme, 0),
+                ],
+                daily_dates: rust.daily_dates.clone(),
+            }
+        );
+
+        let all = compute_stats(&history, None, today);
+        assert_eq!(all.completions, 5);
+        assert_eq!(all.best_streak, 4);
+        assert_eq!(all.current_streak, 1);
+        assert_eq!(all.by_difficulty[2], (Difficulty::Hard, 2));
+
+        assert_eq!(compute_stats(&history, Some(Language::Java), today).completions, 0);
+    }
+
+    #[test]
+    fn test_calendar_marks_completed_days() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        // 2026-03-04 is a Wednesday, so Thursday to Sunday of this week are blank.
+        let dates = BTreeSet::from([date("2026-03-02"), date("2026-03-03"), date("2026-01-12")]);
+        let lines = calendar_lines(&dates, date("2026-03-04"), false);
+
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "Last 8 weeks:");
+        assert_eq!(lines[1], "  Mon ■ · · · · · · ■");
+        assert_eq!(lines[2], "  Tue · · · · · · · ■");
+        assert_eq!(lines[3], "  Wed · · · · · · · ·");
+        assert_eq!(lines[4], "  Thu · · · · · · ·");
+        assert_eq!(current_daily_run(&dates, date("2026-03-04")), 2);
+        assert_eq!(current_daily_run(&dates, date("2026-03-05")), 0);
+    }
+}
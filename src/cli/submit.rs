@@ -1,11 +1,15 @@
 use std::env;
 
-use chrono::{DateTime, Local};
+use chrono::NaiveDate;
 
-use crate::models::{calculate_boss_score, config, project};
+use crate::models::{
+    bonus_boss_score, calculate_boss_score, config, explain_boss_score, find_challenge,
+    get_challenges_dir, load_daily_challenge_at, project, today, Difficulty, ProjectMetadata,
+};
+use crate::models::config::{Completion, UserConfig};
 use crate::lang::run_tests;
 
-pub fn submit_solution() {
+pub fn submit_solution(explain: bool, force: bool, practice: bool, timeout_secs: Option<u64>, no_color: bool) {
     let current_dir = env::current_dir().unwrap_or_else(|e| {
         eprintln!("Failed to get current directory: {}", e);
         std::process::exit(1);
@@ -19,17 +23,32 @@ pub fn submit_solution() {
         }
     };
 
-    let mut user_config = config::load_config();
-    let today = Local::now().format("%Y-%m-%d").to_string();
-
-    if let Some(ref last_date) = user_config.last_completed_date {
-        if last_date == &today {
-            println!("You've already completed today's challenge!");
-            println!();
-            println!("Come back tomorrow for a new challenge.");
-            println!("BOSS Score: {} | Challenges completed: {}",
-                user_config.boss_score, user_config.challenges_completed);
-            return;
+    let bonus = !practice && metadata.challenge_index > 0;
+    let user_config = config::load_config();
+    let date = today().format("%Y-%m-%d").to_string();
+
+    if bonus && bonus_already_completed(&user_config, &metadata, &date) {
+        println!("You've already completed this bonus challenge today!");
+        return;
+    }
+
+    if !practice && !bonus && user_config.last_completed_date.as_deref() == Some(date.as_str()) {
+        println!("You've already completed today's challenge!");
+        println!();
+        println!("Come back tomorrow for a new challenge.");
+        println!("BOSS Score: {} | Challenges completed: {}",
+            user_config.boss_score, user_config.challenges_completed);
+        return;
+    }
+
+    if !force && !practice {
+        let daily = load_daily_challenge_at(metadata.difficulty, metadata.challenge_index, &user_config.history)
+            .map(|c| c.name);
+        if let Err(e) = daily.and_then(|name| check_daily_challenge(&metadata, &name)) {
+            eprintln!("{}", e);
+            eprintln!();
+            eprintln!("Submission rejected. Use `codle submit --force` to submit anyway.");
+            std::process::exit(1);
         }
     }
 
@@ -40,7 +59,8 @@ pub fn submit_solution() {
     );
     println!();
 
-    let summary = match run_tests(metadata.language) {
+    let timeout = super::test_timeout(&metadata, timeout_secs);
+    let summary = match run_tests(metadata.language, &current_dir, Some(timeout)) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to run tests: {}", e);
@@ -53,90 +73,71 @@ pub fn submit_solution() {
         println!();
     }
 
-    if summary.total == 0 {
-        println!("========================================");
-        println!("No test results found. Check the output above for errors.");
-        println!("========================================");
+    let color = super::color::enabled(no_color, config::load_effective_config().color);
+    if summary.total == 0 || summary.failed > 0 {
+        for line in super::result_banner(&summary, metadata.language, color) {
+            println!("{}", line);
+        }
         println!();
+    }
+
+    if summary.total == 0 {
         println!("Submission rejected: could not verify tests.");
         std::process::exit(1);
     }
 
     if summary.failed > 0 {
-        println!("========================================");
-        println!(
-            "{}/{} tests passed - {} failed",
-            summary.passed, summary.total, summary.failed
-        );
-        println!("========================================");
-        println!();
         println!("Submission rejected: all tests must pass before submitting.");
+        if tracks_difficulty(&user_config, &metadata, practice) {
+            let mut updated = user_config.clone();
+            let tier_change = auto_difficulty_after_failure(&mut updated, &date);
+            match config::save_config(&updated) {
+                Ok(()) => print_tier_change(tier_change.as_deref()),
+                Err(e) => eprintln!("Failed to save progress: {}", e),
+            }
+        }
         std::process::exit(1);
     }
 
-    // All tests passed - compute streak
-    let yesterday = (Local::now() - chrono::Duration::days(1))
+    let yesterday = (today() - chrono::Duration::days(1))
         .format("%Y-%m-%d")
         .to_string();
+    let elapsed_secs = metadata.seconds_since_init();
 
-    let streak = if let Some(ref last_date) = user_config.last_completed_date {
-        if last_date == &yesterday {
-            user_config.current_streak + 1
-        } else {
-            1
-        }
+    // Only the saved copy counts: if the save fails, nothing (including
+    // last_completed_date) is recorded and the submission can be retried.
+    let (mut updated, streak, points) =
+        record_submission(&user_config, &metadata, &date, &yesterday, elapsed_secs, practice);
+    let tier_change = if tracks_difficulty(&user_config, &metadata, practice) {
+        let skipped = skipped_days(user_config.last_completed_date.as_deref(), today());
+        auto_difficulty_after_pass(&mut updated, skipped, &date)
     } else {
-        1
+        None
     };
-
     let streak_bonus = streak.min(5);
-    let points = calculate_boss_score(
-        metadata.challenge_difficulty,
-        &metadata.difficulty,
-        streak,
-    );
-
-    user_config.boss_score += points;
-    user_config.challenges_completed += 1;
-    user_config.last_completed_date = Some(today);
-    user_config.current_streak = streak;
-    if streak > user_config.longest_streak {
-        user_config.longest_streak = streak;
-    }
 
-    if let Err(e) = config::save_config(&user_config) {
+    if let Err(e) = config::save_config(&updated) {
         eprintln!("Failed to save progress: {}", e);
+        eprintln!();
+        eprintln!("Your tests passed, but this submission was not recorded.");
+        eprintln!("Your previous progress is unchanged, so it's safe to run `codle submit` again.");
         std::process::exit(1);
     }
+    let user_config = updated;
 
-    // Calculate time taken
-    let submit_time = Local::now();
-    let time_display = if let Some(ref init_time_str) = metadata.initialized_at {
-        if let Ok(init_time) = DateTime::parse_from_rfc3339(init_time_str) {
-            let duration = submit_time.signed_duration_since(init_time);
-            let total_secs = duration.num_seconds();
-            let hours = total_secs / 3600;
-            let minutes = (total_secs % 3600) / 60;
-            let seconds = total_secs % 60;
-
-            if hours > 0 {
-                format!("{}h {}m {}s", hours, minutes, seconds)
-            } else if minutes > 0 {
-                format!("{}m {}s", minutes, seconds)
-            } else {
-                format!("{}s", seconds)
-            }
-        } else {
-            "unknown".to_string()
-        }
-    } else {
-        "unknown".to_string()
-    };
+    let time_display = elapsed_secs.map_or_else(|| "unknown".to_string(), super::format_duration);
 
     // Display results
-    println!("========================================");
-    println!("  CHALLENGE COMPLETE!");
-    println!("========================================");
+    let heading = if practice {
+        "  PRACTICE COMPLETE!"
+    } else if bonus {
+        "  BONUS CHALLENGE COMPLETE!"
+    } else {
+        "  CHALLENGE COMPLETE!"
+    };
+    println!("{}", super::color::green(super::BAR, color));
+    println!("{}", super::color::green(heading, color));
+    println!("{}", super::color::green(super::BAR, color));
     println!();
     println!("  Challenge:  {}", metadata.challenge_name);
     println!("  Language:   {}", metadata.language.display_name());
@@ -144,16 +145,446 @@ pub fn submit_solution() {
     println!("  Tests:      {}/{} passed", summary.passed, summary.total);
     println!("  Time taken: {}", time_display);
     println!();
-    println!(
-        "  Score: {} (challenge) + {} (tier) + {} (streak) = +{}",
-        metadata.challenge_difficulty,
-        metadata.difficulty.tier_offset(),
-        streak_bonus,
-        points
-    );
-    println!("  Streak:     {} day(s)", streak);
-    println!("  BOSS Score: {}", user_config.boss_score);
-    println!("  Completed:  {} challenges total", user_config.challenges_completed);
+    if bonus {
+        println!(
+            "  Score: half of ({} (challenge) + {} (tier){}) = +{}",
+            metadata.challenge_difficulty,
+            metadata.difficulty.tier_offset(),
+            match metadata.hints_revealed {
+                0 => String::new(),
+                hints => format!(" - {} (hints)", hints),
+            },
+            points
+        );
+    } else {
+        let hint_penalty = match metadata.hints_revealed {
+            0 => String::new(),
+            hints => format!(" - {} (hints)", hints),
+        };
+        println!(
+            "  Score: {} (challenge) + {} (tier) + {} (streak){} = +{}",
+            metadata.challenge_difficulty,
+            metadata.difficulty.tier_offset(),
+            streak_bonus,
+            hint_penalty,
+            points
+        );
+    }
+    if practice {
+        println!("  Practice score: {}", user_config.practice_score);
+        println!("  Practiced:  {} challenges total", user_config.practice_completed);
+        println!("  (Practice doesn't affect your streak or BOSS score.)");
+    } else if bonus {
+        println!("  BOSS Score: {}", user_config.boss_score);
+        println!("  Completed:  {} challenges total", user_config.challenges_completed);
+        println!("  (Bonus challenges don't count towards your streak.)");
+    } else {
+        println!("  Streak:     {} day(s)", streak);
+        println!("  BOSS Score: {}", user_config.boss_score);
+        println!("  Completed:  {} challenges total", user_config.challenges_completed);
+    }
     println!();
     println!("========================================");
+    print_tier_change(tier_change.as_deref());
+
+    let explanation = find_challenge(&get_challenges_dir(), &metadata.challenge_name)
+        .and_then(|challenge| challenge.explanation);
+    for line in explanation_lines(explanation.as_deref()) {
+        println!("{}", line);
+    }
+
+    if explain {
+        println!();
+        println!("Score breakdown:");
+        let hints = metadata.hints_revealed;
+        for line in explain_boss_score(metadata.challenge_difficulty, &metadata.difficulty, streak, hints) {
+            println!("  {}", line);
+        }
+        if bonus {
+            println!("  bonus challenge: halved (at least 1) = {}", points);
+        }
+    }
+}
+
+/// `user_config` with a passing submission recorded, plus the streak it
+/// counts towards and the points it earned. Practice earns no streak bonus and
+/// only adds to the practice totals, leaving the daily state as it was. A
+/// bonus challenge earns `bonus_boss_score` and is kept out of the streak.
+fn record_submission(
+    user_config: &UserConfig,
+    metadata: &ProjectMetadata,
+    today: &str,
+    yesterday: &str,
+    duration_secs: Option<i64>,
+    practice: bool,
+) -> (UserConfig, u32, u32) {
+    let mut updated = user_config.clone();
+    *updated.language_counts.entry(metadata.language).or_insert(0) += 1;
+
+    if practice {
+        let points =
+            calculate_boss_score(metadata.challenge_difficulty, &metadata.difficulty, 0, metadata.hints_revealed);
+        updated.practice_score += points;
+        updated.practice_completed += 1;
+        return (updated, 0, points);
+    }
+
+    if metadata.challenge_index > 0 {
+        let points = bonus_boss_score(metadata.challenge_difficulty, &metadata.difficulty, metadata.hints_revealed);
+        updated.boss_score += points;
+        updated.challenges_completed += 1;
+        updated.history.push(Completion {
+            date: today.to_string(),
+            challenge_name: metadata.challenge_name.clone(),
+            language: metadata.language,
+            difficulty: metadata.difficulty,
+            points,
+            duration_secs,
+            bonus: true,
+        });
+        return (updated, 0, points);
+    }
+
+    let streak = match &user_config.last_completed_date {
+        Some(last_date) if last_date == yesterday => user_config.current_streak + 1,
+        _ => 1,
+    };
+    let points =
+        calculate_boss_score(metadata.challenge_difficulty, &metadata.difficulty, streak, metadata.hints_revealed);
+
+    updated.boss_score += points;
+    updated.challenges_completed += 1;
+    updated.history.push(Completion {
+        date: today.to_string(),
+        challenge_name: metadata.challenge_name.clone(),
+        language: metadata.language,
+        difficulty: metadata.difficulty,
+        points,
+        duration_secs,
+        bonus: false,
+    });
+    updated.last_completed_date = Some(today.to_string());
+    updated.current_streak = streak;
+    updated.longest_streak = updated.longest_streak.max(streak);
+    (updated, streak, points)
+}
+
+/// Daily challenges in a row, on consecutive days, that `auto_difficulty`
+/// wants at a tier before moving up from it.
+const PROMOTE_AFTER: usize = 5;
+
+/// Days with a failed submission or none at all that make `auto_difficulty`
+/// move down.
+const DEMOTE_AFTER: u32 = 3;
+
+/// Only daily challenges at the configured tier count towards
+/// `auto_difficulty`, so practice, bonus challenges and projects from a
+/// `.codlerc` override leave it alone.
+fn tracks_difficulty(user_config: &UserConfig, metadata: &ProjectMetadata, practice: bool) -> bool {
+    user_config.auto_difficulty
+        && !practice
+        && metadata.challenge_index == 0
+        && metadata.difficulty == user_config.difficulty
+}
+
+/// Days without a daily completion between `last_completed_date` and today.
+fn skipped_days(last_completed_date: Option<&str>, today: NaiveDate) -> u32 {
+    last_completed_date
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .map_or(0, |last| (today - last).num_days().saturating_sub(1).max(0) as u32)
+}
+
+/// Daily completions at `tier` ending with the latest, on consecutive days.
+fn daily_run_at(history: &[Completion], tier: Difficulty) -> usize {
+    let mut run = 0;
+    let mut expected: Option<NaiveDate> = None;
+    for completion in history.iter().rev().filter(|c| !c.bonus) {
+        let Ok(date) = NaiveDate::parse_from_str(&completion.date, "%Y-%m-%d") else { break };
+        if completion.difficulty != tier || expected.is_some_and(|expected| date != expected) {
+            break;
+        }
+        run += 1;
+        expected = date.pred_opt();
+    }
+    run
+}
+
+/// Applies `auto_difficulty` to a config that just recorded a passing daily
+/// submission on `today`, returning the message to print if the tier changed.
+/// Skipped days count as misses, and a pass otherwise clears them. A failed
+/// day before today is already one of the skipped days, so only a failure
+/// earlier today adds to them.
+fn auto_difficulty_after_pass(user_config: &mut UserConfig, skipped_days: u32, today: &str) -> Option<String> {
+    let failed_today = user_config.last_miss_date.as_deref() == Some(today);
+    let misses = skipped_days + u32::from(failed_today);
+    user_config.difficulty_misses = 0;
+    user_config.last_miss_date = None;
+    let old = user_config.difficulty;
+    if misses >= DEMOTE_AFTER {
+        let new = old.easier()?;
+        user_config.difficulty = new;
+        return Some(format!(
+            "{} missed days or failed submissions at {}: moving down to {}.",
+            misses,
+            old.display_name(),
+            new.display_name()
+        ));
+    }
+    let run = daily_run_at(&user_config.history, old);
+    if run < PROMOTE_AFTER {
+        return None;
+    }
+    let new = old.harder()?;
+    user_config.difficulty = new;
+    Some(format!(
+        "{} daily challenges in a row at {}: moving up to {}.",
+        run,
+        old.display_name(),
+        new.display_name()
+    ))
+}
+
+/// Applies `auto_difficulty` to a config after a daily submission failed its
+/// tests on `today`, returning the message to print if the tier changed. Only
+/// the first failure of a day counts, so retrying a fix doesn't demote.
+fn auto_difficulty_after_failure(user_config: &mut UserConfig, today: &str) -> Option<String> {
+    if user_config.last_miss_date.as_deref() == Some(today) {
+        return None;
+    }
+    user_config.last_miss_date = Some(today.to_string());
+    user_config.difficulty_misses += 1;
+    if user_config.difficulty_misses < DEMOTE_AFTER {
+        return None;
+    }
+    let misses = user_config.difficulty_misses;
+    user_config.difficulty_misses = 0;
+    let old = user_config.difficulty;
+    let new = old.easier()?;
+    user_config.difficulty = new;
+    Some(format!(
+        "Failed submissions on {} days at {}: moving down to {}.",
+        misses,
+        old.display_name(),
+        new.display_name()
+    ))
+}
+
+fn print_tier_change(tier_change: Option<&str>) {
+    if let Some(message) = tier_change {
+        println!();
+        println!("Auto difficulty: {}", message);
+        println!("Change it any time with `codle difficulty <level>`.");
+    }
+}
+
+/// A bonus challenge only scores once per day, since resubmitting it would
+/// otherwise keep adding points.
+fn bonus_already_completed(user_config: &UserConfig, metadata: &ProjectMetadata, today: &str) -> bool {
+    user_config
+        .history
+        .iter()
+        .any(|c| c.bonus && c.date == today && c.challenge_name == metadata.challenge_name)
+}
+
+/// The challenge's write-up, printed under the completion panel.
+fn explanation_lines(explanation: Option<&str>) -> Vec<String> {
+    let Some(explanation) = explanation else {
+        return Vec::new();
+    };
+    let mut lines = vec![String::new(), "How to solve it:".to_string()];
+    lines.extend(explanation.trim().lines().map(|line| {
+        if line.is_empty() {
+            String::new()
+        } else {
+            format!("  {}", line)
+        }
+    }));
+    lines
+}
+
+/// Only today's daily challenge counts towards the score, so an older project
+/// can't be submitted to claim today's.
+fn check_daily_challenge(metadata: &ProjectMetadata, daily_name: &str) -> Result<(), String> {
+    if metadata.challenge_name == daily_name {
+        return Ok(());
+    }
+    let which = match metadata.challenge_index {
+        0 => "challenge".to_string(),
+        index => format!("bonus challenge {}", index),
+    };
+    Err(format!(
+        "This project is for '{}', but today's {} {} is '{}'.",
+        metadata.challenge_name,
+        metadata.difficulty.as_str(),
+        which,
+        daily_name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Difficulty, Language};
+
+    fn project_for(challenge_name: &str) -> ProjectMetadata {
+        ProjectMetadata::new(
+            challenge_name.to_string(),
+            Language::Rs,
+            Difficulty::Medium,
+            "solve".to_string(),
+            None,
+            3,
+        )
+    }
+
+    #[test]
+    fn test_explanation_printed_when_present() {
+        assert_eq!(
+            explanation_lines(Some("Use a hash map.\n\nO(n) time.")),
+            vec!["", "How to solve it:", "  Use a hash map.", "", "  O(n) time."]
+        );
+        assert!(explanation_lines(None).is_empty());
+    }
+
+    #[test]
+    fn test_practice_submit_leaves_daily_state() {
+        let config = UserConfig {
+            boss_score: 10,
+            challenges_completed: 2,
+            last_completed_date: Some("2024-03-01".to_string()),
+            current_streak: 2,
+            longest_streak: 4,
+            ..UserConfig::default()
+        };
+        let metadata = project_for("Two Sum");
+
+        let (practiced, streak, points) =
+            record_submission(&config, &metadata, "2024-03-02", "2024-03-01", Some(60), true);
+        assert_eq!((streak, points), (0, 4));
+        assert_eq!((practiced.practice_completed, practiced.practice_score), (1, 4));
+        assert_eq!((practiced.boss_score, practiced.challenges_completed), (10, 2));
+        assert_eq!(practiced.last_completed_date.as_deref(), Some("2024-03-01"));
+        assert_eq!((practiced.current_streak, practiced.longest_streak), (2, 4));
+        assert!(practiced.history.is_empty());
+        assert_eq!(practiced.language_counts.get(&Language::Rs), Some(&1));
+
+        let (daily, streak, points) =
+            record_submission(&practiced, &metadata, "2024-03-02", "2024-03-01", Some(60), false);
+        assert_eq!((streak, points), (3, 7));
+        assert_eq!((daily.boss_score, daily.challenges_completed), (17, 3));
+        assert_eq!(daily.last_completed_date.as_deref(), Some("2024-03-02"));
+        assert_eq!((daily.current_streak, daily.longest_streak), (3, 4));
+        assert_eq!(daily.history.len(), 1);
+        assert_eq!((daily.practice_completed, daily.practice_score), (1, 4));
+        assert_eq!(daily.language_counts.get(&Language::Rs), Some(&2));
+    }
+
+    #[test]
+    fn test_mismatched_project_rejected() {
+        let err = check_daily_challenge(&project_for("Two Sum"), "Valid Parentheses").unwrap_err();
+        assert_eq!(
+            err,
+            "This project is for 'Two Sum', but today's medium challenge is 'Valid Parentheses'."
+        );
+        assert!(check_daily_challenge(&project_for("Valid Parentheses"), "Valid Parentheses").is_ok());
+    }
+
+    #[test]
+    fn test_bonus_submit_skips_streak() {
+        let config = UserConfig {
+            boss_score: 10,
+            challenges_completed: 2,
+            last_completed_date: Some("2024-03-02".to_string()),
+            current_streak: 3,
+            longest_streak: 3,
+            ..UserConfig::default()
+        };
+        let mut metadata = project_for("Two Sum");
+        metadata.challenge_index = 1;
+
+        let (updated, streak, points) =
+            record_submission(&config, &metadata, "2024-03-02", "2024-03-01", Some(60), false);
+        assert_eq!((streak, points), (0, 2));
+        assert_eq!((updated.boss_score, updated.challenges_completed), (12, 3));
+        assert_eq!(updated.last_completed_date.as_deref(), Some("2024-03-02"));
+        assert_eq!((updated.current_streak, updated.longest_streak), (3, 3));
+        assert!(updated.history[0].bonus);
+        assert!(bonus_already_completed(&updated, &metadata, "2024-03-02"));
+        assert!(!bonus_already_completed(&updated, &metadata, "2024-03-03"));
+
+        let err = check_daily_challenge(&metadata, "Valid Parentheses").unwrap_err();
+        assert!(err.ends_with("today's medium bonus challenge 1 is 'Valid Parentheses'."));
+    }
+
+    fn daily(date: &str, difficulty: Difficulty) -> Completion {
+        Completion {
+            date: date.to_string(),
+            challenge_name: "Two Sum".to_string(),
+            language: Language::Rs,
+            difficulty,
+            points: 4,
+            duration_secs: None,
+            bonus: false,
+        }
+    }
+
+    #[test]
+    fn test_auto_difficulty_promotes_after_run() {
+        let mut config = UserConfig {
+            auto_difficulty: true,
+            history: ["2024-03-01", "2024-03-02", "2024-03-03", "2024-03-04"]
+                .iter()
+                .map(|date| daily(date, Difficulty::Medium))
+                .collect(),
+            ..UserConfig::default()
+        };
+        assert_eq!(auto_difficulty_after_pass(&mut config, 0, "2024-03-05"), None);
+
+        config.history.push(daily("2024-03-05", Difficulty::Medium));
+        assert_eq!(
+            auto_difficulty_after_pass(&mut config, 0, "2024-03-05").as_deref(),
+            Some("5 daily challenges in a row at Medium: moving up to Hard.")
+        );
+        assert_eq!(config.difficulty, Difficulty::Hard);
+
+        // A gap restarts the run.
+        config.history[1].date = "2024-02-20".to_string();
+        assert_eq!(daily_run_at(&config.history, Difficulty::Medium), 3);
+    }
+
+    #[test]
+    fn test_auto_difficulty_demotes_after_misses() {
+        let mut config = UserConfig { auto_difficulty: true, ..UserConfig::default() };
+        assert_eq!(auto_difficulty_after_failure(&mut config, "2024-03-01"), None);
+        // Retrying the same day doesn't count again.
+        assert_eq!(auto_difficulty_after_failure(&mut config, "2024-03-01"), None);
+        assert_eq!(auto_difficulty_after_failure(&mut config, "2024-03-01"), None);
+        assert_eq!(config.difficulty_misses, 1);
+        assert_eq!(auto_difficulty_after_failure(&mut config, "2024-03-02"), None);
+        assert_eq!(
+            auto_difficulty_after_failure(&mut config, "2024-03-03").as_deref(),
+            Some("Failed submissions on 3 days at Medium: moving down to Easy.")
+        );
+        assert_eq!((config.difficulty, config.difficulty_misses), (Difficulty::Easy, 0));
+
+        // Failing earlier today adds to the skipped days.
+        config.difficulty = Difficulty::Hard;
+        config.difficulty_misses = 1;
+        config.last_miss_date = Some("2024-03-05".to_string());
+        assert_eq!(
+            auto_difficulty_after_pass(&mut config, 2, "2024-03-05").as_deref(),
+            Some("3 missed days or failed submissions at Hard: moving down to Medium.")
+        );
+        assert_eq!(config.last_miss_date, None);
+
+        // A failure on a day that was skipped isn't counted twice.
+        config.difficulty_misses = 1;
+        config.last_miss_date = Some("2024-03-04".to_string());
+        assert_eq!(auto_difficulty_after_pass(&mut config, 2, "2024-03-06"), None);
+
+        let today = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        assert_eq!(skipped_days(Some("2024-03-04"), today), 0);
+        assert_eq!(skipped_days(Some("2024-03-01"), today), 3);
+        assert_eq!(skipped_days(None, today), 0);
+    }
 }
@@ -1,9 +1,11 @@
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::models::project;
-use crate::lang::run_tests;
+use crate::models::{config, project, Language, ProjectMetadata};
+use crate::lang::{run_tests, TestSummary};
 
-pub fn test_solution() {
+pub fn test_solution(quiet: bool, since_init: bool, timeout_secs: Option<u64>, no_color: bool) {
     let current_dir = env::current_dir().unwrap_or_else(|e| {
         eprintln!("Failed to get current directory: {}", e);
         std::process::exit(1);
@@ -17,14 +19,22 @@ pub fn test_solution() {
         }
     };
 
-    println!(
-        "Running tests for {} ({})...",
-        metadata.challenge_name,
-        metadata.language.display_name()
-    );
-    println!();
+    if !quiet {
+        println!(
+            "Running tests for {} ({})...",
+            metadata.challenge_name,
+            metadata.language.display_name()
+        );
+        if since_init {
+            if let Some(secs) = metadata.seconds_since_init() {
+                println!("Working for {}", super::format_duration(secs));
+            }
+        }
+        println!();
+    }
 
-    let summary = match run_tests(metadata.language) {
+    let timeout = super::test_timeout(&metadata, timeout_secs);
+    let summary = match run_tests(metadata.language, &current_dir, Some(timeout)) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to run tests: {}", e);
@@ -32,28 +42,276 @@ pub fn test_solution() {
         }
     };
 
+    let color = super::color::enabled(no_color, config::load_effective_config().color);
+    for line in report_lines(&summary, metadata.language, quiet, color) {
+        println!("{}", line);
+    }
+
+    if summary.total == 0 || summary.failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// `codle test --all`: runs every project one level below the current
+/// directory and prints one row per project.
+pub fn test_all(timeout_secs: Option<u64>, no_color: bool) {
+    let current_dir = env::current_dir().unwrap_or_else(|e| {
+        eprintln!("Failed to get current directory: {}", e);
+        std::process::exit(1);
+    });
+
+    let projects = find_projects(&current_dir);
+    if projects.is_empty() {
+        eprintln!("No codle projects found in the subdirectories of {}", current_dir.display());
+        std::process::exit(1);
+    }
+
+    println!("Running tests for {} projects...", projects.len());
+    println!();
+
+    let results: Vec<ProjectResult> =
+        projects.iter().map(|dir| test_project(dir, timeout_secs)).collect();
+    let color = super::color::enabled(no_color, config::load_effective_config().color);
+    for line in table_lines(&results, color) {
+        println!("{}", line);
+    }
+
+    if !results.iter().all(ProjectResult::passed) {
+        std::process::exit(1);
+    }
+}
+
+struct ProjectResult {
+    name: String,
+    outcome: Result<(ProjectMetadata, TestSummary), String>,
+}
+
+impl ProjectResult {
+    fn passed(&self) -> bool {
+        matches!(&self.outcome, Ok((_, s)) if s.total > 0 && s.failed == 0)
+    }
+}
+
+/// Immediate subdirectories holding a `.codle.json`, sorted by name.
+fn find_projects(root: &Path) -> Vec<PathBuf> {
+    let mut projects: Vec<PathBuf> = fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.join(project::METADATA_FILE).is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    projects.sort();
+    projects
+}
+
+fn test_project(dir: &Path, timeout_secs: Option<u64>) -> ProjectResult {
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| dir.display().to_string());
+    let outcome = project::load(dir).and_then(|metadata| {
+        let timeout = super::test_timeout(&metadata, timeout_secs);
+        let summary = run_tests(metadata.language, dir, Some(timeout))?;
+        Ok((metadata, summary))
+    });
+    ProjectResult { name, outcome }
+}
+
+fn table_lines(results: &[ProjectResult], color: bool) -> Vec<String> {
+    let width = results
+        .iter()
+        .map(|r| r.name.len())
+        .chain(std::iter::once("Project".len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![format!("{:<width$}  {:<8}  Result", "Project", "Language")];
+    let (mut passed_tests, mut total_tests) = (0, 0);
+    for result in results {
+        let paint = if result.passed() { super::color::green } else { super::color::red };
+        let (language, status) = match &result.outcome {
+            Ok((metadata, summary)) => {
+                passed_tests += summary.passed;
+                total_tests += summary.total;
+                let status = if summary.compile_error.is_some() {
+                    "doesn't compile".to_string()
+                } else if summary.total == 0 {
+                    "no test results".to_string()
+                } else if summary.failed == 0 {
+                    format!("{}/{} passed", summary.passed, summary.total)
+                } else {
+                    format!("{}/{} passed - {} failed", summary.passed, summary.total, summary.failed)
+                };
+                (metadata.language.display_name(), status)
+            }
+            Err(e) => ("?", format!("error: {}", e)),
+        };
+        lines.push(format!("{:<width$}  {:<8}  {}", result.name, language, paint(&status, color)));
+    }
+
+    lines.push(super::BAR.to_string());
+    lines.push(format!(
+        "{}/{} projects passed ({}/{} tests)",
+        results.iter().filter(|r| r.passed()).count(),
+        results.len(),
+        passed_tests,
+        total_tests
+    ));
+    lines
+}
+
+/// What `codle test` prints after the run. Quiet mode is a single
+/// `passed/total` line for scripts and git hooks, never colored.
+fn report_lines(summary: &TestSummary, language: Language, quiet: bool, color: bool) -> Vec<String> {
+    if quiet {
+        return vec![format!("{}/{}", summary.passed, summary.total)];
+    }
+
+    let mut lines = Vec::new();
     if !summary.output.trim().is_empty() {
-        println!("{}", summary.output.trim());
-        println!();
+        lines.push(summary.output.trim().to_string());
+        lines.push(String::new());
     }
 
-    println!("========================================");
-    if summary.total == 0 {
-        println!("No test results found. Check the output above for errors.");
-    } else if summary.failed == 0 {
-        println!(
-            "{}/{} tests passed",
-            summary.passed, summary.total
+    lines.extend(super::result_banner(summary, language, color));
+    if let Some(diagnosis) = summary.diagnosis.as_ref().filter(|_| summary.total > 0) {
+        lines.push(String::new());
+        lines.push(diagnosis.clone());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(passed: usize, failed: usize) -> TestSummary {
+        TestSummary {
+            passed,
+            failed,
+            total: passed + failed,
+            output: "Test 1: PASS\nTest 2: FAIL\n".to_string(),
+            diagnosis: None,
+            compile_error: None,
+        }
+    }
+
+    #[test]
+    fn test_quiet_prints_only_summary() {
+        assert_eq!(report_lines(&summary(1, 1), Language::C, true, true), vec!["1/2"]);
+        assert_eq!(report_lines(&summary(0, 0), Language::C, true, true), vec!["0/0"]);
+    }
+
+    #[test]
+    fn test_all_runs_each_project() {
+        use crate::lang::{generate_scaffold, ScaffoldOptions};
+        use crate::models::{parse_signature, Challenge, Difficulty, Language};
+        use std::process::Command;
+
+        if ["gcc", "g++", "make"]
+            .iter()
+            .any(|cmd| Command::new(cmd).arg("--version").output().is_err())
+        {
+            return;
+        }
+
+        let challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "Add",
+            "difficulty": 1,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn add(a: i32, b: i32) -> i32",
+            "tests": [
+                {"input": {"a": 1, "b": 2}, "expected": 3},
+                {"input": {"a": -4, "b": 4}, "expected": 0},
+            ],
+        }))
+        .unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+
+        let root = env::temp_dir().join(format!("codle-test-all-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (name, lang, body) in [
+            ("alice_c", Language::C, "return a + b;"),
+            ("bob_cpp", Language::Cpp, "return a - b;"),
+        ] {
+            let dir = root.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            let options = ScaffoldOptions {
+                solution_body: Some(body.to_string()),
+                ..ScaffoldOptions::default()
+            };
+            generate_scaffold(&challenge, &sig, lang, Difficulty::Easy, &dir, &options).unwrap();
+            Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        }
+        fs::create_dir_all(root.join("notes")).unwrap();
+
+        let projects = find_projects(&root);
+        let results: Vec<ProjectResult> = projects.iter().map(|dir| test_project(dir, None)).collect();
+        let lines = table_lines(&results, false);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(
+            lines,
+            vec![
+                "Project  Language  Result",
+                "alice_c  C         2/2 passed",
+                "bob_cpp  C++       0/2 passed - 2 failed",
+                "========================================",
+                "1/2 projects passed (2/4 tests)",
+            ]
         );
-    } else {
-        println!(
-            "{}/{} tests passed - {} failed",
-            summary.passed, summary.total, summary.failed
+    }
+
+    #[test]
+    fn test_diagnosis_replaces_no_results_message() {
+        let summary = TestSummary {
+            diagnosis: Some("Build files are missing (Makefile). Re-run ./setup.sh to regenerate them.".to_string()),
+            ..summary(0, 0)
+        };
+        let lines = report_lines(&summary, Language::C, false, false);
+        assert!(lines.contains(&"Build files are missing (Makefile). Re-run ./setup.sh to regenerate them.".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("No test results found")));
+    }
+
+    #[test]
+    fn test_compile_error_replaces_no_results_message() {
+        let summary = TestSummary {
+            compile_error: Some("error[E0425]: cannot find value `x` in this scope\n --> src/main.rs:2:5".to_string()),
+            ..summary(0, 0)
+        };
+        let lines = report_lines(&summary, Language::C, false, false);
+        let bar = lines.iter().position(|line| line.starts_with("=====")).unwrap();
+        assert_eq!(
+            lines[bar + 1..bar + 5],
+            [
+                "Your code doesn't compile:",
+                "",
+                "  error[E0425]: cannot find value `x` in this scope",
+                "   --> src/main.rs:2:5",
+            ]
         );
     }
-    println!("========================================");
 
-    if summary.total == 0 || summary.failed > 0 {
-        std::process::exit(1);
+    #[test]
+    fn test_verbose_includes_output_and_bars() {
+        let lines = report_lines(&summary(1, 1), Language::C, false, false);
+        assert_eq!(lines[0], "Test 1: PASS\nTest 2: FAIL");
+        assert!(lines.contains(&"1/2 tests passed - 1 failed".to_string()));
+        assert!(lines.contains(&"Failed: 2".to_string()));
+        assert_eq!(lines.last().unwrap(), "========================================");
+    }
+
+    #[test]
+    fn test_colored_banner() {
+        let failing = report_lines(&summary(1, 1), Language::C, false, true);
+        assert!(failing.contains(&"\x1b[31m1/2 tests passed - 1 failed\x1b[0m".to_string()));
+        assert_eq!(failing.last().unwrap(), "\x1b[31m========================================\x1b[0m");
+
+        let passing = report_lines(&summary(2, 0), Language::C, false, true);
+        assert!(passing.contains(&"\x1b[32m2/2 tests passed\x1b[0m".to_string()));
     }
 }
@@ -0,0 +1,103 @@
+use chrono::NaiveDate;
+
+use crate::models::{load_daily_challenge, config, today, Challenge};
+
+/// `codle today`: today's daily challenge, whether it's done, and the streak
+/// and BOSS score, plus any practice totals, at a glance.
+pub fn show_today() {
+    let user_config = config::load_effective_config();
+    let challenge = match load_daily_challenge(user_config.difficulty, &user_config.history) {
+        Ok(challenge) => challenge,
+        Err(e) => {
+            eprintln!("Failed to load challenge: {}", e);
+            std::process::exit(1);
+        }
+    };
+    for line in today_lines(&challenge, &user_config, today()) {
+        println!("{}", line);
+    }
+}
+
+fn today_lines(challenge: &Challenge, user_config: &config::UserConfig, today: NaiveDate) -> Vec<String> {
+    let last = user_config
+        .last_completed_date
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+    let status = if last == Some(today) { "done" } else { "not done yet" };
+    let mut lines = vec![
+        format!(
+            "Today: {} ({}, {}/10) - {}",
+            challenge.name,
+            user_config.difficulty.display_name(),
+            challenge.difficulty,
+            status
+        ),
+        format!(
+            "Streak: {} day(s) | BOSS Score: {}",
+            live_streak(user_config.current_streak, last, today),
+            user_config.boss_score
+        ),
+    ];
+    if user_config.practice_completed > 0 {
+        lines.push(format!(
+            "Practice: {} solved, {} points",
+            user_config.practice_completed, user_config.practice_score
+        ));
+    }
+    lines
+}
+
+/// The saved streak only resets on the next submission, so one whose last
+/// completion was before yesterday is already broken.
+fn live_streak(streak: u32, last: Option<NaiveDate>, today: NaiveDate) -> u32 {
+    match last {
+        Some(date) if (today - date).num_days() <= 1 => streak,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Difficulty;
+
+    #[test]
+    fn test_today_lines() {
+        let challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "Two Sum",
+            "difficulty": 3,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>",
+            "tests": []
+        }))
+        .unwrap();
+        let mut user_config = config::UserConfig {
+            difficulty: Difficulty::Medium,
+            boss_score: 42,
+            current_streak: 4,
+            last_completed_date: Some("2026-03-10".to_string()),
+            ..config::UserConfig::default()
+        };
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+
+        assert_eq!(
+            today_lines(&challenge, &user_config, date("2026-03-10")),
+            vec!["Today: Two Sum (Medium, 3/10) - done", "Streak: 4 day(s) | BOSS Score: 42"]
+        );
+        let tomorrow = today_lines(&challenge, &user_config, date("2026-03-11"));
+        assert_eq!(tomorrow[0], "Today: Two Sum (Medium, 3/10) - not done yet");
+        assert_eq!(tomorrow[1], "Streak: 4 day(s) | BOSS Score: 42");
+        assert_eq!(today_lines(&challenge, &user_config, date("2026-03-13"))[1], "Streak: 0 day(s) | BOSS Score: 42");
+
+        user_config.last_completed_date = None;
+        assert!(today_lines(&challenge, &user_config, date("2026-03-13"))[0].ends_with("- not done yet"));
+
+        user_config.practice_completed = 2;
+        user_config.practice_score = 9;
+        assert_eq!(
+            today_lines(&challenge, &user_config, date("2026-03-13"))[2],
+            "Practice: 2 solved, 9 points"
+        );
+    }
+}
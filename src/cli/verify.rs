@@ -0,0 +1,169 @@
+use std::fs;
+use std::panic;
+use std::path::Path;
+
+use crate::lang::{check_language_support, generate_scaffold, ScaffoldOptions};
+use crate::models::{parse_signature, validate_challenge, Challenge, Difficulty, FunctionSignature, Language};
+
+/// Checks a challenge file before it's added to the set: that it parses, its
+/// tests match the signature, and every language that supports the signature
+/// can generate a scaffold for it.
+pub fn verify_challenge(path: &Path) {
+    let (challenge, sig) = match load_checked(path) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    println!("{} ({})", challenge.name, sig.name);
+    println!();
+    // Panics are reported per language instead of printed as they happen;
+    // the process ends with verify, so the default hook isn't restored.
+    panic::set_hook(Box::new(|_| {}));
+    let results = scaffold_results(&challenge, &sig, tier_of(path));
+    for (language, result) in &results {
+        let outcome = match result {
+            Ok(None) => "ok".to_string(),
+            Ok(Some(reason)) => format!("skipped: {}", reason),
+            Err(e) => format!("FAIL: {}", e),
+        };
+        println!("  {:<11} {}", language.display_name(), outcome);
+    }
+
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    println!();
+    if failed > 0 {
+        println!("{} of {} languages failed to scaffold", failed, results.len());
+        std::process::exit(1);
+    }
+    println!("Challenge is valid");
+}
+
+/// The challenge in `path` with its parsed signature, if both are valid.
+fn load_checked(path: &Path) -> Result<(Challenge, FunctionSignature), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("failed to read: {}", e))?;
+    let challenge: Challenge =
+        serde_json::from_str(&content).map_err(|e| format!("invalid JSON: {}", e))?;
+    let sig = parse_signature(&challenge.function_signature).map_err(|e| format!("bad signature: {}", e))?;
+    validate_challenge(&challenge, &sig).map_err(|e| format!("invalid tests: {}", e))?;
+    Ok((challenge, sig))
+}
+
+/// The tier directory the file sits in, e.g. `challenges/hard/x.json`, or
+/// Medium when it isn't in one.
+fn tier_of(path: &Path) -> Difficulty {
+    let parent = path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str());
+    Difficulty::all()
+        .iter()
+        .copied()
+        .find(|d| Some(d.as_str()) == parent)
+        .unwrap_or(Difficulty::Medium)
+}
+
+/// For each language, `Ok(None)` if a scaffold was generated, `Ok(Some(_))`
+/// with the reason if the language doesn't support the signature, and the
+/// error or panic message otherwise.
+fn scaffold_results(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+) -> Vec<(Language, Result<Option<String>, String>)> {
+    Language::all()
+        .iter()
+        .map(|&language| {
+            let result = match check_language_support(sig, language) {
+                Err(e) => Ok(Some(e)),
+                Ok(()) => scaffold_in_temp_dir(challenge, sig, language, difficulty).map(|_| None),
+            };
+            (language, result)
+        })
+        .collect()
+}
+
+fn scaffold_in_temp_dir(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    language: Language,
+    difficulty: Difficulty,
+) -> Result<(), String> {
+    let dir = std::env::temp_dir().join(format!(
+        "codle-verify-{}-{}-{}",
+        sig.name,
+        language.extension(),
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let result = panic::catch_unwind(|| {
+        generate_scaffold(challenge, sig, language, difficulty, &dir, &ScaffoldOptions::default())
+    });
+    let _ = fs::remove_dir_all(&dir);
+    match result {
+        Ok(generated) => generated,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("generator panicked: {}", message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_fixture(name: &str, signature: &str, tests: serde_json::Value) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("codle-verify-fixture-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("hard")).unwrap();
+        let path = dir.join("hard").join(format!("{}.json", name));
+        let challenge = serde_json::json!({
+            "name": name,
+            "difficulty": 1,
+            "short_description": "",
+            "description": "",
+            "function_signature": signature,
+            "tests": tests,
+        });
+        fs::write(&path, challenge.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_verify_scaffolds_every_language() {
+        let path = write_fixture(
+            "grid_sum",
+            "fn grid_sum(grid: Vec<Vec<i32>>) -> i32",
+            serde_json::json!([{"input": {"grid": [[1, 2], [3]]}, "expected": 6}]),
+        );
+        let (challenge, sig) = load_checked(&path).unwrap();
+        assert_eq!(tier_of(&path), Difficulty::Hard);
+        let results = scaffold_results(&challenge, &sig, tier_of(&path));
+        let _ = fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+
+        assert_eq!(results.len(), Language::all().len());
+        for (language, result) in &results {
+            assert!(result.is_ok(), "{:?}: {:?}", language, result);
+        }
+        let fortran = results.iter().find(|(l, _)| *l == Language::Fortran).unwrap();
+        assert!(matches!(&fortran.1, Ok(Some(_))));
+        assert!(results.iter().any(|(l, result)| *l == Language::C && *result == Ok(None)));
+    }
+
+    #[test]
+    fn test_verify_rejects_mismatched_tests() {
+        let path = write_fixture(
+            "add",
+            "fn add(a: i32, b: i32) -> i32",
+            serde_json::json!([{"input": {"a": 1}, "expected": 1}]),
+        );
+        let err = load_checked(&path).unwrap_err();
+        let _ = fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+        assert!(err.starts_with("invalid tests: test inputs don't match"), "{}", err);
+    }
+}
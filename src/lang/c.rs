@@ -6,19 +6,30 @@ use crate::models::{
 };
 use super::{
     write_setup_script, require_commands, escape_for_heredoc,
-    has_mut_ref_params, is_void_with_mut_ref, get_first_test_inputs, unwrap_mut_ref,
+    has_mut_ref_params, is_void_with_mut_ref, get_first_test_inputs, largest_test_inputs,
+    unwrap_ref, SpecialFloat, ScaffoldOptions,
 };
 
 pub(super) fn translate_type_c(ty: &RustType) -> String {
     match ty {
+        RustType::I8 => "int8_t".to_string(),
+        RustType::I16 => "int16_t".to_string(),
         RustType::I32 => "int".to_string(),
+        RustType::I64 => "long long".to_string(),
+        RustType::U8 => "uint8_t".to_string(),
+        RustType::U16 => "uint16_t".to_string(),
+        RustType::U32 => "uint32_t".to_string(),
+        RustType::U64 => "unsigned long long".to_string(),
+        RustType::F32 => "float".to_string(),
         RustType::F64 => "double".to_string(),
         RustType::Usize => "size_t".to_string(),
         RustType::Bool => "bool".to_string(),
-        RustType::String => "char*".to_string(),
+        RustType::String | RustType::Str => "char*".to_string(),
         RustType::Char => "char".to_string(),
         RustType::Vec(inner) => format!("{}*", translate_type_c(inner)),
-        RustType::MutRef(inner) => translate_type_c(inner),
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_c(inner),
+        // Rejected by `Language::supports`.
+        RustType::Map(..) | RustType::Tuple(_) => "void*".to_string(),
         RustType::Void => "void".to_string(),
     }
 }
@@ -26,14 +37,20 @@ pub(super) fn translate_type_c(ty: &RustType) -> String {
 pub(super) fn render_value_c(value: &Value, ty: &RustType) -> String {
     match ty {
         RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
-        RustType::F64 => {
-            let n = value.as_f64().unwrap_or(0.0);
-            if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                format!("{}", n)
-            }
-        }
+        RustType::I8 | RustType::I16 | RustType::U8 | RustType::U16 => super::integer_literal(value),
+        // 9223372036854775808 doesn't fit a `long long`, so negating it would
+        // make an unsigned constant; `LLONG_MIN` is written the same way.
+        RustType::I64 if value.as_i64() == Some(i64::MIN) => "(-9223372036854775807LL - 1)".to_string(),
+        RustType::I64 => format!("{}LL", super::integer_literal(value)),
+        RustType::U32 => format!("{}U", super::integer_literal(value)),
+        RustType::U64 => format!("{}ULL", super::integer_literal(value)),
+        RustType::F32 | RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "INFINITY".to_string(),
+            Some(SpecialFloat::NegInfinity) => "-INFINITY".to_string(),
+            Some(SpecialFloat::NaN) => "NAN".to_string(),
+            None if *ty == RustType::F32 => format!("{}f", super::format_float(value.as_f64().unwrap_or(0.0))),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
         RustType::Bool => {
             if value.as_bool().unwrap_or(false) {
                 "true".to_string()
@@ -41,12 +58,8 @@ pub(super) fn render_value_c(value: &Value, ty: &RustType) -> String {
                 "false".to_string()
             }
         }
-        RustType::String => format!("\"{}\"", value.as_str().unwrap_or("")),
-        RustType::Char => {
-            let s = value.as_str().unwrap_or("?");
-            let c = s.chars().next().unwrap_or('?');
-            format!("'{}'", c)
-        }
+        RustType::String | RustType::Str => super::string_literal(value, Language::C),
+        RustType::Char => super::char_literal(value, Language::C),
         RustType::Vec(inner) => {
             if let Some(arr) = value.as_array() {
                 let items: Vec<String> = arr.iter().map(|v| render_value_c(v, inner)).collect();
@@ -55,18 +68,91 @@ pub(super) fn render_value_c(value: &Value, ty: &RustType) -> String {
                 "{}".to_string()
             }
         }
-        RustType::MutRef(inner) => render_value_c(value, inner),
-        RustType::Void => "".to_string(),
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => render_value_c(value, inner),
+        RustType::Map(..) | RustType::Tuple(_) | RustType::Void => "".to_string(),
+    }
+}
+
+/// Initializer for a local array. `{}` is only valid from C23 on and would
+/// declare a zero-length array, so an empty input gets one placeholder element
+/// while its `_len` stays 0.
+fn array_initializer(value: &Value, ty: &RustType) -> String {
+    if value.as_array().is_none_or(|arr| arr.is_empty()) {
+        "{0}".to_string()
+    } else {
+        render_value_c(value, ty)
+    }
+}
+
+/// The element type of a `Vec<Vec<T>>`.
+fn matrix_element(ty: &RustType) -> Option<&RustType> {
+    match ty {
+        RustType::Vec(row) => match row.as_ref() {
+            RustType::Vec(elem) => Some(elem),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Declares matrix `var` as one array per row plus an array of row pointers,
+/// followed by `<name>_len`, the row count, and `<name>_col_lens`, the length
+/// of each row.
+fn declare_matrix(indent: &str, var: &str, name: &str, elem: &RustType, value: &Value) -> String {
+    let rows = value.as_array().map(Vec::as_slice).unwrap_or_default();
+    let elem_type = translate_type_c(elem);
+    let row_type = RustType::Vec(Box::new(elem.clone()));
+    // Same placeholder as `array_initializer` for an empty matrix.
+    let braced = |items: Vec<String>| {
+        if items.is_empty() {
+            "{0}".to_string()
+        } else {
+            format!("{{{}}}", items.join(", "))
+        }
+    };
+
+    let mut code = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        code.push_str(&format!(
+            "{}{} {}_row{}[] = {};\n",
+            indent,
+            elem_type,
+            var,
+            i,
+            array_initializer(row, &row_type)
+        ));
     }
+    let row_names = (0..rows.len()).map(|i| format!("{}_row{}", var, i)).collect();
+    let col_lens = rows
+        .iter()
+        .map(|row| row.as_array().map_or(0, Vec::len).to_string())
+        .collect();
+    code.push_str(&format!("{}{}* {}[] = {};\n", indent, elem_type, var, braced(row_names)));
+    code.push_str(&format!("{}int {}_len = {};\n", indent, name, rows.len()));
+    code.push_str(&format!("{}int {}_col_lens[] = {};\n", indent, name, braced(col_lens)));
+    code
 }
 
-fn expand_c_params(sig: &FunctionSignature) -> Vec<String> {
+/// The C parameters for `sig`. Arrays are followed by an `int <name>_len`;
+/// a matrix is an array of row pointers, followed by its row count and an
+/// `int* <name>_col_lens` with each row's length. A `String` is read-only, so
+/// it's a `const char*`, followed by its length too when `string_len` is set.
+pub(super) fn expand_c_params(sig: &FunctionSignature, string_len: bool) -> Vec<String> {
     let mut result = Vec::new();
     for p in &sig.params {
-        let inner = unwrap_mut_ref(&p.ty);
-        if let RustType::Vec(elem) = inner {
+        let inner = unwrap_ref(&p.ty);
+        if let Some(elem) = matrix_element(inner) {
+            result.push(format!("{}** {}", translate_type_c(elem), p.name));
+            result.push(format!("int {}_len", p.name));
+            result.push(format!("int* {}_col_lens", p.name));
+        } else if let RustType::Vec(elem) = inner {
             result.push(format!("{} {}[]", translate_type_c(elem), p.name));
             result.push(format!("int {}_len", p.name));
+        } else if p.ty == RustType::String {
+            result.push(format!("const char* {}", p.name));
+            if string_len {
+                result.push(format!("int {}_len", p.name));
+            }
         } else {
             result.push(format!("{} {}", translate_type_c(inner), p.name));
         }
@@ -74,7 +160,92 @@ fn expand_c_params(sig: &FunctionSignature) -> Vec<String> {
     result
 }
 
-fn c_return_type(sig: &FunctionSignature) -> String {
+/// Declares `<name>_len` for a `String` input of a `c_string_len` challenge.
+/// The length is in bytes, as `strlen` would count it.
+fn string_len_declaration(name: &str, value: &Value, indent: &str) -> String {
+    format!("{}int {}_len = {};\n", indent, name, value.as_str().unwrap_or("").len())
+}
+
+/// Arrays with more elements than this are written to `DATA_HEADER` instead
+/// of being inlined, which keeps test_solution.c readable and fast to compile.
+const INLINE_ARRAY_LIMIT: usize = 1000;
+
+/// Generated header with the arrays too large to inline.
+pub(super) const DATA_HEADER: &str = "test_data.h";
+
+/// The name of the `DATA_HEADER` array holding input `name` of the test at
+/// `test_index`, or `None` if the input is small enough to inline.
+/// A matrix's rows are always inlined.
+fn external_array(test_index: usize, name: &str, value: &Value) -> Option<String> {
+    let arr = value.as_array()?;
+    if arr.first().is_some_and(Value::is_array) {
+        return None;
+    }
+    (arr.len() > INLINE_ARRAY_LIMIT).then(|| format!("codle_test{}_{}", test_index + 1, name))
+}
+
+/// Declares array `var` holding `value`, or pointing at `external` in
+/// `DATA_HEADER`.
+fn declare_array(indent: &str, var: &str, elem: &RustType, value: &Value, external: Option<String>) -> String {
+    match external {
+        Some(data) => format!("{}{}* {} = {};\n", indent, translate_type_c(elem), var, data),
+        None => format!(
+            "{}{} {}[] = {};\n",
+            indent,
+            translate_type_c(elem),
+            var,
+            array_initializer(value, &RustType::Vec(Box::new(elem.clone())))
+        ),
+    }
+}
+
+/// `DATA_HEADER` for the inputs `external_array` moves out of the tests, or
+/// `None` if every input is inlined. The arrays aren't `static`, so
+/// solution.c's `main` can use the first test's without warnings about the
+/// rest; each build includes the header from a single file.
+pub(super) fn generate_c_test_data(sig: &FunctionSignature, tests: &[TestCase]) -> Option<String> {
+    let mut arrays = String::new();
+    for (i, test) in tests.iter().enumerate() {
+        let Some(inputs) = test.input.as_object() else {
+            continue;
+        };
+        for p in &sig.params {
+            let inner_ty = unwrap_ref(&p.ty);
+            let (RustType::Vec(elem), Some(val)) = (inner_ty, inputs.get(&p.name)) else {
+                continue;
+            };
+            if let Some(name) = external_array(i, &p.name, val) {
+                arrays.push_str(&format!(
+                    "{} {}[] = {};\n",
+                    translate_type_c(elem),
+                    name,
+                    render_value_c(val, inner_ty)
+                ));
+            }
+        }
+    }
+    if arrays.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "// Test inputs too large to inline, generated by codle.\n#ifndef CODLE_TEST_DATA_H\n#define CODLE_TEST_DATA_H\n\n#include <stdbool.h>\n{}\n{}\n#endif",
+        stdint_include(sig),
+        arrays
+    ))
+}
+
+/// The parameter list for a declaration. Before C23, `f()` declares a function
+/// with unspecified arguments, so an empty list is spelled `void`.
+pub(super) fn c_param_list(sig: &FunctionSignature, string_len: bool) -> String {
+    let params = expand_c_params(sig, string_len);
+    if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.join(", ")
+    }
+}
+
+pub(super) fn c_return_type(sig: &FunctionSignature) -> String {
     match &sig.return_type {
         RustType::Vec(inner) => format!("{}*", translate_type_c(inner)),
         other => translate_type_c(other),
@@ -86,15 +257,16 @@ pub(super) fn generate_c(
     sig: &FunctionSignature,
     difficulty: Difficulty,
     output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
-    let params_str = expand_c_params(sig);
     let ret_type = c_return_type(sig);
+    let string_len = challenge.c_string_len;
 
     let default_return = match &sig.return_type {
         RustType::Void => String::new(),
         RustType::Bool => "    return false;\n".to_string(),
         RustType::I32 | RustType::Usize => "    return 0;\n".to_string(),
-        RustType::F64 => "    return 0.0;\n".to_string(),
+        RustType::F32 | RustType::F64 => "    return 0.0;\n".to_string(),
         RustType::String => "    return \"\";\n".to_string(),
         RustType::Vec(_) => "    return NULL;\n".to_string(),
         _ => "    return 0;\n".to_string(),
@@ -104,17 +276,14 @@ pub(super) fn generate_c(
     if let Some(inputs) = get_first_test_inputs(challenge) {
         if is_void_with_mut_ref(sig) {
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
+                let inner_ty = unwrap_ref(&p.ty);
                 if let Some(val) = inputs.get(&p.name) {
-                    if let RustType::Vec(elem) = inner_ty {
-                        let arr_val = render_value_c(val, inner_ty);
+                    if let Some(elem) = matrix_element(inner_ty) {
+                        main_body.push_str(&declare_matrix("    ", &p.name, &p.name, elem, val));
+                    } else if let RustType::Vec(elem) = inner_ty {
                         let len = val.as_array().map(|a| a.len()).unwrap_or(0);
-                        main_body.push_str(&format!(
-                            "    {} {}[] = {};\n",
-                            translate_type_c(elem),
-                            p.name,
-                            arr_val
-                        ));
+                        let external = external_array(0, &p.name, val);
+                        main_body.push_str(&declare_array("    ", &p.name, elem, val, external));
                         main_body.push_str(&format!("    int {}_len = {};\n", p.name, len));
                     } else {
                         main_body.push_str(&format!(
@@ -123,26 +292,32 @@ pub(super) fn generate_c(
                             p.name,
                             render_value_c(val, inner_ty)
                         ));
+                        if string_len && p.ty == RustType::String {
+                            main_body.push_str(&string_len_declaration(&p.name, val, "    "));
+                        }
                     }
                 }
             }
             let mut call_args = Vec::new();
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
-                if matches!(inner_ty, RustType::Vec(_)) {
-                    call_args.push(p.name.clone());
+                let inner_ty = unwrap_ref(&p.ty);
+                call_args.push(p.name.clone());
+                if matches!(inner_ty, RustType::Vec(_)) || string_len && p.ty == RustType::String {
                     call_args.push(format!("{}_len", p.name));
-                } else {
-                    call_args.push(p.name.clone());
+                }
+                if matrix_element(inner_ty).is_some() {
+                    call_args.push(format!("{}_col_lens", p.name));
                 }
             }
             main_body.push_str(&format!("    {}({});\n", sig.name, call_args.join(", ")));
             if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
-                let inner_ty = unwrap_mut_ref(&p.ty);
-                if matches!(inner_ty, RustType::Vec(_)) {
+                let inner_ty = unwrap_ref(&p.ty);
+                if let RustType::Vec(elem) = inner_ty {
                     main_body.push_str(&format!(
-                        "    for (int i = 0; i < {}_len; i++) printf(\"%d \", {}[i]);\n",
-                        p.name, p.name
+                        "    for (int i = 0; i < {}_len; i++) printf(\"{} \", {}[i]);\n",
+                        p.name,
+                        printf_format(elem),
+                        p.name
                     ));
                     main_body.push_str("    printf(\"\\n\");\n");
                 }
@@ -150,17 +325,17 @@ pub(super) fn generate_c(
         } else {
             let mut call_args = Vec::new();
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
+                let inner_ty = unwrap_ref(&p.ty);
                 if let Some(val) = inputs.get(&p.name) {
-                    if let RustType::Vec(elem) = inner_ty {
-                        let arr_val = render_value_c(val, inner_ty);
+                    if let Some(elem) = matrix_element(inner_ty) {
+                        main_body.push_str(&declare_matrix("    ", &p.name, &p.name, elem, val));
+                        call_args.push(p.name.clone());
+                        call_args.push(format!("{}_len", p.name));
+                        call_args.push(format!("{}_col_lens", p.name));
+                    } else if let RustType::Vec(elem) = inner_ty {
                         let len = val.as_array().map(|a| a.len()).unwrap_or(0);
-                        main_body.push_str(&format!(
-                            "    {} {}[] = {};\n",
-                            translate_type_c(elem),
-                            p.name,
-                            arr_val
-                        ));
+                        let external = external_array(0, &p.name, val);
+                        main_body.push_str(&declare_array("    ", &p.name, elem, val, external));
                         main_body.push_str(&format!("    int {}_len = {};\n", p.name, len));
                         call_args.push(p.name.clone());
                         call_args.push(format!("{}_len", p.name));
@@ -172,6 +347,10 @@ pub(super) fn generate_c(
                             render_value_c(val, inner_ty)
                         ));
                         call_args.push(p.name.clone());
+                        if string_len && p.ty == RustType::String {
+                            main_body.push_str(&string_len_declaration(&p.name, val, "    "));
+                            call_args.push(format!("{}_len", p.name));
+                        }
                     }
                 }
             }
@@ -181,72 +360,104 @@ pub(super) fn generate_c(
                 sig.name,
                 call_args.join(", ")
             ));
-            main_body.push_str("    printf(\"%d\\n\", result);\n");
+            if let RustType::Vec(elem) = &sig.return_type {
+                // The returned array carries no length, so print as many
+                // elements as the first test expects.
+                let len = challenge.tests[0].expected.as_array().map_or(0, |a| a.len());
+                main_body.push_str(&format!(
+                    "    for (int i = 0; i < {}; i++) printf(\"{} \", result[i]);\n",
+                    len,
+                    printf_format(elem)
+                ));
+                main_body.push_str("    printf(\"\\n\");\n");
+            } else {
+                main_body.push_str(&format!(
+                    "    printf(\"{}\\n\", result);\n",
+                    printf_format(&sig.return_type)
+                ));
+            }
         }
     }
 
+    let first_uses_data = get_first_test_inputs(challenge).is_some_and(|inputs| {
+        inputs
+            .iter()
+            .any(|(name, value)| external_array(0, name, value).is_some())
+    });
+    let data_include = if first_uses_data {
+        format!("#include \"{}\"\n\n", DATA_HEADER)
+    } else {
+        String::new()
+    };
+
     let includes = if sig.return_type == RustType::Void && !has_mut_ref_params(sig) {
         "#include <stdio.h>\n"
     } else {
         "#include <stdio.h>\n#include <stdbool.h>\n#include <stdlib.h>\n"
     };
-
-    let solution_c_no_main = format!(
-        r#"{includes}
-{ret_type} {name}({params}) {{
-{default_return}}}"#,
-        includes = includes,
-        ret_type = ret_type,
-        name = sig.name,
-        params = params_str.join(", "),
-        default_return = default_return,
-    );
+    let includes = format!("{}{}", includes, stdint_include(sig));
 
     let solution_c = format!(
         r#"{includes}
 {ret_type} {name}({params}) {{
 {default_return}}}
 
-int main() {{
+// `make test` compiles this file with -DCODLE_TEST and links it against
+// test_solution.c, so this is the only copy of your solution.
+#ifndef CODLE_TEST
+{data_include}int main() {{
 {main_body}    return 0;
-}}"#,
+}}
+#endif"#,
         includes = includes,
         ret_type = ret_type,
         name = sig.name,
-        params = params_str.join(", "),
-        default_return = default_return,
+        params = c_param_list(sig, string_len),
+        default_return = options.body_or("    ", &default_return),
+        data_include = data_include,
         main_body = main_body,
     );
 
-    let tests_code = generate_c_tests(sig, &challenge.tests);
-
-    let metadata = ProjectMetadata::new(
+    let tests_code = generate_c_tests(sig, &challenge.tests, string_len);
+    let data_section = generate_c_test_data(sig, &challenge.tests)
+        .map(|data| {
+            format!(
+                "\necho \"[codle] step: writing {header}\"\ncat > {header} << 'DATA'\n{}\nDATA\n",
+                escape_for_heredoc(&data),
+                header = DATA_HEADER
+            )
+        })
+        .unwrap_or_default();
+
+    let mut metadata = ProjectMetadata::new(
         challenge.name.clone(),
         Language::C,
         difficulty,
         sig.name.clone(),
-        Some(chrono::Local::now().to_rfc3339()),
+        options.initialized_at(),
         challenge.difficulty,
     );
+    metadata.challenge_index = options.challenge_index;
     let metadata_content = metadata_json(&metadata);
 
-    let makefile = r#"CC = gcc
+    let mut makefile = r#"CC = gcc
 CFLAGS = -Wall -Wextra -std=c11 -g
 TARGET = solution
 TEST_TARGET = test_runner
 SRC = solution.c
 TEST_SRC = test_solution.c
+DATA = test_data.h
 
 all: $(TARGET)
 
-$(TARGET): $(SRC)
+$(TARGET): $(SRC) $(wildcard $(DATA))
 	$(CC) $(CFLAGS) -o $(TARGET) $(SRC)
 
 test: $(TEST_TARGET)
 	./$(TEST_TARGET)
 
-$(TEST_TARGET): $(TEST_SRC) solution_lib.c
-	$(CC) $(CFLAGS) -o $(TEST_TARGET) solution_lib.c $(TEST_SRC)
+$(TEST_TARGET): $(SRC) $(TEST_SRC) $(wildcard $(DATA))
+	$(CC) $(CFLAGS) -DCODLE_TEST -o $(TEST_TARGET) $(SRC) $(TEST_SRC)
 
 run: $(TARGET)
 	./$(TARGET)
@@ -254,62 +465,314 @@ run: $(TARGET)
 clean:
 	rm -f $(TARGET) $(TEST_TARGET)
 
-.PHONY: all run clean test"#;
+.PHONY: all run clean test"#.to_string();
+    if options.with_bench {
+        makefile.push_str(BENCH_MAKEFILE_RULES);
+    }
 
     let setup_sh = format!(
         r#"#!/bin/bash
 set -e
 
+echo "[codle] step: checking required tools"
 {}
 
+echo "[codle] step: writing Makefile"
 cat > Makefile << 'MAKEFILE'
 {}
 MAKEFILE
 
+# Older scaffolds linked the tests against a copy of the solution; solution.c
+# is now the only source, so a leftover copy would just go stale.
+rm -f solution_lib.c
+
+echo "[codle] step: writing solution.c"
 cat > solution.c << 'SOLUTION'
 {}
 SOLUTION
 
-cat > solution_lib.c << 'SOLUTION_LIB'
-{}
-SOLUTION_LIB
-
+echo "[codle] step: writing test_solution.c"
 cat > test_solution.c << 'TESTS'
 {}
 TESTS
-
+{}
+echo "[codle] step: writing .codle.json"
 cat > .codle.json << 'METADATA'
 {}
 METADATA
 
 echo "Run: make && ./solution"
 echo "Test: make test"
-"#,
-        require_commands(&["gcc", "make"]),
+{}"#,
+        require_commands(Language::C.required_commands()),
         makefile,
         escape_for_heredoc(&solution_c),
-        escape_for_heredoc(&solution_c_no_main),
         escape_for_heredoc(&tests_code),
-        metadata_content
+        data_section,
+        metadata_content,
+        bench_section(challenge, sig, options)
     );
 
     write_setup_script(output_dir, &setup_sh)
 }
 
-pub(super) fn generate_c_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+/// Appended to the Makefile by `--with-bench`. The bench is built with -O2 so
+/// it measures optimized code.
+const BENCH_MAKEFILE_RULES: &str = r#"
+
+BENCH_TARGET = bench_runner
+BENCH_SRC = bench_solution.c
+
+bench: $(BENCH_TARGET)
+	./$(BENCH_TARGET)
+
+$(BENCH_TARGET): $(SRC) $(BENCH_SRC)
+	$(CC) $(CFLAGS) -O2 -DCODLE_TEST -o $(BENCH_TARGET) $(SRC) $(BENCH_SRC)
+
+clean: clean-bench
+
+clean-bench:
+	rm -f $(BENCH_TARGET)
+
+.PHONY: bench clean-bench"#;
+
+fn bench_section(challenge: &Challenge, sig: &FunctionSignature, options: &ScaffoldOptions) -> String {
+    if !options.with_bench {
+        return String::new();
+    }
+
+    format!(
+        r#"
+echo "[codle] step: writing bench_solution.c"
+cat > bench_solution.c << 'BENCH'
+{}
+BENCH
+
+echo "Bench: make bench"
+"#,
+        escape_for_heredoc(&generate_c_bench(challenge, sig))
+    )
+}
+
+/// Timed loop over the largest test input. Inputs are redeclared on every run
+/// so an in-place solution always starts from the original data, and a
+/// returned array, which the solution allocates, is freed each time.
+pub(super) fn generate_c_bench(challenge: &Challenge, sig: &FunctionSignature) -> String {
+    let empty = serde_json::Map::new();
+    let inputs = largest_test_inputs(challenge).unwrap_or(&empty);
+    let mut call_args = Vec::new();
+    let declarations = declare_inputs(sig, inputs, "        ", &mut call_args, challenge.c_string_len, None);
+    let call = format!("{}({})", sig.name, call_args.join(", "));
+    let call = if matches!(sig.return_type, RustType::Vec(_)) { format!("free({})", call) } else { call };
+
+    format!(
+        r#"#include <stdio.h>
+#include <stdbool.h>
+#include <stdlib.h>
+#include <math.h>
+#include <time.h>
+{stdint}
+// Forward declaration - implemented in solution.c
+{ret_type} {name}({params});
+
+#define RUNS 1000
+
+int main() {{
+    clock_t start = clock();
+    for (int run = 0; run < RUNS; run++) {{
+{declarations}        {call};
+    }}
+    double elapsed = (double)(clock() - start) / CLOCKS_PER_SEC;
+    printf("{name}: %.2f us per call over %d runs\n", elapsed / RUNS * 1e6, RUNS);
+    return 0;
+}}"#,
+        stdint = stdint_include(sig),
+        ret_type = c_return_type(sig),
+        name = sig.name,
+        params = c_param_list(sig, challenge.c_string_len),
+        declarations = declarations,
+        call = call,
+    )
+}
+
+/// `#include <stdint.h>` if `sig` uses a fixed-width integer, so headers
+/// and sources that don't need it keep their usual includes.
+fn stdint_include(sig: &FunctionSignature) -> &'static str {
+    if super::uses_fixed_width_int(sig) {
+        "#include <stdint.h>\n"
+    } else {
+        ""
+    }
+}
+
+/// The `printf` conversion for a value of type `ty`; `bool` and the integers
+/// narrower than `int` are promoted to `int`, so `bool` prints as 0 or 1.
+fn printf_format(ty: &RustType) -> &'static str {
+    match ty {
+        RustType::I64 => "%lld",
+        RustType::U32 => "%u",
+        RustType::U64 => "%llu",
+        RustType::Usize => "%zu",
+        // A `float` is promoted to `double` when passed to `printf`.
+        RustType::F32 | RustType::F64 => "%g",
+        RustType::String => "%s",
+        RustType::Char => "%c",
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => printf_format(inner),
+        _ => "%d",
+    }
+}
+
+/// Condition that is true when `actual` doesn't match `expected`. `!=` on
+/// `char*` compares addresses, so string elements go through `strcmp`, and
+/// floats are allowed to be off by `float_epsilon`.
+fn element_differs(actual: &str, expected: &Value, ty: &RustType) -> String {
+    let expected = super::render_value(expected, ty, Language::C);
+    if matches!(ty, RustType::String) {
+        format!("strcmp({}, {}) != 0", actual, expected)
+    } else if super::is_float(ty) {
+        format!(
+            "!({0} == {1} || fabs({0} - {1}) < {2})",
+            actual,
+            expected,
+            super::float_epsilon(ty)
+        )
+    } else {
+        format!("{} != {}", actual, expected)
+    }
+}
+
+/// Declares each input as a local, arrays as `<name>_arr` plus `<name>_len`,
+/// and pushes the matching call arguments. With a `test_index`, large arrays
+/// point into `DATA_HEADER`.
+/// Statements clearing `test_passed` unless array `var` holds the expected
+/// elements: in order, or for an unordered test, each distinct element as
+/// many times as expected.
+fn array_checks(var: &str, test: &TestCase, elem: &RustType) -> String {
+    let expected = test.expected.as_array().map(Vec::as_slice).unwrap_or_default();
+    let mut code = String::new();
+    if test.unordered {
+        for (value, count) in super::element_counts(&test.expected) {
+            code.push_str(&format!(
+                "        {{ int n = 0; for (int k = 0; k < {}; k++) if (!({})) n++; if (n != {}) test_passed = 0; }}\n",
+                expected.len(),
+                element_differs(&format!("{}[k]", var), value, elem),
+                count
+            ));
+        }
+    } else {
+        for (j, value) in expected.iter().enumerate() {
+            code.push_str(&format!(
+                "        if ({}) test_passed = 0;\n",
+                element_differs(&format!("{}[{}]", var, j), value, elem)
+            ));
+        }
+    }
+    code
+}
+
+/// Statements printing the expected elements of array `var` next to the
+/// ones it holds, for a failed test. `label` names the array when a test
+/// checks more than one.
+fn array_report(var: &str, label: &str, test: &TestCase, elem: &RustType) -> String {
+    let len = test.expected.as_array().map_or(0, Vec::len);
+    let expected = super::shown_value(&test.expected, &RustType::Vec(Box::new(elem.clone())));
+    let (format, arg) = match elem {
+        RustType::Bool => ("%s".to_string(), format!("{}[k] ? \"true\" : \"false\"", var)),
+        RustType::String | RustType::Char => (format!("\\\"{}\\\"", printf_format(elem)), format!("{}[k]", var)),
+        _ => (printf_format(elem).to_string(), format!("{}[k]", var)),
+    };
+    format!(
+        concat!(
+            "            printf(\"  {label}expected: {expected}\\n\");\n",
+            "            printf(\"  {label}got:      [\");\n",
+            "            for (int k = 0; k < {len}; k++) printf(k ? \", {format}\" : \"{format}\", {arg});\n",
+            "            printf(\"]\\n\");\n",
+        ),
+        label = label,
+        expected = super::escape_literal(&expected, '"', Language::C).replace('%', "%%"),
+        len = len,
+        format = format,
+        arg = arg,
+    )
+}
+
+/// The PASS/FAIL lines after `array_checks`, printing `reports` on failure.
+fn array_verdict(test_num: &str, reports: &str) -> String {
+    format!(
+        "        if (test_passed) {{ printf(\"Test {0}: PASS\\n\"); passed++; }}\n        else {{\n            printf(\"Test {0}: FAIL\\n\");\n{1}            failed++;\n        }}\n",
+        test_num, reports
+    )
+}
+
+fn declare_inputs(
+    sig: &FunctionSignature,
+    inputs: &serde_json::Map<String, Value>,
+    indent: &str,
+    call_args: &mut Vec<String>,
+    string_len: bool,
+    test_index: Option<usize>,
+) -> String {
+    let mut code = String::new();
+    for p in &sig.params {
+        let inner_ty = unwrap_ref(&p.ty);
+        if let Some(val) = inputs.get(&p.name) {
+            if let Some(elem) = matrix_element(inner_ty) {
+                let var = format!("{}_arr", p.name);
+                code.push_str(&declare_matrix(indent, &var, &p.name, elem, val));
+                call_args.push(var);
+                call_args.push(format!("{}_len", p.name));
+                call_args.push(format!("{}_col_lens", p.name));
+            } else if let RustType::Vec(elem) = inner_ty {
+                let len = val.as_array().map(|a| a.len()).unwrap_or(0);
+                let external = test_index.and_then(|i| external_array(i, &p.name, val));
+                code.push_str(&declare_array(indent, &format!("{}_arr", p.name), elem, val, external));
+                code.push_str(&format!("{}int {}_len = {};\n", indent, p.name, len));
+                call_args.push(format!("{}_arr", p.name));
+                call_args.push(format!("{}_len", p.name));
+            } else {
+                code.push_str(&format!(
+                    "{}{} {} = {};\n",
+                    indent,
+                    super::translate_type(inner_ty, Language::C),
+                    p.name,
+                    super::render_value(val, inner_ty, Language::C)
+                ));
+                call_args.push(p.name.clone());
+                if string_len && p.ty == RustType::String {
+                    code.push_str(&string_len_declaration(&p.name, val, indent));
+                    call_args.push(format!("{}_len", p.name));
+                }
+            }
+        }
+    }
+    code
+}
+
+pub(super) fn generate_c_tests(sig: &FunctionSignature, tests: &[TestCase], string_len: bool) -> String {
     let mut test_code = String::new();
     test_code.push_str("#include <stdio.h>\n");
     test_code.push_str("#include <stdbool.h>\n");
     test_code.push_str("#include <stdlib.h>\n");
-    test_code.push_str("#include <string.h>\n\n");
+    test_code.push_str("#include <string.h>\n");
+    test_code.push_str("#include <math.h>\n");
+    test_code.push_str(stdint_include(sig));
+    if generate_c_test_data(sig, tests).is_some() {
+        test_code.push_str(&format!("#include \"{}\"\n", DATA_HEADER));
+    }
+    test_code.push('\n');
 
-    test_code.push_str("// Forward declaration - implemented in solution.c\n");
+    test_code.push_str(&format!(
+        "// Forward declaration - implemented in solution.c\n{} {}({});\n",
+        c_return_type(sig),
+        sig.name,
+        c_param_list(sig, string_len)
+    ));
 
     test_code.push_str("\nint main() {\n");
     test_code.push_str("    int passed = 0, failed = 0;\n\n");
 
     for (i, test) in tests.iter().enumerate() {
-        let test_num = i + 1;
+        let test_num = super::test_case_label(test, i);
 
         if let Some(inputs) = test.input.as_object() {
             test_code.push_str(&format!("    // Test {}\n", test_num));
@@ -318,93 +781,32 @@ pub(super) fn generate_c_tests(sig: &FunctionSignature, tests: &[TestCase]) -> S
             let mut call_args = Vec::new();
 
             if is_void_with_mut_ref(sig) {
-                for p in &sig.params {
-                    let inner_ty = unwrap_mut_ref(&p.ty);
-                    if let Some(val) = inputs.get(&p.name) {
-                        if let RustType::Vec(elem) = inner_ty {
-                            let arr_val = super::render_value(val, inner_ty, Language::C);
-                            let len = val.as_array().map(|a| a.len()).unwrap_or(0);
-                            test_code.push_str(&format!(
-                                "        {} {}_arr[] = {};\n",
-                                super::translate_type(elem, Language::C),
-                                p.name,
-                                arr_val
-                            ));
-                            test_code.push_str(&format!("        int {}_len = {};\n", p.name, len));
-                            call_args.push(format!("{}_arr", p.name));
-                            call_args.push(format!("{}_len", p.name));
-                        } else {
-                            test_code.push_str(&format!(
-                                "        {} {} = {};\n",
-                                super::translate_type(inner_ty, Language::C),
-                                p.name,
-                                super::render_value(val, inner_ty, Language::C)
-                            ));
-                            call_args.push(p.name.clone());
-                        }
-                    }
-                }
+                test_code.push_str(&declare_inputs(sig, inputs, "        ", &mut call_args, string_len, Some(i)));
                 test_code.push_str(&format!(
                     "        {}({});\n",
                     sig.name,
                     call_args.join(", ")
                 ));
 
-                if let Some(p) = sig
-                    .params
+                let outputs = super::mut_ref_outputs(sig, test);
+                let (checks, reports): (Vec<String>, Vec<String>) = outputs
                     .iter()
-                    .find(|p| matches!(&p.ty, RustType::MutRef(_)))
-                {
-                    let inner = unwrap_mut_ref(&p.ty);
-                    if let RustType::Vec(_) = inner {
-                        if let Some(expected_arr) = test.expected.as_array() {
-                            test_code.push_str("        int test_passed = 1;\n");
-                            for (j, expected_val) in expected_arr.iter().enumerate() {
-                                test_code.push_str(&format!(
-                                    "        if ({}_arr[{}] != {}) test_passed = 0;\n",
-                                    p.name,
-                                    j,
-                                    super::render_value(expected_val, &RustType::I32, Language::C)
-                                ));
-                            }
-                            test_code.push_str(&format!(
-                                "        if (test_passed) {{ printf(\"Test {}: PASS\\n\"); passed++; }}\n",
-                                test_num
-                            ));
-                            test_code.push_str(&format!(
-                                "        else {{ printf(\"Test {}: FAIL\\n\"); failed++; }}\n",
-                                test_num
-                            ));
+                    .filter_map(|(p, output)| match unwrap_ref(&p.ty) {
+                        RustType::Vec(elem) if output.expected.is_array() => {
+                            let var = format!("{}_arr", p.name);
+                            let label = if outputs.len() > 1 { format!("{} ", p.name) } else { String::new() };
+                            Some((array_checks(&var, output, elem), array_report(&var, &label, output, elem)))
                         }
-                    }
+                        _ => None,
+                    })
+                    .unzip();
+                if !checks.is_empty() {
+                    test_code.push_str("        int test_passed = 1;\n");
+                    test_code.push_str(&checks.concat());
+                    test_code.push_str(&array_verdict(&test_num, &reports.concat()));
                 }
             } else {
-                for p in &sig.params {
-                    let inner_ty = unwrap_mut_ref(&p.ty);
-                    if let Some(val) = inputs.get(&p.name) {
-                        if let RustType::Vec(elem) = inner_ty {
-                            let arr_val = super::render_value(val, inner_ty, Language::C);
-                            let len = val.as_array().map(|a| a.len()).unwrap_or(0);
-                            test_code.push_str(&format!(
-                                "        {} {}_arr[] = {};\n",
-                                super::translate_type(elem, Language::C),
-                                p.name,
-                                arr_val
-                            ));
-                            test_code.push_str(&format!("        int {}_len = {};\n", p.name, len));
-                            call_args.push(format!("{}_arr", p.name));
-                            call_args.push(format!("{}_len", p.name));
-                        } else {
-                            test_code.push_str(&format!(
-                                "        {} {} = {};\n",
-                                super::translate_type(inner_ty, Language::C),
-                                p.name,
-                                super::render_value(val, inner_ty, Language::C)
-                            ));
-                            call_args.push(p.name.clone());
-                        }
-                    }
-                }
+                test_code.push_str(&declare_inputs(sig, inputs, "        ", &mut call_args, string_len, Some(i)));
 
                 match &sig.return_type {
                     RustType::Vec(inner) => {
@@ -416,21 +818,11 @@ pub(super) fn generate_c_tests(sig: &FunctionSignature, tests: &[TestCase]) -> S
                         ));
                         if let Some(expected_arr) = test.expected.as_array() {
                             test_code.push_str("        int test_passed = 1;\n");
-                            for (j, expected_val) in expected_arr.iter().enumerate() {
-                                test_code.push_str(&format!(
-                                    "        if (result[{}] != {}) test_passed = 0;\n",
-                                    j,
-                                    super::render_value(expected_val, inner, Language::C)
-                                ));
+                            if expected_arr.is_empty() {
+                                test_code.push_str("        (void)result;\n");
                             }
-                            test_code.push_str(&format!(
-                                "        if (test_passed) {{ printf(\"Test {}: PASS\\n\"); passed++; }}\n",
-                                test_num
-                            ));
-                            test_code.push_str(&format!(
-                                "        else {{ printf(\"Test {}: FAIL\\n\"); failed++; }}\n",
-                                test_num
-                            ));
+                            test_code.push_str(&array_checks("result", test, inner));
+                            test_code.push_str(&array_verdict(&test_num, &array_report("result", "", test, inner)));
                         }
                     }
                     _ => {
@@ -441,13 +833,36 @@ pub(super) fn generate_c_tests(sig: &FunctionSignature, tests: &[TestCase]) -> S
                             call_args.join(", ")
                         ));
                         let expected = super::render_value(&test.expected, &sig.return_type, Language::C);
+                        let condition = if super::expects_nan(&sig.return_type, &test.expected) {
+                            "isnan(result)".to_string()
+                        } else if sig.return_type == RustType::String {
+                            format!("result != NULL && strcmp(result, {}) == 0", expected)
+                        } else if super::compares_approximately(&sig.return_type, &test.expected) {
+                            format!(
+                                "result == {0} || fabs(result - {0}) < {1}",
+                                expected,
+                                super::float_epsilon(&sig.return_type)
+                            )
+                        } else {
+                            format!("result == {}", expected)
+                        };
+                        // `printf` has no `bool` format, and strings and chars are
+                        // quoted like the expected literal.
+                        let (got, actual) = match &sig.return_type {
+                            RustType::Bool => ("%s", "result ? \"true\" : \"false\""),
+                            RustType::String => ("\\\"%s\\\"", "result ? result : \"(null)\""),
+                            RustType::Char => ("'%c'", "result"),
+                            ty => (printf_format(ty), "result"),
+                        };
+                        // The expected literal is shown inside the format string.
+                        let shown = super::escape_literal(&expected, '"', Language::C).replace('%', "%%");
                         test_code.push_str(&format!(
-                            "        if (result == {}) {{ printf(\"Test {}: PASS\\n\"); passed++; }}\n",
-                            expected, test_num
+                            "        if ({}) {{ printf(\"Test {}: PASS\\n\"); passed++; }}\n",
+                            condition, test_num
                         ));
                         test_code.push_str(&format!(
-                            "        else {{ printf(\"Test {}: FAIL (expected {}, got %d)\\n\", result); failed++; }}\n",
-                            test_num, expected
+                            "        else {{ printf(\"Test {}: FAIL (expected {}, got {})\\n\", {}); failed++; }}\n",
+                            test_num, shown, got, actual
                         ));
                     }
                 }
@@ -464,6 +879,26 @@ pub(super) fn generate_c_tests(sig: &FunctionSignature, tests: &[TestCase]) -> S
     test_code
 }
 
+/// Explains a `make test` that never got as far as compiling because a
+/// generated file is gone, e.g. deleted by hand or never written because
+/// setup.sh stopped early. Shared by the C, C++ and Fortran runners.
+pub(super) fn diagnose_make_failure(output: &str) -> Option<String> {
+    let missing = output.lines().find_map(|line| {
+        if let Some(rest) = line.split("No rule to make target '").nth(1) {
+            let target = rest.split('\'').next().unwrap_or_default();
+            // Only the Makefile itself can be missing a top-level rule.
+            return Some(if line.contains("needed by") { target } else { "Makefile" });
+        }
+        line.strip_suffix(": No such file or directory")
+            .and_then(|rest| rest.rsplit("fatal error: ").next())
+            .filter(|_| line.contains("fatal error: "))
+    })?;
+    Some(format!(
+        "Build files are missing ({}). Re-run ./setup.sh to regenerate them.",
+        missing
+    ))
+}
+
 pub(super) fn parse_c_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
     let mut passed = 0;
     let mut failed = 0;
@@ -499,5 +934,7 @@ pub(super) fn parse_c_output(_stdout: &str, _stderr: &str, combined: &str) -> Re
         failed,
         total: passed + failed,
         output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
     })
 }
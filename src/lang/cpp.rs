@@ -6,19 +6,37 @@ use crate::models::{
 };
 use super::{
     write_setup_script, require_commands, escape_for_heredoc,
-    is_void_with_mut_ref, get_first_test_inputs, unwrap_mut_ref,
+    is_void_with_mut_ref, get_first_test_inputs, largest_test_inputs, unwrap_ref, SpecialFloat,
+    ScaffoldOptions,
 };
 
 pub(super) fn translate_type_cpp(ty: &RustType) -> String {
     match ty {
+        RustType::I8 => "int8_t".to_string(),
+        RustType::I16 => "int16_t".to_string(),
         RustType::I32 => "int".to_string(),
+        RustType::I64 => "long long".to_string(),
+        RustType::U8 => "uint8_t".to_string(),
+        RustType::U16 => "uint16_t".to_string(),
+        RustType::U32 => "uint32_t".to_string(),
+        RustType::U64 => "unsigned long long".to_string(),
+        RustType::F32 => "float".to_string(),
         RustType::F64 => "double".to_string(),
         RustType::Usize => "size_t".to_string(),
         RustType::Bool => "bool".to_string(),
-        RustType::String => "std::string".to_string(),
+        RustType::String | RustType::Str => "std::string".to_string(),
         RustType::Char => "char".to_string(),
         RustType::Vec(inner) => format!("std::vector<{}>", translate_type_cpp(inner)),
+        RustType::Option(inner) => format!("std::optional<{}>", translate_type_cpp(inner)),
+        RustType::Map(key, value) => format!(
+            "std::unordered_map<{}, {}>",
+            translate_type_cpp(key),
+            translate_type_cpp(value)
+        ),
+        RustType::Ref(inner) => format!("const {}&", translate_type_cpp(inner)),
         RustType::MutRef(inner) => format!("{}&", translate_type_cpp(inner)),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
         RustType::Void => "void".to_string(),
     }
 }
@@ -26,21 +44,28 @@ pub(super) fn translate_type_cpp(ty: &RustType) -> String {
 pub(super) fn render_value_cpp(value: &Value, ty: &RustType) -> String {
     match ty {
         RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
-        RustType::F64 => {
-            let n = value.as_f64().unwrap_or(0.0);
-            if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                format!("{}", n)
-            }
-        }
+        RustType::I8 | RustType::I16 | RustType::U8 | RustType::U16 => super::integer_literal(value),
+        // 9223372036854775808 doesn't fit a `long long`, so negating it would
+        // make an unsigned constant; `LLONG_MIN` is written the same way.
+        RustType::I64 if value.as_i64() == Some(i64::MIN) => "(-9223372036854775807LL - 1)".to_string(),
+        RustType::I64 => format!("{}LL", super::integer_literal(value)),
+        RustType::U32 => format!("{}U", super::integer_literal(value)),
+        RustType::U64 => format!("{}ULL", super::integer_literal(value)),
+        RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "std::numeric_limits<double>::infinity()".to_string(),
+            Some(SpecialFloat::NegInfinity) => "-std::numeric_limits<double>::infinity()".to_string(),
+            Some(SpecialFloat::NaN) => "std::numeric_limits<double>::quiet_NaN()".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::F32 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "std::numeric_limits<float>::infinity()".to_string(),
+            Some(SpecialFloat::NegInfinity) => "-std::numeric_limits<float>::infinity()".to_string(),
+            Some(SpecialFloat::NaN) => "std::numeric_limits<float>::quiet_NaN()".to_string(),
+            None => format!("{}f", super::format_float(value.as_f64().unwrap_or(0.0))),
+        },
         RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
-        RustType::String => format!("\"{}\"", value.as_str().unwrap_or("")),
-        RustType::Char => {
-            let s = value.as_str().unwrap_or("?");
-            let c = s.chars().next().unwrap_or('?');
-            format!("'{}'", c)
-        }
+        RustType::String | RustType::Str => super::string_literal(value, Language::Cpp),
+        RustType::Char => super::char_literal(value, Language::Cpp),
         RustType::Vec(inner) => {
             if let Some(arr) = value.as_array() {
                 let items: Vec<String> = arr.iter().map(|v| render_value_cpp(v, inner)).collect();
@@ -49,16 +74,199 @@ pub(super) fn render_value_cpp(value: &Value, ty: &RustType) -> String {
                 "{}".to_string()
             }
         }
-        RustType::MutRef(inner) => render_value_cpp(value, inner),
+        RustType::Option(_) if value.is_null() => "std::nullopt".to_string(),
+        RustType::Option(inner) => render_value_cpp(value, inner),
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("{{{}, {}}}", render_value_cpp(&k, key), render_value_cpp(v, inner)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_cpp(value, inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
         RustType::Void => "".to_string(),
     }
 }
 
+/// `==` for everything but floats, which may be off by `float_epsilon`;
+/// vectors of floats go through the generated `approx_equal` helper.
+fn equality(actual: &str, expected: &Value, ty: &RustType) -> String {
+    let approximate = super::compares_approximately(ty, expected);
+    let expected = render_expected_cpp(expected, ty);
+    if super::is_float_vec(ty) {
+        format!("approx_equal({}, {})", actual, expected)
+    } else if approximate {
+        format!(
+            "({0} == {1} || std::fabs({0} - {1}) < {2})",
+            actual,
+            expected,
+            super::float_epsilon(ty)
+        )
+    } else {
+        format!("{} == {}", actual, expected)
+    }
+}
+
+/// Braced initializer lists can't appear as an operand of `==`, so vectors
+/// and maps are spelled with their full type when used as an expected value.
+fn render_expected_cpp(value: &Value, ty: &RustType) -> String {
+    match ty {
+        RustType::Vec(_) | RustType::Map(..) => {
+            format!("{}{}", translate_type_cpp(ty), render_value_cpp(value, ty))
+        }
+        RustType::Option(inner) if !value.is_null() => render_expected_cpp(value, inner),
+        _ => render_value_cpp(value, ty),
+    }
+}
+
+/// The condition for `actual` passing `test`. An unordered vector is first
+/// sorted in place by statements added to `code`, then compared with a
+/// sorted `expected`.
+fn test_condition(code: &mut String, actual: &str, test: &TestCase, ty: &RustType) -> String {
+    if super::expects_nan(ty, &test.expected) {
+        return format!("std::isnan({})", actual);
+    }
+    if !super::compares_unordered(test, ty) {
+        return equality(actual, &test.expected, ty);
+    }
+    code.push_str(&format!("        std::sort({0}.begin(), {0}.end());\n", actual));
+    code.push_str(&format!(
+        "        {} expected = {};\n",
+        translate_type_cpp(ty),
+        render_value_cpp(&test.expected, ty)
+    ));
+    code.push_str("        std::sort(expected.begin(), expected.end());\n");
+    if super::is_float_vec(ty) {
+        format!("approx_equal({}, expected)", actual)
+    } else {
+        format!("{} == expected", actual)
+    }
+}
+
+/// Formats any value a solution can return for the expected/got lines of a
+/// failed test, in the same JSON-like style as `shown_value`. `int8_t` and
+/// `uint8_t` are character types, so they get overloads printing numbers.
+const SHOW_HELPER: &str = r#"template <typename T> std::string show(const T& x);
+template <typename T> std::string show(const std::vector<T>& xs);
+template <typename T> std::string show(const std::optional<T>& x);
+template <typename K, typename V> std::string show(const std::unordered_map<K, V>& m);
+std::string show(const std::string& s) { return "\"" + s + "\""; }
+std::string show(char c) { return std::string("\"") + c + "\""; }
+std::string show(bool b) { return b ? "true" : "false"; }
+std::string show(signed char n) { return std::to_string(n); }
+std::string show(unsigned char n) { return std::to_string(n); }
+
+template <typename T> std::string show(const T& x) {
+    std::ostringstream out;
+    out << x;
+    return out.str();
+}
+
+template <typename T> std::string show(const std::vector<T>& xs) {
+    std::string s = "[";
+    for (size_t i = 0; i < xs.size(); i++) {
+        const T& x = xs[i];
+        s += (i ? ", " : "") + show(x);
+    }
+    return s + "]";
+}
+
+template <typename T> std::string show(const std::optional<T>& x) {
+    return x ? show(*x) : "null";
+}
+
+template <typename K, typename V> std::string show(const std::unordered_map<K, V>& m) {
+    std::string s = "{";
+    for (const auto& entry : m) {
+        s += (s.size() > 1 ? ", " : "") + show(entry.first) + ": " + show(entry.second);
+    }
+    return s + "}";
+}
+"#;
+
+/// Statements printing what `actual` should have been and what it holds,
+/// for a failed test. `label` names the output when a test checks several.
+fn failure_report(actual: &str, label: &str, test: &TestCase, ty: &RustType) -> String {
+    format!(
+        "            std::cout << \"  {label}expected: {expected}\" << std::endl;\n            std::cout << \"  {label}got:      \" << show({actual}) << std::endl;\n",
+        label = label,
+        expected = super::escape_literal(&super::shown_value(&test.expected, ty), '"', Language::Cpp),
+        actual = actual,
+    )
+}
+
+/// The PASS/FAIL lines for `condition`, printing `reports` on failure.
+fn verdict(condition: &str, test_num: &str, reports: &str) -> String {
+    format!(
+        "        if ({0}) {{ std::cout << \"Test {1}: PASS\" << std::endl; passed++; }}\n        else {{\n            std::cout << \"Test {1}: FAIL\" << std::endl;\n{2}            failed++;\n        }}\n",
+        condition, test_num, reports
+    )
+}
+
+/// `expr` as written to a stream. `int8_t` and `uint8_t` would print as
+/// characters, so a unary `+` promotes them to `int`.
+fn streamed(expr: &str, ty: &RustType) -> String {
+    match ty {
+        RustType::I8 | RustType::U8 => format!("+{}", expr),
+        _ => expr.to_string(),
+    }
+}
+
+/// Statements printing a vector `name` of `inner`, one line per row for a
+/// grid.
+fn print_vector(name: &str, inner: &RustType) -> String {
+    match inner {
+        RustType::Vec(elem) => format!(
+            "    for (const auto& row : {}) {{\n        for (const auto& x : row) std::cout << {} << \" \";\n        std::cout << std::endl;\n    }}\n",
+            name,
+            streamed("x", elem)
+        ),
+        elem => format!(
+            "    for (const auto& x : {}) std::cout << {} << \" \";\n    std::cout << std::endl;\n",
+            name,
+            streamed("x", elem)
+        ),
+    }
+}
+
+/// Statements printing `name`, which may be a vector, an optional or a map.
+fn print_value(name: &str, ty: &RustType) -> String {
+    match ty {
+        RustType::Vec(inner) => print_vector(name, inner),
+        RustType::Map(key, value) => {
+            let entry: String = print_value("entry.second", value)
+                .lines()
+                .map(|line| format!("    {}\n", line))
+                .collect();
+            format!(
+                "    for (const auto& entry : {}) {{\n        std::cout << {} << \": \";\n{}    }}\n",
+                name,
+                streamed("entry.first", key),
+                entry
+            )
+        }
+        RustType::Option(inner) => {
+            let present: String = print_value(&format!("*{}", name), inner)
+                .lines()
+                .map(|line| format!("    {}\n", line))
+                .collect();
+            format!(
+                "    if ({}) {{\n{}    }} else {{\n        std::cout << \"nullopt\" << std::endl;\n    }}\n",
+                name, present
+            )
+        }
+        _ => format!("    std::cout << {} << std::endl;\n", streamed(name, ty)),
+    }
+}
+
 pub(super) fn generate_cpp(
     challenge: &Challenge,
     sig: &FunctionSignature,
     difficulty: Difficulty,
     output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
     let params_str: Vec<String> = sig
         .params
@@ -72,7 +280,7 @@ pub(super) fn generate_cpp(
         RustType::Void => String::new(),
         RustType::Bool => "    return false;\n".to_string(),
         RustType::I32 | RustType::Usize => "    return 0;\n".to_string(),
-        RustType::F64 => "    return 0.0;\n".to_string(),
+        RustType::F32 | RustType::F64 => "    return 0.0;\n".to_string(),
         RustType::String => "    return \"\";\n".to_string(),
         RustType::Vec(_) => "    return {};\n".to_string(),
         _ => "    return {};\n".to_string(),
@@ -82,7 +290,7 @@ pub(super) fn generate_cpp(
     if let Some(inputs) = get_first_test_inputs(challenge) {
         if is_void_with_mut_ref(sig) {
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
+                let inner_ty = unwrap_ref(&p.ty);
                 if let Some(val) = inputs.get(&p.name) {
                     main_body.push_str(&format!(
                         "    {} {} = {};\n",
@@ -95,16 +303,7 @@ pub(super) fn generate_cpp(
             let call_args: Vec<String> = sig.params.iter().map(|p| p.name.clone()).collect();
             main_body.push_str(&format!("    {}({});\n", sig.name, call_args.join(", ")));
             if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
-                let inner_ty = unwrap_mut_ref(&p.ty);
-                if let RustType::Vec(_) = inner_ty {
-                    main_body.push_str(&format!(
-                        "    for (const auto& x : {}) std::cout << x << \" \";\n",
-                        p.name
-                    ));
-                    main_body.push_str("    std::cout << std::endl;\n");
-                } else {
-                    main_body.push_str(&format!("    std::cout << {} << std::endl;\n", p.name));
-                }
+                main_body.push_str(&print_value(&p.name, unwrap_ref(&p.ty)));
             }
         } else {
             let mut args = Vec::new();
@@ -112,9 +311,9 @@ pub(super) fn generate_cpp(
                 if let Some(val) = inputs.get(&p.name) {
                     main_body.push_str(&format!(
                         "    {} {} = {};\n",
-                        super::translate_type(unwrap_mut_ref(&p.ty), Language::Cpp),
+                        super::translate_type(unwrap_ref(&p.ty), Language::Cpp),
                         p.name,
-                        super::render_value(val, unwrap_mut_ref(&p.ty), Language::Cpp)
+                        super::render_value(val, unwrap_ref(&p.ty), Language::Cpp)
                     ));
                     args.push(p.name.clone());
                 }
@@ -124,23 +323,14 @@ pub(super) fn generate_cpp(
                 sig.name,
                 args.join(", ")
             ));
-            if matches!(&sig.return_type, RustType::Vec(_)) {
-                main_body
-                    .push_str("    for (const auto& x : result) std::cout << x << \" \";\n");
-                main_body.push_str("    std::cout << std::endl;\n");
-            } else {
-                main_body.push_str("    std::cout << result << std::endl;\n");
-            }
+            main_body.push_str(&print_value("result", &sig.return_type));
         }
     }
 
     let mut includes = vec!["#include <iostream>"];
-    let needs_vector = sig.params.iter().any(|p| {
-        matches!(unwrap_mut_ref(&p.ty), RustType::Vec(_))
-    }) || matches!(&sig.return_type, RustType::Vec(_));
-    let needs_string = sig.params.iter().any(|p| {
-        matches!(unwrap_mut_ref(&p.ty), RustType::String)
-    }) || matches!(&sig.return_type, RustType::String);
+    let needs_vector = super::uses_type(sig, |ty| matches!(ty, RustType::Vec(_)));
+    let needs_string = super::uses_type(sig, |ty| matches!(ty, RustType::String));
+    let needs_optional = super::uses_type(sig, |ty| matches!(ty, RustType::Option(_)));
 
     if needs_vector {
         includes.push("#include <vector>");
@@ -148,69 +338,68 @@ pub(super) fn generate_cpp(
     if needs_string {
         includes.push("#include <string>");
     }
+    if needs_optional {
+        includes.push("#include <optional>");
+    }
+    if super::uses_map(sig) {
+        includes.push("#include <unordered_map>");
+    }
+    if super::uses_fixed_width_int(sig) {
+        includes.push("#include <cstdint>");
+    }
 
     let solution_hpp = format!(
         r#"#pragma once
 {}
-{}
 
 {} {}({});"#,
-        if needs_vector { "#include <vector>" } else { "" },
-        if needs_string { "#include <string>" } else { "" },
+        // Everything solution.cpp includes except <iostream>.
+        includes[1..].join("\n"),
         ret_type,
         sig.name,
         params_str.join(", ")
     );
 
-    let solution_cpp_lib = format!(
-        r#"{includes}
-#include "solution.hpp"
-
-{ret_type} {name}({params}) {{
-{default_return}}}"#,
-        includes = includes.join("\n"),
-        ret_type = ret_type,
-        name = sig.name,
-        params = params_str.join(", "),
-        default_return = default_return,
-    );
-
     let solution_cpp = format!(
         r#"{includes}
 
 {ret_type} {name}({params}) {{
 {default_return}}}
 
+// `make test` compiles this file with -DCODLE_TEST and links it against
+// test_solution.cpp, so this is the only copy of your solution.
+#ifndef CODLE_TEST
 int main() {{
 {main_body}    return 0;
-}}"#,
+}}
+#endif"#,
         includes = includes.join("\n"),
         ret_type = ret_type,
         name = sig.name,
         params = params_str.join(", "),
-        default_return = default_return,
+        default_return = options.body_or("    ", &default_return),
         main_body = main_body,
     );
 
     let tests_code = generate_cpp_tests(sig, &challenge.tests);
 
-    let metadata = ProjectMetadata::new(
+    let mut metadata = ProjectMetadata::new(
         challenge.name.clone(),
         Language::Cpp,
         difficulty,
         sig.name.clone(),
-        Some(chrono::Local::now().to_rfc3339()),
+        options.initialized_at(),
         challenge.difficulty,
     );
+    metadata.challenge_index = options.challenge_index;
     let metadata_content = metadata_json(&metadata);
 
-    let makefile = r#"CXX = g++
+    let mut makefile = r#"CXX = g++
 CXXFLAGS = -Wall -Wextra -std=c++17 -g
 TARGET = solution
 TEST_TARGET = test_runner
 SRC = solution.cpp
 TEST_SRC = test_solution.cpp
-LIB_SRC = solution_lib.cpp
 
 all: $(TARGET)
 
@@ -220,8 +409,8 @@ $(TARGET): $(SRC)
 test: $(TEST_TARGET)
 	./$(TEST_TARGET)
 
-$(TEST_TARGET): $(TEST_SRC) $(LIB_SRC)
-	$(CXX) $(CXXFLAGS) -o $(TEST_TARGET) $(LIB_SRC) $(TEST_SRC)
+$(TEST_TARGET): $(SRC) $(TEST_SRC) solution.hpp
+	$(CXX) $(CXXFLAGS) -DCODLE_TEST -o $(TEST_TARGET) $(SRC) $(TEST_SRC)
 
 run: $(TARGET)
 	./$(TARGET)
@@ -229,77 +418,189 @@ run: $(TARGET)
 clean:
 	rm -f $(TARGET) $(TEST_TARGET)
 
-.PHONY: all run clean test"#;
+.PHONY: all run clean test"#.to_string();
+    if options.with_bench {
+        makefile.push_str(BENCH_MAKEFILE_RULES);
+    }
 
     let setup_sh = format!(
         r#"#!/bin/bash
 set -e
 
+echo "[codle] step: checking required tools"
 {}
 
+echo "[codle] step: writing Makefile"
 cat > Makefile << 'MAKEFILE'
 {}
 MAKEFILE
 
+# Older scaffolds linked the tests against a copy of the solution; solution.cpp
+# is now the only source, so a leftover copy would just go stale.
+rm -f solution_lib.cpp
+
+echo "[codle] step: writing solution.cpp"
 cat > solution.cpp << 'SOLUTION'
 {}
 SOLUTION
 
+echo "[codle] step: writing solution.hpp"
 cat > solution.hpp << 'HEADER'
 {}
 HEADER
 
-cat > solution_lib.cpp << 'SOLUTION_LIB'
-{}
-SOLUTION_LIB
-
+echo "[codle] step: writing test_solution.cpp"
 cat > test_solution.cpp << 'TESTS'
 {}
 TESTS
 
+echo "[codle] step: writing .codle.json"
 cat > .codle.json << 'METADATA'
 {}
 METADATA
 
 echo "Run: make && ./solution"
 echo "Test: make test"
-"#,
-        require_commands(&["g++", "make"]),
+{}"#,
+        require_commands(Language::Cpp.required_commands()),
         makefile,
         escape_for_heredoc(&solution_cpp),
         escape_for_heredoc(&solution_hpp),
-        escape_for_heredoc(&solution_cpp_lib),
         escape_for_heredoc(&tests_code),
-        metadata_content
+        metadata_content,
+        bench_section(challenge, sig, options)
     );
 
     write_setup_script(output_dir, &setup_sh)
 }
 
+/// Appended to the Makefile by `--with-bench`. The bench is built with -O2 so
+/// it measures optimized code.
+const BENCH_MAKEFILE_RULES: &str = r#"
+
+BENCH_TARGET = bench_runner
+BENCH_SRC = bench_solution.cpp
+
+bench: $(BENCH_TARGET)
+	./$(BENCH_TARGET)
+
+$(BENCH_TARGET): $(SRC) $(BENCH_SRC) solution.hpp
+	$(CXX) $(CXXFLAGS) -O2 -DCODLE_TEST -o $(BENCH_TARGET) $(SRC) $(BENCH_SRC)
+
+clean: clean-bench
+
+clean-bench:
+	rm -f $(BENCH_TARGET)
+
+.PHONY: bench clean-bench"#;
+
+fn bench_section(challenge: &Challenge, sig: &FunctionSignature, options: &ScaffoldOptions) -> String {
+    if !options.with_bench {
+        return String::new();
+    }
+
+    format!(
+        r#"
+echo "[codle] step: writing bench_solution.cpp"
+cat > bench_solution.cpp << 'BENCH'
+{}
+BENCH
+
+echo "Bench: make bench"
+"#,
+        escape_for_heredoc(&generate_cpp_bench(challenge, sig))
+    )
+}
+
+/// Timed loop over the largest test input. Inputs are redeclared on every run
+/// so an in-place solution always starts from the original data.
+pub(super) fn generate_cpp_bench(challenge: &Challenge, sig: &FunctionSignature) -> String {
+    let inputs = largest_test_inputs(challenge);
+    let mut declarations = String::new();
+    for p in &sig.params {
+        let inner_ty = unwrap_ref(&p.ty);
+        let value = inputs.and_then(|i| i.get(&p.name)).cloned().unwrap_or(Value::Null);
+        declarations.push_str(&format!(
+            "        {} {} = {};\n",
+            super::translate_type(inner_ty, Language::Cpp),
+            p.name,
+            super::render_value(&value, inner_ty, Language::Cpp)
+        ));
+    }
+    let args: Vec<String> = sig.params.iter().map(|p| p.name.clone()).collect();
+
+    format!(
+        r#"#include <chrono>
+#include <iostream>
+#include <vector>
+#include <string>
+
+#include "solution.hpp"
+
+constexpr int RUNS = 1000;
+
+int main() {{
+    auto start = std::chrono::steady_clock::now();
+    for (int run = 0; run < RUNS; run++) {{
+{declarations}        {name}({args});
+    }}
+    std::chrono::duration<double, std::micro> elapsed = std::chrono::steady_clock::now() - start;
+    std::cout << "{name}: " << elapsed.count() / RUNS << " us per call over " << RUNS << " runs" << std::endl;
+    return 0;
+}}"#,
+        declarations = declarations,
+        name = sig.name,
+        args = args.join(", "),
+    )
+}
+
 pub(super) fn generate_cpp_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
     let mut test_code = String::new();
     test_code.push_str("#include <iostream>\n");
     test_code.push_str("#include <vector>\n");
-    test_code.push_str("#include <string>\n\n");
+    test_code.push_str("#include <string>\n");
+    test_code.push_str("#include <cmath>\n");
+    test_code.push_str("#include <limits>\n");
+    test_code.push_str("#include <optional>\n");
+    test_code.push_str("#include <sstream>\n");
+    test_code.push_str("#include <unordered_map>\n");
+    if tests.iter().any(|t| t.unordered) {
+        test_code.push_str("#include <algorithm>\n");
+    }
+    test_code.push('\n');
 
-    let params_str: Vec<String> = sig
-        .params
-        .iter()
-        .map(|p| format!("{} {}", super::translate_type(&p.ty, Language::Cpp), p.name))
-        .collect();
-    let ret_type = super::translate_type(&sig.return_type, Language::Cpp);
-    test_code.push_str(&format!(
-        "// Forward declaration - implemented in solution.cpp\n{} {}({});\n\n",
-        ret_type,
-        sig.name,
-        params_str.join(", ")
-    ));
+    test_code.push_str("// Declared in solution.hpp - implemented in solution.cpp\n");
+    test_code.push_str("#include \"solution.hpp\"\n\n");
+    test_code.push_str(SHOW_HELPER);
+    test_code.push('\n');
+
+    let mut float_vecs: Vec<&RustType> = Vec::new();
+    for ty in super::checked_types(sig) {
+        if super::is_float_vec(ty) && !float_vecs.contains(&ty) {
+            float_vecs.push(ty);
+        }
+    }
+    for ty in float_vecs {
+        test_code.push_str(&format!(
+            r#"static bool approx_equal(const {ty}& actual, const {ty}& expected) {{
+    if (actual.size() != expected.size()) return false;
+    for (size_t i = 0; i < actual.size(); i++) {{
+        if (!(actual[i] == expected[i] || std::fabs(actual[i] - expected[i]) < {eps})) return false;
+    }}
+    return true;
+}}
+
+"#,
+            ty = translate_type_cpp(ty),
+            eps = super::float_epsilon(ty)
+        ));
+    }
 
     test_code.push_str("int main() {\n");
     test_code.push_str("    int passed = 0, failed = 0;\n\n");
 
     for (i, test) in tests.iter().enumerate() {
-        let test_num = i + 1;
+        let test_num = super::test_case_label(test, i);
 
         if let Some(inputs) = test.input.as_object() {
             test_code.push_str(&format!("    // Test {}\n", test_num));
@@ -307,7 +608,7 @@ pub(super) fn generate_cpp_tests(sig: &FunctionSignature, tests: &[TestCase]) ->
 
             if is_void_with_mut_ref(sig) {
                 for p in &sig.params {
-                    let inner_ty = unwrap_mut_ref(&p.ty);
+                    let inner_ty = unwrap_ref(&p.ty);
                     if let Some(val) = inputs.get(&p.name) {
                         test_code.push_str(&format!(
                             "        {} {} = {};\n",
@@ -324,31 +625,38 @@ pub(super) fn generate_cpp_tests(sig: &FunctionSignature, tests: &[TestCase]) ->
                     call_args.join(", ")
                 ));
 
-                if let Some(p) = sig
-                    .params
+                // Each output's check may declare `expected`, so with several
+                // they get a block each.
+                let outputs = super::mut_ref_outputs(sig, test);
+                let reports: String = outputs
                     .iter()
-                    .find(|p| matches!(&p.ty, RustType::MutRef(_)))
-                {
-                    let inner = unwrap_mut_ref(&p.ty);
-                    let expected = super::render_value(&test.expected, inner, Language::Cpp);
-                    test_code.push_str(&format!(
-                        "        if ({} == {}) {{ std::cout << \"Test {}: PASS\" << std::endl; passed++; }}\n",
-                        p.name, expected, test_num
-                    ));
-                    test_code.push_str(&format!(
-                        "        else {{ std::cout << \"Test {}: FAIL\" << std::endl; failed++; }}\n",
-                        test_num
-                    ));
-                }
+                    .map(|(p, output)| {
+                        let label = if outputs.len() > 1 { format!("{} ", p.name) } else { String::new() };
+                        failure_report(&p.name, &label, output, unwrap_ref(&p.ty))
+                    })
+                    .collect();
+                let condition = if let [(p, output)] = outputs.as_slice() {
+                    test_condition(&mut test_code, &p.name, output, unwrap_ref(&p.ty))
+                } else {
+                    test_code.push_str("        bool ok = true;\n");
+                    for (p, output) in &outputs {
+                        let mut check = String::new();
+                        let condition = test_condition(&mut check, &p.name, output, unwrap_ref(&p.ty));
+                        check.push_str(&format!("        ok = ok && ({});\n", condition));
+                        test_code.push_str(&super::scoped(&check, "        ", "    ", "{"));
+                    }
+                    "ok".to_string()
+                };
+                test_code.push_str(&verdict(&condition, &test_num, &reports));
             } else {
                 let mut args = Vec::new();
                 for p in &sig.params {
                     if let Some(val) = inputs.get(&p.name) {
                         test_code.push_str(&format!(
                             "        {} {} = {};\n",
-                            super::translate_type(unwrap_mut_ref(&p.ty), Language::Cpp),
+                            super::translate_type(unwrap_ref(&p.ty), Language::Cpp),
                             p.name,
-                            super::render_value(val, unwrap_mut_ref(&p.ty), Language::Cpp)
+                            super::render_value(val, unwrap_ref(&p.ty), Language::Cpp)
                         ));
                         args.push(p.name.clone());
                     }
@@ -358,15 +666,9 @@ pub(super) fn generate_cpp_tests(sig: &FunctionSignature, tests: &[TestCase]) ->
                     sig.name,
                     args.join(", ")
                 ));
-                let expected = super::render_value(&test.expected, &sig.return_type, Language::Cpp);
-                test_code.push_str(&format!(
-                    "        if (result == {}) {{ std::cout << \"Test {}: PASS\" << std::endl; passed++; }}\n",
-                    expected, test_num
-                ));
-                test_code.push_str(&format!(
-                    "        else {{ std::cout << \"Test {}: FAIL\" << std::endl; failed++; }}\n",
-                    test_num
-                ));
+                let condition = test_condition(&mut test_code, "result", test, &sig.return_type);
+                let report = failure_report("result", "", test, &sig.return_type);
+                test_code.push_str(&verdict(&condition, &test_num, &report));
             }
 
             test_code.push_str("    }\n\n");
@@ -0,0 +1,411 @@
+use serde_json::Value;
+
+use crate::models::{
+    Challenge, Difficulty, FunctionSignature, Language, ProjectMetadata, RustType,
+    TestCase, metadata_json,
+};
+use super::{
+    write_setup_script, require_commands, escape_for_heredoc,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref,
+    SpecialFloat, ScaffoldOptions,
+};
+
+pub(super) fn translate_type_csharp(ty: &RustType) -> String {
+    match ty {
+        RustType::I32 | RustType::Usize => "int".to_string(),
+        RustType::I8 => "sbyte".to_string(),
+        RustType::I16 => "short".to_string(),
+        RustType::I64 => "long".to_string(),
+        RustType::U8 => "byte".to_string(),
+        RustType::U16 => "ushort".to_string(),
+        RustType::U32 => "uint".to_string(),
+        RustType::U64 => "ulong".to_string(),
+        RustType::F32 => "float".to_string(),
+        RustType::F64 => "double".to_string(),
+        RustType::Bool => "bool".to_string(),
+        RustType::String | RustType::Str => "string".to_string(),
+        RustType::Char => "char".to_string(),
+        RustType::Vec(inner) => format!("List<{}>", translate_type_csharp(inner)),
+        RustType::Option(inner) => format!("{}?", translate_type_csharp(inner)),
+        RustType::Map(key, value) => {
+            format!("Dictionary<{}, {}>", translate_type_csharp(key), translate_type_csharp(value))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_csharp(inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
+        RustType::Void => "void".to_string(),
+    }
+}
+
+pub(super) fn render_value_csharp(value: &Value, ty: &RustType) -> String {
+    match ty {
+        RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
+        // Cast so `Assert.Equal` infers the narrow type, as it must to
+        // compare against a `short?`.
+        RustType::I8 | RustType::I16 | RustType::U8 | RustType::U16 => {
+            format!("({}){}", translate_type_csharp(ty), super::integer_literal(value))
+        }
+        RustType::I64 => format!("{}L", super::integer_literal(value)),
+        RustType::U32 => format!("{}U", super::integer_literal(value)),
+        RustType::U64 => format!("{}UL", super::integer_literal(value)),
+        RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "double.PositiveInfinity".to_string(),
+            Some(SpecialFloat::NegInfinity) => "double.NegativeInfinity".to_string(),
+            Some(SpecialFloat::NaN) => "double.NaN".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::F32 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "float.PositiveInfinity".to_string(),
+            Some(SpecialFloat::NegInfinity) => "float.NegativeInfinity".to_string(),
+            Some(SpecialFloat::NaN) => "float.NaN".to_string(),
+            None => format!("{}f", super::format_float(value.as_f64().unwrap_or(0.0))),
+        },
+        RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
+        RustType::String | RustType::Str => super::string_literal(value, Language::Cs),
+        RustType::Char => super::char_literal(value, Language::Cs),
+        RustType::Vec(inner) => {
+            let items: Vec<String> = value
+                .as_array()
+                .map(|arr| arr.iter().map(|v| render_value_csharp(v, inner)).collect())
+                .unwrap_or_default();
+            if items.is_empty() {
+                format!("new {}()", translate_type_csharp(ty))
+            } else {
+                format!("new {} {{ {} }}", translate_type_csharp(ty), items.join(", "))
+            }
+        }
+        RustType::Option(_) if value.is_null() => "null".to_string(),
+        RustType::Option(inner) => render_value_csharp(value, inner),
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("[{}] = {}", render_value_csharp(&k, key), render_value_csharp(v, inner)))
+                .collect();
+            if entries.is_empty() {
+                format!("new {}()", translate_type_csharp(ty))
+            } else {
+                format!("new {} {{ {} }}", translate_type_csharp(ty), entries.join(", "))
+            }
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_csharp(value, inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
+        RustType::Void => String::new(),
+    }
+}
+
+/// The method's parameter list. A `&mut` parameter becomes a `ref`
+/// parameter, so the method can reassign it as well as mutate it.
+pub(super) fn param_list(sig: &FunctionSignature) -> String {
+    sig.params
+        .iter()
+        .map(|p| {
+            let modifier = if matches!(&p.ty, RustType::MutRef(_)) { "ref " } else { "" };
+            format!("{}{} {}", modifier, translate_type_csharp(unwrap_ref(&p.ty)), p.name)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Declarations of the parameters set to `inputs`, then the call, with
+/// `ref` passed for each `&mut` parameter.
+fn call_statements(
+    sig: &FunctionSignature,
+    inputs: &serde_json::Map<String, Value>,
+    indent: &str,
+) -> (String, String) {
+    let mut decls = String::new();
+    let mut args = Vec::new();
+    for p in &sig.params {
+        if let Some(val) = inputs.get(&p.name) {
+            let ty = unwrap_ref(&p.ty);
+            decls.push_str(&format!(
+                "{}{} {} = {};\n",
+                indent,
+                translate_type_csharp(ty),
+                p.name,
+                render_value_csharp(val, ty)
+            ));
+            if matches!(&p.ty, RustType::MutRef(_)) {
+                args.push(format!("ref {}", p.name));
+            } else {
+                args.push(p.name.clone());
+            }
+        }
+    }
+    (decls, format!("Program.{}({})", sig.name, args.join(", ")))
+}
+
+pub(super) fn generate_csharp(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
+) -> Result<(), String> {
+    let mut main_body = String::new();
+    if let Some(inputs) = get_first_test_inputs(challenge) {
+        let (decls, call) = call_statements(sig, inputs, "        ");
+        main_body.push_str(&decls);
+        if is_void_with_mut_ref(sig) {
+            main_body.push_str(&format!("        {};\n", call));
+            if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
+                main_body.push_str(&format!("        Console.WriteLine(JsonSerializer.Serialize({}));\n", p.name));
+            }
+        } else if sig.return_type == RustType::Void {
+            main_body.push_str(&format!("        {};\n", call));
+        } else {
+            main_body.push_str(&format!("        var result = {};\n", call));
+            main_body.push_str("        Console.WriteLine(JsonSerializer.Serialize(result));\n");
+        }
+    }
+
+    // Printed as JSON, since `List<T>` and `Dictionary` only `ToString` to
+    // their type names.
+    let program_cs = format!(
+        r#"using System.Text.Json;
+
+public static class Program
+{{
+    public static {} {}({})
+    {{
+{}    }}
+
+    public static void Main()
+    {{
+{}    }}
+}}"#,
+        translate_type_csharp(&sig.return_type),
+        sig.name,
+        param_list(sig),
+        options.body_or("        ", "        throw new NotImplementedException();\n"),
+        main_body,
+    );
+
+    let tests_code = generate_csharp_tests(sig, &challenge.tests);
+
+    let mut metadata = ProjectMetadata::new(
+        challenge.name.clone(),
+        Language::Cs,
+        difficulty,
+        sig.name.clone(),
+        options.initialized_at(),
+        challenge.difficulty,
+    );
+    metadata.challenge_index = options.challenge_index;
+    let metadata_content = metadata_json(&metadata);
+
+    // The project files target whichever SDK is installed; their heredocs
+    // are unquoted so `$TFM` expands.
+    let setup_sh = format!(
+        r#"#!/bin/bash
+set -e
+
+echo "[codle] step: checking required tools"
+{}
+TFM="net$(dotnet --version | cut -d. -f1).0"
+mkdir -p Solution Tests
+
+echo "[codle] step: writing Solution/Solution.csproj"
+cat > Solution/Solution.csproj << CSPROJ
+<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>$TFM</TargetFramework>
+    <ImplicitUsings>enable</ImplicitUsings>
+    <Nullable>enable</Nullable>
+  </PropertyGroup>
+</Project>
+CSPROJ
+
+echo "[codle] step: writing Tests/Tests.csproj"
+cat > Tests/Tests.csproj << CSPROJ
+<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>$TFM</TargetFramework>
+    <ImplicitUsings>enable</ImplicitUsings>
+    <Nullable>enable</Nullable>
+    <IsPackable>false</IsPackable>
+  </PropertyGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.NET.Test.Sdk" Version="17.11.1" />
+    <PackageReference Include="xunit" Version="2.9.2" />
+    <PackageReference Include="xunit.runner.visualstudio" Version="2.8.2" />
+  </ItemGroup>
+  <ItemGroup>
+    <ProjectReference Include="../Solution/Solution.csproj" />
+  </ItemGroup>
+</Project>
+CSPROJ
+
+echo "[codle] step: creating the solution file"
+dotnet new sln --name Codle --force
+dotnet sln add Solution/Solution.csproj Tests/Tests.csproj
+
+echo "[codle] step: writing Solution/Program.cs"
+cat > Solution/Program.cs << 'SOLUTION'
+{}
+SOLUTION
+
+echo "[codle] step: writing Tests/SolutionTests.cs"
+cat > Tests/SolutionTests.cs << 'TESTS'
+{}
+TESTS
+
+echo "[codle] step: restoring packages"
+dotnet restore
+
+echo "[codle] step: writing .codle.json"
+cat > .codle.json << 'METADATA'
+{}
+METADATA
+
+echo "Run: dotnet run --project Solution"
+echo "Test: dotnet test"
+"#,
+        require_commands(Language::Cs.required_commands()),
+        escape_for_heredoc(&program_cs),
+        escape_for_heredoc(&tests_code),
+        metadata_content
+    );
+
+    write_setup_script(output_dir, &setup_sh)
+}
+
+fn unwrap_option(ty: &RustType) -> &RustType {
+    match ty {
+        RustType::Option(inner) => inner,
+        other => other,
+    }
+}
+
+/// `xs` sorted into a canonical order: scalars by value, so floats still
+/// line up for the tolerance check, and lists or maps by their JSON.
+fn sorted(xs: &str, elem: &RustType) -> String {
+    if matches!(elem, RustType::Vec(_) | RustType::Map(..) | RustType::Option(_)) {
+        format!("{}.OrderBy(x => JsonSerializer.Serialize(x), StringComparer.Ordinal).ToList()", xs)
+    } else {
+        format!("{}.OrderBy(x => x).ToList()", xs)
+    }
+}
+
+/// xUnit assertions that `actual` matches the expected value: `Assert.Equal`,
+/// which compares lists and dictionaries element by element, except for
+/// floats, which are compared within `float_epsilon`. An unordered list is
+/// sorted, as is `expected`, before comparing.
+fn result_assertion(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    let value = &test.expected;
+    let mut expected = render_value_csharp(value, ty);
+    let mut code = String::new();
+    let mut actual = actual.to_string();
+    if let RustType::Vec(elem) = ty {
+        if super::compares_unordered(test, ty) {
+            code.push_str(&format!("        var actual = {};\n", sorted(&actual, elem)));
+            actual = "actual".to_string();
+            expected = sorted(&format!("({})", expected), elem);
+        }
+    }
+
+    let inner = unwrap_option(ty);
+    let assertion = if super::expects_nan(inner, value) {
+        let value = if inner == ty { actual.clone() } else { format!("{}!.Value", actual) };
+        format!("        Assert.True({}.IsNaN({v}), $\"expected: NaN but was: {{{v}}}\");\n", translate_type_csharp(inner), v = value)
+    } else if value.is_null() {
+        format!("        Assert.Null({});\n", actual)
+    } else if super::compares_approximately(inner, value) {
+        let value = if inner == ty { actual.clone() } else { format!("{}!.Value", actual) };
+        format!(
+            "        Assert.True(Math.Abs({v} - {e}) < {eps}, $\"expected: {{{e}}} but was: {{{v}}}\");\n",
+            v = value,
+            e = expected,
+            eps = super::float_epsilon(ty)
+        )
+    } else if let (RustType::Bool, Some(b)) = (ty, value.as_bool()) {
+        format!("        Assert.{}({});\n", if b { "True" } else { "False" }, actual)
+    } else if super::is_float_vec(ty) {
+        format!(
+            r#"        var expected = {e};
+        Assert.Equal(expected.Count, {a}.Count);
+        for (int i = 0; i < expected.Count; i++)
+        {{
+            Assert.True(Math.Abs({a}[i] - expected[i]) < {eps}, $"expected: [{{string.Join(", ", expected)}}] but was: [{{string.Join(", ", {a})}}]");
+        }}
+"#,
+            e = expected,
+            a = actual,
+            eps = super::float_epsilon(ty)
+        )
+    } else {
+        format!("        Assert.Equal({}, {});\n", expected, actual)
+    };
+    code + &assertion
+}
+
+pub(super) fn generate_csharp_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+    let mut test_fns = Vec::new();
+
+    for (i, test) in tests.iter().enumerate() {
+        let method_suffix = match test.identifier() {
+            Some(name) => format!("_{}", name),
+            None => (i + 1).to_string(),
+        };
+        let mut body = String::new();
+
+        if let Some(inputs) = test.input.as_object() {
+            let (decls, call) = call_statements(sig, inputs, "        ");
+            body.push_str(&decls);
+            if is_void_with_mut_ref(sig) {
+                body.push_str(&format!("        {};\n", call));
+                let outputs = super::mut_ref_outputs(sig, test);
+                for (p, output) in &outputs {
+                    let assertion = result_assertion(&p.name, output, unwrap_ref(&p.ty));
+                    if outputs.len() > 1 {
+                        body.push_str(&super::scoped(&assertion, "        ", "    ", "{"));
+                    } else {
+                        body.push_str(&assertion);
+                    }
+                }
+            } else if sig.return_type == RustType::Void {
+                body.push_str(&format!("        {};\n", call));
+            } else {
+                body.push_str(&format!("        var result = {};\n", call));
+                body.push_str(&result_assertion("result", test, &sig.return_type));
+            }
+        }
+
+        test_fns.push(format!("    [Fact]\n    public void Test{}()\n    {{\n{}    }}", method_suffix, body));
+    }
+
+    format!(
+        "using System.Text.Json;\nusing Xunit;\n\npublic class SolutionTests\n{{\n{}\n}}",
+        test_fns.join("\n\n")
+    )
+}
+
+/// Reads the `Failed!  - Failed:     1, Passed:     2, ...` summary that
+/// `dotnet test` prints for each test project.
+pub(super) fn parse_dotnet_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for line in combined.lines().filter(|line| line.contains(" - Failed:")) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        for (i, part) in parts.iter().enumerate() {
+            let count = || parts.get(i + 1).and_then(|n| n.trim_end_matches(',').parse::<usize>().ok());
+            match *part {
+                "Passed:" => passed += count().unwrap_or(0),
+                "Failed:" => failed += count().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(super::TestSummary {
+        passed,
+        failed,
+        total: passed + failed,
+        output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
+    })
+}
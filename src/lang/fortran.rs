@@ -0,0 +1,570 @@
+use serde_json::{Map, Value};
+
+use crate::models::{
+    Challenge, Difficulty, FunctionSignature, Language, ProjectMetadata, RustType,
+    TestCase, metadata_json,
+};
+use super::{
+    write_setup_script, require_commands, escape_for_heredoc,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref, SpecialFloat, ScaffoldOptions,
+};
+
+pub(super) fn translate_type_fortran(ty: &RustType) -> String {
+    match ty {
+        // Fortran has no unsigned kinds; an unsigned type takes the next
+        // wider signed one.
+        RustType::I8 => "integer(1)".to_string(),
+        RustType::I16 | RustType::U8 => "integer(2)".to_string(),
+        RustType::I32 | RustType::U16 | RustType::Usize => "integer".to_string(),
+        RustType::I64 | RustType::U32 | RustType::U64 => "integer(8)".to_string(),
+        RustType::F32 => "real".to_string(),
+        RustType::F64 => "real(8)".to_string(),
+        RustType::Bool => "logical".to_string(),
+        RustType::String | RustType::Str => "character(len=*)".to_string(),
+        RustType::Char => "character(len=1)".to_string(),
+        // Arrays take their element type; the bounds go on the variable name.
+        RustType::Vec(inner) => translate_type_fortran(inner),
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_fortran(inner),
+        // Rejected by `Language::supports`.
+        RustType::Map(..) | RustType::Tuple(_) | RustType::Void => "".to_string(),
+    }
+}
+
+pub(super) fn render_value_fortran(value: &Value, ty: &RustType) -> String {
+    match ty {
+        RustType::I8 => format!("{}_1", super::integer_literal(value)),
+        RustType::I16 | RustType::U8 => format!("{}_2", super::integer_literal(value)),
+        RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
+        RustType::U16 => super::integer_literal(value),
+        // `-9223372036854775808_8` negates a literal too big for its kind.
+        RustType::I64 if value.as_i64() == Some(i64::MIN) => "(-9223372036854775807_8 - 1_8)".to_string(),
+        RustType::I64 | RustType::U32 | RustType::U64 => format!("{}_8", super::integer_literal(value)),
+        RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "ieee_value(0.0d0, ieee_positive_inf)".to_string(),
+            Some(SpecialFloat::NegInfinity) => "ieee_value(0.0d0, ieee_negative_inf)".to_string(),
+            Some(SpecialFloat::NaN) => "ieee_value(0.0d0, ieee_quiet_nan)".to_string(),
+            None => double_literal(&super::format_float(value.as_f64().unwrap_or(0.0))),
+        },
+        // An unsuffixed literal is a default (single precision) real.
+        RustType::F32 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "ieee_value(0.0, ieee_positive_inf)".to_string(),
+            Some(SpecialFloat::NegInfinity) => "ieee_value(0.0, ieee_negative_inf)".to_string(),
+            Some(SpecialFloat::NaN) => "ieee_value(0.0, ieee_quiet_nan)".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::Bool => {
+            if value.as_bool().unwrap_or(false) {
+                ".true.".to_string()
+            } else {
+                ".false.".to_string()
+            }
+        }
+        RustType::String | RustType::Str => string_literal(value.as_str().unwrap_or("")),
+        RustType::Char => {
+            let c = value.as_str().and_then(|s| s.chars().next()).unwrap_or('?');
+            string_literal(&c.to_string())
+        }
+        RustType::Vec(inner) => {
+            let items: Vec<String> = value
+                .as_array()
+                .map(|arr| arr.iter().map(|v| render_value_fortran(v, inner)).collect())
+                .unwrap_or_default();
+            if items.is_empty() {
+                format!("[{} ::]", translate_type_fortran(inner))
+            } else {
+                format!("[{}]", items.join(", "))
+            }
+        }
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => render_value_fortran(value, inner),
+        RustType::Map(..) | RustType::Tuple(_) | RustType::Void => "".to_string(),
+    }
+}
+
+/// Fortran strings have no escapes: a quote is doubled, and control characters
+/// are concatenated in with `achar`.
+fn string_literal(s: &str) -> String {
+    let mut parts = Vec::new();
+    let mut quoted = String::new();
+    for c in s.chars() {
+        if c.is_ascii_control() {
+            if !quoted.is_empty() {
+                parts.push(format!("'{}'", std::mem::take(&mut quoted)));
+            }
+            parts.push(format!("achar({})", c as u32));
+        } else if c == '\'' {
+            quoted.push_str("''");
+        } else {
+            quoted.push(c);
+        }
+    }
+    if !quoted.is_empty() || parts.is_empty() {
+        parts.push(format!("'{}'", quoted));
+    }
+    parts.join(" // ")
+}
+
+/// A plain decimal literal is single precision in Fortran, so every real gets
+/// a `d` exponent to make it `real(8)`.
+fn double_literal(formatted: &str) -> String {
+    if formatted.contains(['e', 'E']) {
+        formatted.replace(['e', 'E'], "d")
+    } else {
+        format!("{}d0", formatted)
+    }
+}
+
+/// Declaration of a local holding a value of type `ty` returned by the solution.
+fn result_declaration(ty: &RustType, name: &str) -> String {
+    match ty {
+        RustType::Vec(inner) => {
+            format!("{}, allocatable :: {}(:)", translate_type_fortran(inner), name)
+        }
+        RustType::String => format!("character(len=:), allocatable :: {}", name),
+        other => format!("{} :: {}", translate_type_fortran(other), name),
+    }
+}
+
+pub(super) fn dummy_arguments(sig: &FunctionSignature) -> Vec<String> {
+    let mut args = Vec::new();
+    for p in &sig.params {
+        args.push(p.name.clone());
+        if matches!(unwrap_ref(&p.ty), RustType::Vec(_)) {
+            args.push(format!("{}_len", p.name));
+        }
+    }
+    args
+}
+
+/// The `solution` module holding the function stub. Array parameters are
+/// explicit-shape, sized by a trailing `<name>_len` argument like the C backend.
+pub(super) fn generate_fortran_module(sig: &FunctionSignature, options: &ScaffoldOptions) -> String {
+    let is_subroutine = sig.return_type == RustType::Void;
+    let kind = if is_subroutine { "subroutine" } else { "function" };
+
+    let mut decls = String::new();
+    for p in &sig.params {
+        let intent = if matches!(&p.ty, RustType::MutRef(_)) { "inout" } else { "in" };
+        match unwrap_ref(&p.ty) {
+            RustType::Vec(elem) => {
+                decls.push_str(&format!("    integer, intent(in) :: {}_len\n", p.name));
+                decls.push_str(&format!(
+                    "    {}, intent({}) :: {}({}_len)\n",
+                    translate_type_fortran(elem),
+                    intent,
+                    p.name,
+                    p.name
+                ));
+            }
+            other => decls.push_str(&format!(
+                "    {}, intent({}) :: {}\n",
+                translate_type_fortran(other),
+                intent,
+                p.name
+            )),
+        }
+    }
+
+    let mut header = format!("  {} {}({})", kind, sig.name, dummy_arguments(sig).join(", "));
+    let mut stub = String::new();
+    if !is_subroutine {
+        header.push_str(" result(res)");
+        decls.push_str(&format!("    {}\n", result_declaration(&sig.return_type, "res")));
+        let default = match &sig.return_type {
+            RustType::Vec(_) => "allocate(res(0))",
+            RustType::F32 => "res = 0.0",
+            RustType::F64 => "res = 0.0d0",
+            RustType::Bool => "res = .false.",
+            RustType::String => "res = ''",
+            RustType::Char => "res = ' '",
+            _ => "res = 0",
+        };
+        stub = format!("    {}\n", default);
+    }
+    let body = options.body_or("    ", &stub);
+    let body = if body.is_empty() { body } else { format!("\n{}", body) };
+
+    format!(
+        r#"! Fortran arrays are 1-based: the first element of `a` is a(1) and the last
+! is a(a_len). Index values in the expected outputs are still 0-based, as in
+! the challenge description. Multi-dimensional arrays are column-major (the
+! first subscript varies fastest), the opposite of C and Rust.
+module solution
+  implicit none
+contains
+
+{header}
+{decls}{body}  end {kind} {name}
+
+end module solution
+"#,
+        header = header,
+        decls = decls,
+        body = body,
+        kind = kind,
+        name = sig.name,
+    )
+}
+
+/// Local declarations, assignments and call arguments for one set of inputs,
+/// each line prefixed with `indent`.
+fn input_setup(
+    sig: &FunctionSignature,
+    inputs: &Map<String, Value>,
+    indent: &str,
+) -> (String, String, Vec<String>) {
+    let mut decls = String::new();
+    let mut assigns = String::new();
+    let mut args = Vec::new();
+
+    for p in &sig.params {
+        let ty = unwrap_ref(&p.ty);
+        let Some(val) = inputs.get(&p.name) else {
+            continue;
+        };
+        match ty {
+            RustType::Vec(elem) => {
+                let len = val.as_array().map_or(0, |a| a.len());
+                decls.push_str(&format!(
+                    "{}{} :: {}({})\n",
+                    indent,
+                    translate_type_fortran(elem),
+                    p.name,
+                    len
+                ));
+                if len > 0 {
+                    assigns.push_str(&format!(
+                        "{}{} = {}\n",
+                        indent,
+                        p.name,
+                        render_value_fortran(val, ty)
+                    ));
+                }
+                args.push(p.name.clone());
+                args.push(len.to_string());
+            }
+            RustType::String => {
+                let len = val.as_str().map_or(0, |s| s.chars().count());
+                decls.push_str(&format!("{}character(len={}) :: {}\n", indent, len, p.name));
+                assigns.push_str(&format!("{}{} = {}\n", indent, p.name, render_value_fortran(val, ty)));
+                args.push(p.name.clone());
+            }
+            other => {
+                decls.push_str(&format!("{}{} :: {}\n", indent, translate_type_fortran(other), p.name));
+                assigns.push_str(&format!("{}{} = {}\n", indent, p.name, render_value_fortran(val, ty)));
+                args.push(p.name.clone());
+            }
+        }
+    }
+
+    (decls, assigns, args)
+}
+
+/// Statements setting `ok` to whether `var` matches `test`'s expected value.
+/// An unordered array must hold each distinct expected element as many times.
+fn comparison(var: &str, ty: &RustType, test: &TestCase) -> String {
+    let expected = &test.expected;
+    match ty {
+        RustType::Vec(elem) => {
+            let len = expected.as_array().map_or(0, |a| a.len());
+            let mut code = format!("    ok = size({}) == {}\n", var, len);
+            if test.unordered {
+                for (value, count) in super::element_counts(expected) {
+                    let value = render_value_fortran(value, elem);
+                    let matches = match elem.as_ref() {
+                        RustType::F32 | RustType::F64 => format!(
+                            "{0} == {1} .or. abs({0} - {1}) < {2}",
+                            var,
+                            value,
+                            epsilon_fortran(elem)
+                        ),
+                        RustType::Bool => format!("{} .eqv. {}", var, value),
+                        _ => format!("{} == {}", var, value),
+                    };
+                    code.push_str(&format!("    if (ok) ok = count({}) == {}\n", matches, count));
+                }
+            } else if len > 0 && super::is_float(elem) {
+                let values = render_value_fortran(expected, ty);
+                code.push_str(&format!(
+                    "    if (ok) ok = all({0} == {1} .or. abs({0} - {1}) < {2})\n",
+                    var,
+                    values,
+                    epsilon_fortran(elem)
+                ));
+            } else if len > 0 {
+                let op = if **elem == RustType::Bool { ".eqv." } else { "==" };
+                code.push_str(&format!(
+                    "    if (ok) ok = all({} {} {})\n",
+                    var,
+                    op,
+                    render_value_fortran(expected, ty)
+                ));
+            }
+            code
+        }
+        _ if super::expects_nan(ty, expected) => format!("    ok = ieee_is_nan({})\n", var),
+        _ if super::compares_approximately(ty, expected) => format!(
+            "    ok = {0} == {1} .or. abs({0} - {1}) < {2}\n",
+            var,
+            render_value_fortran(expected, ty),
+            epsilon_fortran(ty)
+        ),
+        RustType::Bool => format!("    ok = {} .eqv. {}\n", var, render_value_fortran(expected, ty)),
+        _ => format!("    ok = {} == {}\n", var, render_value_fortran(expected, ty)),
+    }
+}
+
+/// `float_epsilon` as a real of `ty`'s kind: double precision for `f64`.
+fn epsilon_fortran(ty: &RustType) -> String {
+    match ty {
+        RustType::F32 => super::F32_EPSILON.to_string(),
+        _ => super::FLOAT_EPSILON.replace('e', "d"),
+    }
+}
+
+pub(super) fn generate_fortran(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
+) -> Result<(), String> {
+    let module_code = generate_fortran_module(sig, options);
+
+    let mut decls = String::new();
+    let mut body = String::new();
+    if let Some(inputs) = get_first_test_inputs(challenge) {
+        let (input_decls, assigns, args) = input_setup(sig, inputs, "  ");
+        decls.push_str(&input_decls);
+        body.push_str(&assigns);
+        if sig.return_type == RustType::Void {
+            body.push_str(&format!("  call {}({})\n", sig.name, args.join(", ")));
+            if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
+                body.push_str(&format!("  print *, {}\n", p.name));
+            }
+        } else {
+            decls.push_str(&format!("  {}\n", result_declaration(&sig.return_type, "res")));
+            body.push_str(&format!("  res = {}({})\n", sig.name, args.join(", ")));
+            body.push_str("  print *, res\n");
+        }
+    }
+
+    let main_code = format!(
+        r#"program main
+  use, intrinsic :: ieee_arithmetic
+  use solution
+  implicit none
+{}
+{}end program main
+"#,
+        decls,
+        body,
+    );
+
+    let tests_code = generate_fortran_tests(sig, &challenge.tests);
+
+    let mut metadata = ProjectMetadata::new(
+        challenge.name.clone(),
+        Language::Fortran,
+        difficulty,
+        sig.name.clone(),
+        options.initialized_at(),
+        challenge.difficulty,
+    );
+    metadata.challenge_index = options.challenge_index;
+    let metadata_content = metadata_json(&metadata);
+
+    let makefile = r#"FC = gfortran
+FFLAGS = -Wall -g -ffree-line-length-none
+TARGET = solution
+TEST_TARGET = test_runner
+
+all: $(TARGET)
+
+solution.o: solution.f90
+	$(FC) $(FFLAGS) -c solution.f90
+
+$(TARGET): solution.o main.f90
+	$(FC) $(FFLAGS) -o $(TARGET) solution.o main.f90
+
+test: $(TEST_TARGET)
+	./$(TEST_TARGET)
+
+$(TEST_TARGET): solution.o test_solution.f90
+	$(FC) $(FFLAGS) -o $(TEST_TARGET) solution.o test_solution.f90
+
+run: $(TARGET)
+	./$(TARGET)
+
+clean:
+	rm -f $(TARGET) $(TEST_TARGET) *.o *.mod
+
+.PHONY: all run clean test"#;
+
+    let setup_sh = format!(
+        r#"#!/bin/bash
+set -e
+
+echo "[codle] step: checking required tools"
+{}
+
+echo "[codle] step: writing Makefile"
+cat > Makefile << 'MAKEFILE'
+{}
+MAKEFILE
+
+echo "[codle] step: writing solution.f90"
+cat > solution.f90 << 'SOLUTION'
+{}
+SOLUTION
+
+echo "[codle] step: writing main.f90"
+cat > main.f90 << 'MAIN'
+{}
+MAIN
+
+echo "[codle] step: writing test_solution.f90"
+cat > test_solution.f90 << 'TESTS'
+{}
+TESTS
+
+echo "[codle] step: writing .codle.json"
+cat > .codle.json << 'METADATA'
+{}
+METADATA
+
+echo "Run: make && ./solution"
+echo "Test: make test"
+"#,
+        require_commands(Language::Fortran.required_commands()),
+        makefile,
+        escape_for_heredoc(&module_code),
+        escape_for_heredoc(&main_code),
+        escape_for_heredoc(&tests_code),
+        metadata_content
+    );
+
+    write_setup_script(output_dir, &setup_sh)
+}
+
+/// A test program printing the same `Test N: PASS` / `N/M tests passed` lines
+/// as the C harness, so `parse_c_output` reads its results.
+pub(super) fn generate_fortran_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+    let mut calls = String::new();
+    let mut subroutines = String::new();
+
+    for (i, test) in tests.iter().enumerate() {
+        let Some(inputs) = test.input.as_object() else {
+            continue;
+        };
+        let name = format!("test_{}", super::test_fn_suffix(test, i));
+        let label = super::test_case_label(test, i);
+        let (mut decls, mut body, args) = input_setup(sig, inputs, "    ");
+
+        if sig.return_type == RustType::Void {
+            body.push_str(&format!("    call {}({})\n", sig.name, args.join(", ")));
+            if is_void_with_mut_ref(sig) {
+                decls.push_str("    logical :: ok\n");
+                match super::mut_ref_outputs(sig, test).as_slice() {
+                    [(p, output)] => body.push_str(&comparison(&p.name, unwrap_ref(&p.ty), output)),
+                    outputs => {
+                        // `comparison` sets `ok`, so each result is kept in `all_ok`.
+                        decls.push_str("    logical :: all_ok\n");
+                        body.push_str("    all_ok = .true.\n");
+                        for (p, output) in outputs {
+                            body.push_str(&comparison(&p.name, unwrap_ref(&p.ty), output));
+                            body.push_str("    all_ok = all_ok .and. ok\n");
+                        }
+                        body.push_str("    ok = all_ok\n");
+                    }
+                }
+                body.push_str(&format!("    call check('{}', ok)\n", label));
+            }
+        } else {
+            decls.push_str(&format!("    {}\n", result_declaration(&sig.return_type, "actual")));
+            decls.push_str("    logical :: ok\n");
+            body.push_str(&format!("    actual = {}({})\n", sig.name, args.join(", ")));
+            body.push_str(&comparison("actual", &sig.return_type, test));
+            body.push_str(&format!("    call check('{}', ok)\n", label));
+        }
+
+        calls.push_str(&format!("  call {}()\n", name));
+        subroutines.push_str(&format!(
+            "  subroutine {name}()\n{decls}\n{body}  end subroutine {name}\n\n",
+            name = name,
+            decls = decls,
+            body = body
+        ));
+    }
+
+    format!(
+        r#"program test_solution
+  use, intrinsic :: ieee_arithmetic
+  use solution
+  implicit none
+  integer :: passed = 0, failed = 0
+
+{calls}
+  print '(/, i0, "/", i0, " tests passed")', passed, passed + failed
+  if (failed > 0) stop 1
+
+contains
+
+  subroutine check(label, ok)
+    character(len=*), intent(in) :: label
+    logical, intent(in) :: ok
+
+    if (ok) then
+      print '(a)', 'Test ' // label // ': PASS'
+      passed = passed + 1
+    else
+      print '(a)', 'Test ' // label // ': FAIL'
+      failed = failed + 1
+    end if
+  end subroutine check
+
+{subroutines}end program test_solution
+"#,
+        calls = calls,
+        subroutines = subroutines
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::parse_signature;
+
+    #[test]
+    fn test_module_snapshot() {
+        let sig = parse_signature("fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>").unwrap();
+        let expected = r#"! Fortran arrays are 1-based: the first element of `a` is a(1) and the last
+! is a(a_len). Index values in the expected outputs are still 0-based, as in
+! the challenge description. Multi-dimensional arrays are column-major (the
+! first subscript varies fastest), the opposite of C and Rust.
+module solution
+  implicit none
+contains
+
+  function two_sum(nums, nums_len, target) result(res)
+    integer, intent(in) :: nums_len
+    integer, intent(in) :: nums(nums_len)
+    integer, intent(in) :: target
+    integer, allocatable :: res(:)
+
+    allocate(res(0))
+  end function two_sum
+
+end module solution
+"#;
+        assert_eq!(generate_fortran_module(&sig, &ScaffoldOptions::default()), expected);
+    }
+
+    #[test]
+    fn test_mut_ref_becomes_inout_subroutine() {
+        let sig = parse_signature("fn reverse(nums: &mut Vec<f64>)").unwrap();
+        let module = generate_fortran_module(&sig, &ScaffoldOptions::default());
+        assert!(module.contains("  subroutine reverse(nums, nums_len)\n"));
+        assert!(module.contains("    real(8), intent(inout) :: nums(nums_len)\n"));
+        assert!(module.contains("  end subroutine reverse\n"));
+    }
+}
@@ -0,0 +1,369 @@
+use serde_json::Value;
+
+use crate::models::{
+    Challenge, Difficulty, FunctionSignature, Language, ProjectMetadata, RustType,
+    TestCase, metadata_json,
+};
+use super::{
+    write_setup_script, require_commands, escape_for_heredoc,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref,
+    SpecialFloat, ScaffoldOptions,
+};
+
+pub(super) fn translate_type_go(ty: &RustType) -> String {
+    match ty {
+        RustType::I32 | RustType::Usize => "int".to_string(),
+        RustType::I8 => "int8".to_string(),
+        RustType::I16 => "int16".to_string(),
+        RustType::I64 => "int64".to_string(),
+        RustType::U8 => "uint8".to_string(),
+        RustType::U16 => "uint16".to_string(),
+        RustType::U32 => "uint32".to_string(),
+        RustType::U64 => "uint64".to_string(),
+        RustType::F32 => "float32".to_string(),
+        RustType::F64 => "float64".to_string(),
+        RustType::Bool => "bool".to_string(),
+        RustType::String | RustType::Str => "string".to_string(),
+        RustType::Char => "rune".to_string(),
+        RustType::Vec(inner) => format!("[]{}", translate_type_go(inner)),
+        RustType::Map(key, value) => {
+            format!("map[{}]{}", translate_type_go(key), translate_type_go(value))
+        }
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => {
+            translate_type_go(inner)
+        }
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
+        RustType::Void => String::new(),
+    }
+}
+
+/// Integer literals are converted to their fixed-width type, and `f32` values to
+/// `float32`, so that `x := value` declares a variable of the parameter's type.
+pub(super) fn render_value_go(value: &Value, ty: &RustType) -> String {
+    match ty {
+        RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
+        RustType::I8 | RustType::I16 | RustType::I64 | RustType::U8 | RustType::U16 | RustType::U32 | RustType::U64 => {
+            format!("{}({})", translate_type_go(ty), super::integer_literal(value))
+        }
+        RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "math.Inf(1)".to_string(),
+            Some(SpecialFloat::NegInfinity) => "math.Inf(-1)".to_string(),
+            Some(SpecialFloat::NaN) => "math.NaN()".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::F32 => format!("float32({})", render_value_go(value, &RustType::F64)),
+        RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
+        RustType::String | RustType::Str => super::string_literal(value, Language::Go),
+        RustType::Char => super::char_literal(value, Language::Go),
+        RustType::Vec(inner) => {
+            let items: Vec<String> = value
+                .as_array()
+                .map(|arr| arr.iter().map(|v| render_value_go(v, inner)).collect())
+                .unwrap_or_default();
+            format!("{}{{{}}}", translate_type_go(ty), items.join(", "))
+        }
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("{}: {}", render_value_go(&k, key), render_value_go(v, inner)))
+                .collect();
+            format!("{}{{{}}}", translate_type_go(ty), entries.join(", "))
+        }
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => {
+            render_value_go(value, inner)
+        }
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
+        RustType::Void => String::new(),
+    }
+}
+
+/// `import` block for the standard packages `code` refers to, since Go
+/// rejects unused imports.
+fn imports(code: &str, always: &[&str]) -> String {
+    let mut packages: Vec<&str> = always.to_vec();
+    for package in ["fmt", "math", "reflect", "sort"] {
+        if !packages.contains(&package) && code.contains(&format!("{}.", package)) {
+            packages.push(package);
+        }
+    }
+    packages.sort();
+    match packages.as_slice() {
+        [] => String::new(),
+        [package] => format!("import \"{}\"\n\n", package),
+        _ => {
+            let lines: Vec<String> = packages.iter().map(|p| format!("\t\"{}\"\n", p)).collect();
+            format!("import (\n{})\n\n", lines.concat())
+        }
+    }
+}
+
+pub(super) fn generate_go(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
+) -> Result<(), String> {
+    let params_str: Vec<String> = sig
+        .params
+        .iter()
+        .map(|p| format!("{} {}", p.name, super::translate_type(unwrap_ref(&p.ty), Language::Go)))
+        .collect();
+    let ret_str = if sig.return_type == RustType::Void {
+        String::new()
+    } else {
+        format!(" {}", super::translate_type(&sig.return_type, Language::Go))
+    };
+
+    let mut main_body = String::new();
+    if let Some(inputs) = get_first_test_inputs(challenge) {
+        let mut args = Vec::new();
+        for p in &sig.params {
+            if let Some(val) = inputs.get(&p.name) {
+                main_body.push_str(&format!(
+                    "\t{} := {}\n",
+                    p.name,
+                    super::render_value(val, unwrap_ref(&p.ty), Language::Go)
+                ));
+                args.push(p.name.clone());
+            }
+        }
+        if is_void_with_mut_ref(sig) {
+            main_body.push_str(&format!("\t{}({})\n", sig.name, args.join(", ")));
+            if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
+                main_body.push_str(&format!("\tfmt.Println({})\n", p.name));
+            }
+        } else if sig.return_type == RustType::Void {
+            main_body.push_str(&format!("\t{}({})\n", sig.name, args.join(", ")));
+        } else {
+            main_body.push_str(&format!("\tresult := {}({})\n", sig.name, args.join(", ")));
+            main_body.push_str("\tfmt.Println(result)\n");
+        }
+    }
+
+    let body = options.body_or("\t", "\tpanic(\"TODO\")\n");
+    let solution_go = format!(
+        r#"package main
+
+{}func {}({}){} {{
+{}}}
+
+func main() {{
+{}}}"#,
+        imports(&format!("{}{}", body, main_body), &[]),
+        sig.name,
+        params_str.join(", "),
+        ret_str,
+        body,
+        main_body,
+    );
+
+    let tests_code = generate_go_tests(sig, &challenge.tests);
+
+    let mut metadata = ProjectMetadata::new(
+        challenge.name.clone(),
+        Language::Go,
+        difficulty,
+        sig.name.clone(),
+        options.initialized_at(),
+        challenge.difficulty,
+    );
+    metadata.challenge_index = options.challenge_index;
+    let metadata_content = metadata_json(&metadata);
+
+    let setup_sh = format!(
+        r#"#!/bin/bash
+set -e
+
+echo "[codle] step: checking required tools"
+{}
+
+echo "[codle] step: writing go.mod"
+cat > go.mod << 'GOMOD'
+module codle
+
+go 1.21
+GOMOD
+
+echo "[codle] step: writing solution.go"
+cat > solution.go << 'SOLUTION'
+{}
+SOLUTION
+
+echo "[codle] step: writing solution_test.go"
+cat > solution_test.go << 'TESTS'
+{}
+TESTS
+
+echo "[codle] step: writing .codle.json"
+cat > .codle.json << 'METADATA'
+{}
+METADATA
+
+echo "Run: go run ."
+echo "Test: go test -v ./..."
+"#,
+        require_commands(Language::Go.required_commands()),
+        escape_for_heredoc(&solution_go),
+        escape_for_heredoc(&tests_code),
+        metadata_content
+    );
+
+    write_setup_script(output_dir, &setup_sh)
+}
+
+/// Statements sorting slice `var` of `elem`s: numerically for floats, which
+/// are then compared within `float_epsilon`, and otherwise by Go-syntax
+/// representation, which orders any element type consistently.
+fn sort_statement(var: &str, elem: &RustType) -> String {
+    if *elem == RustType::F64 {
+        format!("\tsort.Float64s({})\n", var)
+    } else if *elem == RustType::F32 {
+        format!("\tsort.Slice({0}, func(i, j int) bool {{ return {0}[i] < {0}[j] }})\n", var)
+    } else {
+        format!(
+            "\tsort.Slice({0}, func(i, j int) bool {{ return fmt.Sprintf(\"%#v\", {0}[i]) < fmt.Sprintf(\"%#v\", {0}[j]) }})\n",
+            var
+        )
+    }
+}
+
+/// Statements that fail the test unless `actual` matches the expected value:
+/// floats within `float_epsilon`, slices and maps with `reflect.DeepEqual`.
+/// `math` works on `float64`, so `float32` values are converted first.
+/// An unordered slice is sorted, as is `expected`, before comparing.
+fn result_check(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    let value = &test.expected;
+    let expected = render_value_go(value, ty);
+    let (sort_actual, sort_expected) = match ty {
+        RustType::Vec(elem) if super::compares_unordered(test, ty) => {
+            (sort_statement(actual, elem), sort_statement("expected", elem))
+        }
+        _ => (String::new(), String::new()),
+    };
+    let condition = if super::expects_nan(ty, value) {
+        format!("!math.IsNaN({})", float64(actual, ty))
+    } else if super::compares_approximately(ty, value) {
+        format!(
+            "{a} != expected && math.Abs({d}) >= {}",
+            super::float_epsilon(ty),
+            a = actual,
+            d = float64(&format!("{}-expected", actual), ty)
+        )
+    } else if super::is_float_vec(ty) {
+        return format!(
+            r#"{sa}	expected := {e}
+{se}	if len({a}) != len(expected) {{
+		t.Fatalf("got %v, want %v", {a}, expected)
+	}}
+	for i := range expected {{
+		if {a}[i] != expected[i] && math.Abs({d}) >= {eps} {{
+			t.Fatalf("got %v, want %v", {a}, expected)
+		}}
+	}}
+"#,
+            sa = sort_actual,
+            e = expected,
+            se = sort_expected,
+            a = actual,
+            eps = super::float_epsilon(ty),
+            d = float64(&format!("{}[i]-expected[i]", actual), ty)
+        );
+    } else if matches!(ty, RustType::Vec(_) | RustType::Map(..)) {
+        // A nil slice or map and an empty one print the same but aren't DeepEqual.
+        format!(
+            "!(len({a}) == 0 && len(expected) == 0) && !reflect.DeepEqual({a}, expected)",
+            a = actual
+        )
+    } else {
+        format!("{} != expected", actual)
+    };
+    format!(
+        "{}\texpected := {}\n{}\tif {} {{\n\t\tt.Errorf(\"got %v, want %v\", {}, expected)\n\t}}\n",
+        sort_actual, expected, sort_expected, condition, actual
+    )
+}
+
+/// `expr`, a float of type `ty` or an element of it, as a `float64`.
+fn float64(expr: &str, ty: &RustType) -> String {
+    match ty {
+        RustType::Vec(inner) => float64(expr, inner),
+        RustType::F32 => format!("float64({})", expr),
+        _ => expr.to_string(),
+    }
+}
+
+pub(super) fn generate_go_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+    let mut test_fns = Vec::new();
+
+    for (i, test) in tests.iter().enumerate() {
+        let fn_suffix = match test.identifier() {
+            Some(name) => format!("_{}", name),
+            None => (i + 1).to_string(),
+        };
+        let mut body = String::new();
+
+        if let Some(inputs) = test.input.as_object() {
+            let mut args = Vec::new();
+            for p in &sig.params {
+                if let Some(val) = inputs.get(&p.name) {
+                    body.push_str(&format!(
+                        "\t{} := {}\n",
+                        p.name,
+                        super::render_value(val, unwrap_ref(&p.ty), Language::Go)
+                    ));
+                    args.push(p.name.clone());
+                }
+            }
+            if is_void_with_mut_ref(sig) {
+                body.push_str(&format!("\t{}({})\n", sig.name, args.join(", ")));
+                let outputs = super::mut_ref_outputs(sig, test);
+                for (p, output) in &outputs {
+                    let check = result_check(&p.name, output, unwrap_ref(&p.ty));
+                    if outputs.len() > 1 {
+                        body.push_str(&super::scoped(&check, "\t", "\t", "{"));
+                    } else {
+                        body.push_str(&check);
+                    }
+                }
+            } else if sig.return_type == RustType::Void {
+                body.push_str(&format!("\t{}({})\n", sig.name, args.join(", ")));
+            } else {
+                body.push_str(&format!("\tresult := {}({})\n", sig.name, args.join(", ")));
+                body.push_str(&result_check("result", test, &sig.return_type));
+            }
+        }
+
+        test_fns.push(format!("func Test{}(t *testing.T) {{\n{}}}", fn_suffix, body));
+    }
+
+    let test_fns = test_fns.join("\n\n");
+    format!("package main\n\n{}{}", imports(&test_fns, &["testing"]), test_fns)
+}
+
+/// Counts `go test -v`'s `--- PASS` and `--- FAIL` lines. A build failure
+/// leaves neither, only a `FAIL ... [build failed]` line.
+pub(super) fn parse_go_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for line in combined.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("--- PASS:") {
+            passed += 1;
+        } else if trimmed.starts_with("--- FAIL:") {
+            failed += 1;
+        }
+    }
+
+    Ok(super::TestSummary {
+        passed,
+        failed,
+        total: passed + failed,
+        output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
+    })
+}
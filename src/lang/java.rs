@@ -5,42 +5,100 @@ use crate::models::{
     TestCase, metadata_json,
 };
 use super::{
-    write_setup_script, require_commands, escape_for_heredoc,
-    is_void_with_mut_ref, get_first_test_inputs, unwrap_mut_ref,
-    get_first_mut_ref_inner_type,
+    write_setup_script, require_commands, escape_for_heredoc, build_tool_name,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref,
+    get_first_mut_ref_inner_type, SpecialFloat, ScaffoldOptions,
 };
 
+/// Java has no unsigned integers, so an unsigned type takes the next wider
+/// signed one: `u8` is a `short` and `u32` a `long`.
 pub(super) fn translate_type_java(ty: &RustType) -> String {
     match ty {
-        RustType::I32 | RustType::Usize => "int".to_string(),
+        RustType::I8 => "byte".to_string(),
+        RustType::I16 | RustType::U8 => "short".to_string(),
+        RustType::I32 | RustType::U16 | RustType::Usize => "int".to_string(),
+        RustType::I64 | RustType::U32 | RustType::U64 => "long".to_string(),
+        RustType::F32 => "float".to_string(),
         RustType::F64 => "double".to_string(),
         RustType::Bool => "boolean".to_string(),
-        RustType::String => "String".to_string(),
+        RustType::String | RustType::Str => "String".to_string(),
         RustType::Char => "char".to_string(),
         RustType::Vec(inner) => format!("{}[]", translate_type_java(inner)),
-        RustType::MutRef(inner) => translate_type_java(inner),
+        RustType::Option(inner) => boxed_type_java(inner),
+        RustType::Map(key, value) => {
+            format!("Map<{}, {}>", boxed_type_java(key), boxed_type_java(value))
+        }
+        RustType::Tuple(elems) => tuple_record_name(elems).to_string(),
+        RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_java(inner),
         RustType::Void => "void".to_string(),
     }
 }
 
+/// Accessors of the record a tuple is returned as, one per element.
+const TUPLE_FIELDS: [&str; 3] = ["first", "second", "third"];
+
+/// Java has no tuples, so a tuple return type is a record, declared next to
+/// `App` by `tuple_record`. `Language::supports` allows at most three elements.
+fn tuple_record_name(elems: &[RustType]) -> &'static str {
+    if elems.len() == 3 {
+        "Triple"
+    } else {
+        "Pair"
+    }
+}
+
+/// The declaration of the record `sig` returns, if it returns a tuple.
+fn tuple_record(sig: &FunctionSignature) -> String {
+    let RustType::Tuple(elems) = &sig.return_type else {
+        return String::new();
+    };
+    let fields: Vec<String> = elems
+        .iter()
+        .zip(TUPLE_FIELDS)
+        .map(|(ty, field)| format!("{} {}", translate_type_java(ty), field))
+        .collect();
+    format!("\n\nrecord {}({}) {{}}", tuple_record_name(elems), fields.join(", "))
+}
+
+/// The wrapper class for a primitive, which can hold `null`.
+fn boxed_type_java(ty: &RustType) -> String {
+    match ty {
+        RustType::I8 => "Byte".to_string(),
+        RustType::I16 | RustType::U8 => "Short".to_string(),
+        RustType::I32 | RustType::U16 | RustType::Usize => "Integer".to_string(),
+        RustType::I64 | RustType::U32 | RustType::U64 => "Long".to_string(),
+        RustType::F32 => "Float".to_string(),
+        RustType::F64 => "Double".to_string(),
+        RustType::Bool => "Boolean".to_string(),
+        RustType::Char => "Character".to_string(),
+        _ => translate_type_java(ty),
+    }
+}
+
 pub(super) fn render_value_java(value: &Value, ty: &RustType) -> String {
     match ty {
+        // An `int` literal only narrows implicitly in an assignment, not
+        // when passed to `assertEquals` or boxed into a `Byte`.
+        RustType::I8 => format!("(byte) {}", super::integer_literal(value)),
+        RustType::I16 | RustType::U8 => format!("(short) {}", super::integer_literal(value)),
         RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
-        RustType::F64 => {
-            let n = value.as_f64().unwrap_or(0.0);
-            if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                format!("{}", n)
-            }
-        }
+        RustType::U16 => super::integer_literal(value),
+        RustType::I64 | RustType::U32 | RustType::U64 => format!("{}L", super::integer_literal(value)),
+        RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "Double.POSITIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NegInfinity) => "Double.NEGATIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NaN) => "Double.NaN".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::F32 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "Float.POSITIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NegInfinity) => "Float.NEGATIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NaN) => "Float.NaN".to_string(),
+            None => format!("{}f", super::format_float(value.as_f64().unwrap_or(0.0))),
+        },
         RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
-        RustType::String => format!("\"{}\"", value.as_str().unwrap_or("")),
-        RustType::Char => {
-            let s = value.as_str().unwrap_or("?");
-            let c = s.chars().next().unwrap_or('?');
-            format!("'{}'", c)
-        }
+        RustType::String | RustType::Str => super::string_literal(value, Language::Java),
+        RustType::Char => super::char_literal(value, Language::Java),
         RustType::Vec(inner) => {
             if let Some(arr) = value.as_array() {
                 let items: Vec<String> = arr.iter().map(|v| render_value_java(v, inner)).collect();
@@ -49,7 +107,40 @@ pub(super) fn render_value_java(value: &Value, ty: &RustType) -> String {
                 format!("new {}[] {{}}", translate_type_java(inner))
             }
         }
-        RustType::MutRef(inner) => render_value_java(value, inner),
+        RustType::Option(_) if value.is_null() => "null".to_string(),
+        // Boxed explicitly, or `assertEquals(5, result)` is ambiguous.
+        RustType::Option(inner) => {
+            let rendered = render_value_java(value, inner);
+            let boxed = boxed_type_java(inner);
+            if boxed == translate_type_java(inner) {
+                rendered
+            } else {
+                format!("{}.valueOf({})", boxed, rendered)
+            }
+        }
+        // Copied into a `HashMap` so the solution may modify it; `Map.of`
+        // takes at most 10 pairs, past which the entries are listed one by one.
+        RustType::Map(key, inner) => {
+            let entries = super::map_entries(value, key);
+            let rendered: Vec<String> = entries
+                .iter()
+                .map(|(k, v)| format!("{}, {}", render_value_java(k, key), render_value_java(v, inner)))
+                .collect();
+            if entries.len() <= 10 {
+                format!("new HashMap<>(Map.of({}))", rendered.join(", "))
+            } else {
+                let pairs: Vec<String> = rendered.iter().map(|pair| format!("Map.entry({})", pair)).collect();
+                format!("new HashMap<>(Map.ofEntries({}))", pairs.join(", "))
+            }
+        }
+        RustType::Tuple(elems) => {
+            let items: Vec<String> = super::tuple_items(value, elems)
+                .iter()
+                .map(|(v, ty)| render_value_java(v, ty))
+                .collect();
+            format!("new {}({})", tuple_record_name(elems), items.join(", "))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_java(value, inner),
         RustType::Void => "".to_string(),
     }
 }
@@ -59,6 +150,7 @@ pub(super) fn generate_java(
     sig: &FunctionSignature,
     difficulty: Difficulty,
     output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
     let params_str: Vec<String> = sig
         .params
@@ -66,7 +158,7 @@ pub(super) fn generate_java(
         .map(|p| {
             format!(
                 "{} {}",
-                super::translate_type(unwrap_mut_ref(&p.ty), Language::Java),
+                super::translate_type(unwrap_ref(&p.ty), Language::Java),
                 p.name
             )
         })
@@ -90,10 +182,19 @@ pub(super) fn generate_java(
     let default_return = match effective_return_type {
         RustType::Void => String::new(),
         RustType::Bool => "        return false;\n".to_string(),
-        RustType::I32 | RustType::Usize => "        return 0;\n".to_string(),
+        RustType::I8
+        | RustType::I16
+        | RustType::I32
+        | RustType::I64
+        | RustType::U8
+        | RustType::U16
+        | RustType::U32
+        | RustType::U64
+        | RustType::Usize => "        return 0;\n".to_string(),
+        RustType::F32 => "        return 0.0f;\n".to_string(),
         RustType::F64 => "        return 0.0;\n".to_string(),
         RustType::String => "        return \"\";\n".to_string(),
-        RustType::Vec(_) => format!("        return new {};\n", render_value_java(&Value::Array(vec![]), effective_return_type)),
+        RustType::Vec(_) => format!("        return {};\n", render_value_java(&Value::Array(vec![]), effective_return_type)),
         _ => "        return null;\n".to_string(),
     };
 
@@ -101,7 +202,7 @@ pub(super) fn generate_java(
     if let Some(inputs) = get_first_test_inputs(challenge) {
         if is_void_with_mut_ref(sig) {
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
+                let inner_ty = unwrap_ref(&p.ty);
                 if let Some(val) = inputs.get(&p.name) {
                     main_body.push_str(&format!(
                         "        {} {} = {};\n",
@@ -119,9 +220,10 @@ pub(super) fn generate_java(
                 call_args.join(", ")
             ));
             if matches!(effective_return_type, RustType::Vec(_)) {
-                main_body.push_str(
-                    "        System.out.println(java.util.Arrays.toString(result));\n",
-                );
+                main_body.push_str(&format!(
+                    "        System.out.println(java.util.Arrays.{}(result));\n",
+                    arrays_to_string(effective_return_type)
+                ));
             } else {
                 main_body.push_str("        System.out.println(result);\n");
             }
@@ -131,9 +233,9 @@ pub(super) fn generate_java(
                 if let Some(val) = inputs.get(&p.name) {
                     main_body.push_str(&format!(
                         "        {} {} = {};\n",
-                        super::translate_type(unwrap_mut_ref(&p.ty), Language::Java),
+                        super::translate_type(unwrap_ref(&p.ty), Language::Java),
                         p.name,
-                        super::render_value(val, unwrap_mut_ref(&p.ty), Language::Java)
+                        super::render_value(val, unwrap_ref(&p.ty), Language::Java)
                     ));
                     args.push(p.name.clone());
                 }
@@ -145,9 +247,10 @@ pub(super) fn generate_java(
                 args.join(", ")
             ));
             if matches!(&sig.return_type, RustType::Vec(_)) {
-                main_body.push_str(
-                    "        System.out.println(java.util.Arrays.toString(result));\n",
-                );
+                main_body.push_str(&format!(
+                    "        System.out.println(java.util.Arrays.{}(result));\n",
+                    arrays_to_string(&sig.return_type)
+                ));
             } else {
                 main_body.push_str("        System.out.println(result);\n");
             }
@@ -157,58 +260,68 @@ pub(super) fn generate_java(
     let app_java = format!(
         r#"package codle;
 
-public class App {{
+{}public class App {{
     public static {} {}({}) {{
 {}    }}
 
     public static void main(String[] args) {{
 {}    }}
-}}"#,
+}}{}"#,
+        map_imports(sig),
         ret_type,
         sig.name,
         params_str.join(", "),
-        default_return,
+        options.body_or("        ", &default_return),
         main_body,
+        tuple_record(sig),
     );
 
     let tests_code = generate_java_tests(sig, &challenge.tests);
 
-    let metadata = ProjectMetadata::new(
+    let mut metadata = ProjectMetadata::new(
         challenge.name.clone(),
         Language::Java,
         difficulty,
         sig.name.clone(),
-        Some(chrono::Local::now().to_rfc3339()),
+        options.initialized_at(),
         challenge.difficulty,
     );
+    metadata.challenge_index = options.challenge_index;
     let metadata_content = metadata_json(&metadata);
 
     let setup_sh = format!(
         r#"#!/bin/bash
 set -e
 
+echo "[codle] step: checking required tools"
 {}
 
+echo "[codle] step: running gradle init"
 gradle init --type java-application --dsl groovy --project-name "{}" --package codle --no-incubating --overwrite
 
+echo "[codle] step: updating app/build.gradle"
 cat >> app/build.gradle << 'TESTLOG'
 
 test {{
     testLogging {{
         events "passed", "failed", "skipped"
+        exceptionFormat "full"
     }}
 }}
 TESTLOG
 
+echo "[codle] step: writing app/src/main/java/codle/App.java"
 cat > app/src/main/java/codle/App.java << 'SOLUTION'
 {}
 SOLUTION
 
+echo "[codle] step: writing app/src/test/java/codle/AppTest.java"
 mkdir -p app/src/test/java/codle
 cat > app/src/test/java/codle/AppTest.java << 'TESTS'
 {}
 TESTS
 
+echo "[codle] step: writing .codle.json"
 cat > .codle.json << 'METADATA'
 {}
 METADATA
@@ -216,8 +329,8 @@ METADATA
 echo "Run: ./gradlew run"
 echo "Test: ./gradlew test"
 "#,
-        require_commands(&["gradle"]),
-        sig.name,
+        require_commands(Language::Java.required_commands()),
+        build_tool_name(&sig.name),
         escape_for_heredoc(&app_java),
         escape_for_heredoc(&tests_code),
         metadata_content
@@ -226,17 +339,127 @@ echo "Test: ./gradlew test"
     write_setup_script(output_dir, &setup_sh)
 }
 
+fn map_imports(sig: &FunctionSignature) -> &'static str {
+    if super::uses_map(sig) {
+        "import java.util.HashMap;\nimport java.util.Map;\n\n"
+    } else {
+        ""
+    }
+}
+
+fn is_nested_array(ty: &RustType) -> bool {
+    matches!(ty, RustType::Vec(inner) if matches!(inner.as_ref(), RustType::Vec(_)))
+}
+
+/// `Arrays.toString` prints the addresses of inner arrays, so grids need
+/// `deepToString`.
+fn arrays_to_string(ty: &RustType) -> &'static str {
+    if is_nested_array(ty) {
+        "deepToString"
+    } else {
+        "toString"
+    }
+}
+
+/// Grids are compared with `Arrays.deepEquals` so every row is compared by
+/// value, with both grids in the failure message.
+fn deep_equals_assertion(actual: &str, expected: &str) -> String {
+    format!(
+        "        assertTrue(java.util.Arrays.deepEquals({e}, {a}), \"expected: \" + java.util.Arrays.deepToString({e}) + \" but was: \" + java.util.Arrays.deepToString({a}));\n",
+        e = expected,
+        a = actual
+    )
+}
+
+/// The expression the assertion compares `actual` with. For an unordered
+/// array, `actual` is sorted in place and a sorted `expected` declared in
+/// `body` instead.
+fn expected_value(body: &mut String, actual: &str, test: &TestCase, ty: &RustType) -> String {
+    let rendered = render_value_java(&test.expected, ty);
+    let RustType::Vec(elem) = ty else {
+        return rendered;
+    };
+    if !super::compares_unordered(test, ty) {
+        return rendered;
+    }
+    body.push_str(&sort_statement(actual, elem));
+    body.push_str(&format!("        {} expected = {};\n", translate_type_java(ty), rendered));
+    body.push_str(&sort_statement("expected", elem));
+    "expected".to_string()
+}
+
+/// Sorts array `var` of `elem`s. `boolean[]` has no `Arrays.sort`, so its
+/// `true`s are counted and moved to the front.
+fn sort_statement(var: &str, elem: &RustType) -> String {
+    match elem {
+        RustType::Vec(_) => format!("        java.util.Arrays.sort({}, java.util.Arrays::compare);\n", var),
+        RustType::Bool => format!(
+            "        {{ int trues = 0; for (boolean b : {0}) if (b) trues++; java.util.Arrays.fill({0}, false); java.util.Arrays.fill({0}, 0, trues, true); }}\n",
+            var
+        ),
+        _ => format!("        java.util.Arrays.sort({});\n", var),
+    }
+}
+
+/// Float results are compared within `float_epsilon`, arrays element by
+/// element.
+fn float_assertion(actual: &str, expected: &str, ty: &RustType) -> String {
+    format!("        assertEquals({}, {}, {});\n", expected, actual, super::typed_float_epsilon(ty))
+}
+
+fn float_array_assertion(actual: &str, expected: &str, ty: &RustType) -> String {
+    format!(
+        "        assertArrayEquals({}, {}, {});\n",
+        expected,
+        actual,
+        super::typed_float_epsilon(ty)
+    )
+}
+
+/// Appends to `body` the assertions that `actual`, of type `ty`, matches the
+/// test's expected value. A record's `equals` compares arrays by identity, so
+/// a tuple is checked field by field.
+fn result_assertion(body: &mut String, actual: &str, test: &TestCase, ty: &RustType) {
+    if let RustType::Tuple(elems) = ty {
+        for ((expected, elem), field) in super::tuple_items(&test.expected, elems).into_iter().zip(TUPLE_FIELDS) {
+            let item = TestCase { expected, ..test.clone() };
+            result_assertion(body, &format!("{}.{}()", actual, field), &item, elem);
+        }
+        return;
+    }
+    let expected = expected_value(body, actual, test, ty);
+    let assertion = if is_nested_array(ty) {
+        deep_equals_assertion(actual, &expected)
+    } else if super::is_float_vec(ty) {
+        float_array_assertion(actual, &expected, ty)
+    } else if matches!(ty, RustType::Vec(_)) {
+        format!("        assertArrayEquals({}, {});\n", expected, actual)
+    } else if super::expects_nan(ty, &test.expected) {
+        format!("        assertTrue(Double.isNaN({0}), \"expected: NaN but was: \" + {0});\n", actual)
+    } else if test.expected.is_null() {
+        format!("        assertNull({});\n", actual)
+    } else if super::compares_approximately(ty, &test.expected) {
+        float_assertion(actual, &expected, ty)
+    } else {
+        format!("        assertEquals({}, {});\n", expected, actual)
+    };
+    body.push_str(&assertion);
+}
+
 pub(super) fn generate_java_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
     let mut test_fns = Vec::new();
 
     for (i, test) in tests.iter().enumerate() {
-        let test_num = i + 1;
+        let method_suffix = match test.identifier() {
+            Some(name) => format!("_{}", name),
+            None => (i + 1).to_string(),
+        };
         let mut body = String::new();
 
         if let Some(inputs) = test.input.as_object() {
             if is_void_with_mut_ref(sig) {
                 for p in &sig.params {
-                    let inner_ty = unwrap_mut_ref(&p.ty);
+                    let inner_ty = unwrap_ref(&p.ty);
                     if let Some(val) = inputs.get(&p.name) {
                         body.push_str(&format!(
                             "        {} {} = {};\n",
@@ -255,17 +478,18 @@ pub(super) fn generate_java_tests(sig: &FunctionSignature, tests: &[TestCase]) -
                         sig.name,
                         call_args.join(", ")
                     ));
-                    let expected = super::render_value(&test.expected, inner_ty, Language::Java);
-                    if matches!(inner_ty, RustType::Vec(_)) {
-                        body.push_str(&format!(
-                            "        assertArrayEquals({}, result);\n",
-                            expected
-                        ));
-                    } else {
-                        body.push_str(&format!(
-                            "        assertEquals({}, result);\n",
-                            expected
-                        ));
+                    // The first `&mut` parameter's value is returned; any
+                    // others are arrays the method updates in place.
+                    let returned = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_)));
+                    let outputs = super::mut_ref_outputs(sig, test);
+                    for (p, output) in &outputs {
+                        let actual = if returned.is_some_and(|r| r.name == p.name) { "result" } else { &p.name };
+                        let mut code = String::new();
+                        result_assertion(&mut code, actual, output, unwrap_ref(&p.ty));
+                        if outputs.len() > 1 {
+                            code = super::scoped(&code, "        ", "    ", "{");
+                        }
+                        body.push_str(&code);
                     }
                 }
             } else {
@@ -274,9 +498,9 @@ pub(super) fn generate_java_tests(sig: &FunctionSignature, tests: &[TestCase]) -
                     if let Some(val) = inputs.get(&p.name) {
                         body.push_str(&format!(
                             "        {} {} = {};\n",
-                            super::translate_type(unwrap_mut_ref(&p.ty), Language::Java),
+                            super::translate_type(unwrap_ref(&p.ty), Language::Java),
                             p.name,
-                            super::render_value(val, unwrap_mut_ref(&p.ty), Language::Java)
+                            super::render_value(val, unwrap_ref(&p.ty), Language::Java)
                         ));
                         args.push(p.name.clone());
                     }
@@ -287,12 +511,7 @@ pub(super) fn generate_java_tests(sig: &FunctionSignature, tests: &[TestCase]) -
                     sig.name,
                     args.join(", ")
                 ));
-                let expected = super::render_value(&test.expected, &sig.return_type, Language::Java);
-                if matches!(&sig.return_type, RustType::Vec(_)) {
-                    body.push_str(&format!("        assertArrayEquals({}, result);\n", expected));
-                } else {
-                    body.push_str(&format!("        assertEquals({}, result);\n", expected));
-                }
+                result_assertion(&mut body, "result", test, &sig.return_type);
             }
         }
 
@@ -300,19 +519,52 @@ pub(super) fn generate_java_tests(sig: &FunctionSignature, tests: &[TestCase]) -
             r#"    @Test
     void test{}() {{
 {}    }}"#,
-            test_num, body
+            method_suffix, body
         ));
     }
 
     format!(
         r#"package codle;
 
-import org.junit.jupiter.api.Test;
+{}import org.junit.jupiter.api.Test;
 import static org.junit.jupiter.api.Assertions.*;
 
 class AppTest {{
 {}
 }}"#,
+        map_imports(sig),
         test_fns.join("\n\n")
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::parse_signature;
+
+    #[test]
+    fn test_tuple_return_snapshot() {
+        let sig = parse_signature("fn min_max(nums: Vec<i32>) -> (i32, i32)").unwrap();
+        let tests: Vec<TestCase> =
+            serde_json::from_value(json!([{"input": {"nums": [3, 1, 2]}, "expected": [1, 3]}])).unwrap();
+        assert_eq!(translate_type_java(&sig.return_type), "Pair");
+        assert_eq!(tuple_record(&sig), "\n\nrecord Pair(int first, int second) {}");
+        let expected = r#"package codle;
+
+import org.junit.jupiter.api.Test;
+import static org.junit.jupiter.api.Assertions.*;
+
+class AppTest {
+    @Test
+    void test1() {
+        int[] nums = new int[] {3, 1, 2};
+        Pair result = App.min_max(nums);
+        assertEquals(1, result.first());
+        assertEquals(3, result.second());
+    }
+}"#;
+        assert_eq!(generate_java_tests(&sig, &tests), expected);
+    }
+}
@@ -0,0 +1,353 @@
+use serde_json::Value;
+
+use crate::models::{
+    Challenge, Difficulty, FunctionSignature, Language, ProjectMetadata, RustType,
+    TestCase, metadata_json,
+};
+use super::{
+    write_setup_script, require_commands, escape_for_heredoc, build_tool_name,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref,
+    SpecialFloat, ScaffoldOptions,
+};
+
+/// The JSDoc or TypeScript type for `ty`. Every integer is a plain `number`,
+/// which is why `Language::supports` rejects 64-bit ones. A union element
+/// type is parenthesized so `[]` applies to the whole union.
+pub(super) fn translate_type_js(ty: &RustType) -> String {
+    match ty {
+        RustType::I8
+        | RustType::I16
+        | RustType::I32
+        | RustType::I64
+        | RustType::U8
+        | RustType::U16
+        | RustType::U32
+        | RustType::U64
+        | RustType::Usize
+        | RustType::F32
+        | RustType::F64 => {
+            "number".to_string()
+        }
+        RustType::Bool => "boolean".to_string(),
+        RustType::String | RustType::Str | RustType::Char => "string".to_string(),
+        RustType::Vec(inner) => match inner.as_ref() {
+            RustType::Option(_) => format!("({})[]", translate_type_js(inner)),
+            _ => format!("{}[]", translate_type_js(inner)),
+        },
+        RustType::Option(inner) => format!("{} | null", translate_type_js(inner)),
+        RustType::Map(key, value) => {
+            format!("Map<{}, {}>", translate_type_js(key), translate_type_js(value))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_js(inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
+        RustType::Void => "void".to_string(),
+    }
+}
+
+pub(super) fn render_value_js(value: &Value, ty: &RustType) -> String {
+    match ty {
+        RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
+        RustType::I8 | RustType::I16 | RustType::I64 | RustType::U8 | RustType::U16 | RustType::U32 | RustType::U64 => {
+            super::integer_literal(value)
+        }
+        RustType::F32 | RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "Infinity".to_string(),
+            Some(SpecialFloat::NegInfinity) => "-Infinity".to_string(),
+            Some(SpecialFloat::NaN) => "NaN".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
+        RustType::String | RustType::Str => super::string_literal(value, Language::Js),
+        RustType::Char => {
+            let c = value.as_str().and_then(|s| s.chars().next()).unwrap_or('?');
+            super::string_literal(&Value::String(c.to_string()), Language::Js)
+        }
+        RustType::Vec(inner) => {
+            let items: Vec<String> = value
+                .as_array()
+                .map(|arr| arr.iter().map(|v| render_value_js(v, inner)).collect())
+                .unwrap_or_default();
+            format!("[{}]", items.join(", "))
+        }
+        RustType::Option(_) if value.is_null() => "null".to_string(),
+        RustType::Option(inner) => render_value_js(value, inner),
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("[{}, {}]", render_value_js(&k, key), render_value_js(v, inner)))
+                .collect();
+            format!("new Map([{}])", entries.join(", "))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_js(value, inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
+        RustType::Void => "undefined".to_string(),
+    }
+}
+
+pub(super) fn generate_javascript(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
+) -> Result<(), String> {
+    let mut jsdoc = vec!["/**".to_string()];
+    for p in &sig.params {
+        jsdoc.push(format!(
+            " * @param {{{}}} {}",
+            super::translate_type(unwrap_ref(&p.ty), Language::Js),
+            p.name
+        ));
+    }
+    jsdoc.push(format!(
+        " * @returns {{{}}}",
+        super::translate_type(&sig.return_type, Language::Js)
+    ));
+    jsdoc.push(" */".to_string());
+    let param_names: Vec<String> = sig.params.iter().map(|p| p.name.clone()).collect();
+
+    let mut main_body = String::new();
+    if let Some(inputs) = get_first_test_inputs(challenge) {
+        let mut args = Vec::new();
+        for p in &sig.params {
+            if let Some(val) = inputs.get(&p.name) {
+                main_body.push_str(&format!(
+                    "    const {} = {};\n",
+                    p.name,
+                    super::render_value(val, unwrap_ref(&p.ty), Language::Js)
+                ));
+                args.push(p.name.clone());
+            }
+        }
+        if is_void_with_mut_ref(sig) {
+            main_body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+            if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
+                main_body.push_str(&format!("    console.log({});\n", p.name));
+            }
+        } else {
+            main_body.push_str(&format!("    const result = {}({});\n", sig.name, args.join(", ")));
+            main_body.push_str("    console.log(result);\n");
+        }
+    }
+
+    let solution_js = format!(
+        r#"{}
+function {}({}) {{
+{}}}
+
+module.exports = {{ {} }};
+
+if (require.main === module) {{
+{}}}"#,
+        jsdoc.join("\n"),
+        sig.name,
+        param_names.join(", "),
+        options.body_or("    ", "    throw new Error(\"TODO\");\n"),
+        sig.name,
+        main_body,
+    );
+
+    let tests_code = generate_javascript_tests(sig, &challenge.tests);
+
+    let package_json = format!(
+        r#"{{
+  "name": "{}",
+  "private": true,
+  "scripts": {{
+    "test": "jest"
+  }},
+  "devDependencies": {{
+    "jest": "^29.7.0"
+  }}
+}}"#,
+        build_tool_name(&sig.name)
+    );
+
+    let mut metadata = ProjectMetadata::new(
+        challenge.name.clone(),
+        Language::Js,
+        difficulty,
+        sig.name.clone(),
+        options.initialized_at(),
+        challenge.difficulty,
+    );
+    metadata.challenge_index = options.challenge_index;
+    let metadata_content = metadata_json(&metadata);
+
+    let setup_sh = format!(
+        r#"#!/bin/bash
+set -e
+
+echo "[codle] step: checking required tools"
+{}
+
+echo "[codle] step: writing package.json"
+cat > package.json << 'PACKAGE'
+{}
+PACKAGE
+
+echo "[codle] step: installing jest"
+npm install
+
+echo "[codle] step: writing solution.js"
+cat > solution.js << 'SOLUTION'
+{}
+SOLUTION
+
+echo "[codle] step: writing solution.test.js"
+cat > solution.test.js << 'TESTS'
+{}
+TESTS
+
+echo "[codle] step: writing .codle.json"
+cat > .codle.json << 'METADATA'
+{}
+METADATA
+
+echo "Run: node solution.js"
+echo "Test: npx jest"
+"#,
+        require_commands(Language::Js.required_commands()),
+        package_json,
+        escape_for_heredoc(&solution_js),
+        escape_for_heredoc(&tests_code),
+        metadata_content
+    );
+
+    write_setup_script(output_dir, &setup_sh)
+}
+
+/// Jest assertion that `actual` matches the expected value: `toBe` for
+/// scalars, `toEqual` for arrays and maps, and floats within `float_epsilon`, element
+/// by element for a `number[]`. An unordered array is compared after
+/// putting both sides through the test file's `unordered` helper.
+pub(super) fn result_assertion(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    let value = &test.expected;
+    let mut expected = render_value_js(value, ty);
+    let mut code = String::new();
+    let mut actual = actual;
+    if super::compares_unordered(test, ty) {
+        code.push_str(&format!("    const actual = unordered({});\n", actual));
+        actual = "actual";
+        expected = format!("unordered({})", expected);
+    }
+
+    let assertion = if super::expects_nan(ty, value) {
+        format!("    expect({}).toBeNaN();\n", actual)
+    } else if value.is_null() {
+        format!("    expect({}).toBeNull();\n", actual)
+    } else if super::compares_approximately(unwrap_option(ty), value) {
+        format!(
+            "    expect(Math.abs({} - {})).toBeLessThan({});\n",
+            actual,
+            expected,
+            super::float_epsilon(ty)
+        )
+    } else if super::is_float_vec(ty) {
+        format!(
+            r#"    const expected = {e};
+    expect({a}).toHaveLength(expected.length);
+    expected.forEach((x, i) => expect(Math.abs({a}[i] - x)).toBeLessThan({eps}));
+"#,
+            e = expected,
+            a = actual,
+            eps = super::float_epsilon(ty)
+        )
+    } else if matches!(unwrap_option(ty), RustType::Vec(_) | RustType::Map(..)) {
+        format!("    expect({}).toEqual({});\n", actual, expected)
+    } else {
+        format!("    expect({}).toBe({});\n", actual, expected)
+    };
+    code + &assertion
+}
+
+/// Sorts a copy of an array into a canonical order: numbers by value, so
+/// floats still line up for the tolerance check, and anything else by its
+/// JSON.
+const UNORDERED_HELPER: &str = r#"function unordered(xs) {
+    const key = (x) => (typeof x === "number" ? x : JSON.stringify(x));
+    return [...xs].sort((a, b) => (key(a) < key(b) ? -1 : key(a) > key(b) ? 1 : 0));
+}"#;
+
+fn unwrap_option(ty: &RustType) -> &RustType {
+    match ty {
+        RustType::Option(inner) => inner,
+        other => other,
+    }
+}
+
+pub(super) fn generate_javascript_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+    let mut test_fns = vec![format!("const {{ {} }} = require(\"./solution\");", sig.name)];
+    if tests.iter().any(|t| t.unordered) {
+        test_fns.push(UNORDERED_HELPER.to_string());
+    }
+
+    for (i, test) in tests.iter().enumerate() {
+        let test_name = super::test_fn_suffix(test, i);
+        let mut body = String::new();
+
+        if let Some(inputs) = test.input.as_object() {
+            let mut args = Vec::new();
+            for p in &sig.params {
+                if let Some(val) = inputs.get(&p.name) {
+                    body.push_str(&format!(
+                        "    const {} = {};\n",
+                        p.name,
+                        super::render_value(val, unwrap_ref(&p.ty), Language::Js)
+                    ));
+                    args.push(p.name.clone());
+                }
+            }
+            if is_void_with_mut_ref(sig) {
+                body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+                let outputs = super::mut_ref_outputs(sig, test);
+                for (p, output) in &outputs {
+                    let assertion = result_assertion(&p.name, output, unwrap_ref(&p.ty));
+                    if outputs.len() > 1 {
+                        body.push_str(&super::scoped(&assertion, "    ", "    ", "{"));
+                    } else {
+                        body.push_str(&assertion);
+                    }
+                }
+            } else if sig.return_type == RustType::Void {
+                body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+            } else {
+                body.push_str(&format!("    const result = {}({});\n", sig.name, args.join(", ")));
+                body.push_str(&result_assertion("result", test, &sig.return_type));
+            }
+        }
+
+        test_fns.push(format!("test(\"test_{}\", () => {{\n{}}});", test_name, body));
+    }
+
+    test_fns.join("\n\n")
+}
+
+/// Reads Jest's `Tests:       1 failed, 2 passed, 3 total` summary line.
+pub(super) fn parse_jest_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    if let Some(line) = combined.lines().find(|line| line.trim_start().starts_with("Tests:")) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        for (i, part) in parts.iter().enumerate() {
+            let count = || parts[i - 1].parse::<usize>().ok();
+            match part.trim_end_matches(',') {
+                "passed" if i > 0 => passed = count().unwrap_or(passed),
+                "failed" if i > 0 => failed = count().unwrap_or(failed),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(super::TestSummary {
+        passed,
+        failed,
+        total: passed + failed,
+        output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
+    })
+}
@@ -5,51 +5,101 @@ use crate::models::{
     TestCase, metadata_json,
 };
 use super::{
-    write_setup_script, require_commands, escape_for_heredoc,
-    is_void_with_mut_ref, get_first_test_inputs, unwrap_mut_ref,
-    get_first_mut_ref_inner_type,
+    write_setup_script, require_commands, escape_for_heredoc, build_tool_name,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref,
+    get_first_mut_ref_inner_type, SpecialFloat, ScaffoldOptions,
 };
 
+/// An unsigned type widens to the next signed one, as in Java, rather than
+/// taking Kotlin's `UByte` and friends, which need `u` literals everywhere.
+/// `u64` has no wider type, so it is a `ULong`.
 pub(super) fn translate_type_kt(ty: &RustType) -> String {
     match ty {
-        RustType::I32 | RustType::Usize => "Int".to_string(),
+        RustType::I8 => "Byte".to_string(),
+        RustType::I16 | RustType::U8 => "Short".to_string(),
+        RustType::I32 | RustType::U16 | RustType::Usize => "Int".to_string(),
+        RustType::I64 | RustType::U32 => "Long".to_string(),
+        RustType::U64 => "ULong".to_string(),
+        RustType::F32 => "Float".to_string(),
         RustType::F64 => "Double".to_string(),
         RustType::Bool => "Boolean".to_string(),
-        RustType::String => "String".to_string(),
+        RustType::String | RustType::Str => "String".to_string(),
         RustType::Char => "Char".to_string(),
         RustType::Vec(inner) => format!("MutableList<{}>", translate_type_kt(inner)),
-        RustType::MutRef(inner) => translate_type_kt(inner),
+        RustType::Option(inner) => format!("{}?", translate_type_kt(inner)),
+        RustType::Map(key, value) => {
+            format!("MutableMap<{}, {}>", translate_type_kt(key), translate_type_kt(value))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_kt(inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
         RustType::Void => "Unit".to_string(),
     }
 }
 
+/// A `Byte` or `Short` value. Kotlin has no literals for them and an `Int`
+/// never equals a `Byte` in `assertEquals`, so the value is converted; a
+/// negative one is parenthesized, since `-1.toByte()` negates into an `Int`.
+fn narrowed_literal(value: &Value, conversion: &str) -> String {
+    let n = super::integer_literal(value);
+    if n.starts_with('-') {
+        format!("({}).{}()", n, conversion)
+    } else {
+        format!("{}.{}()", n, conversion)
+    }
+}
+
 pub(super) fn render_value_kt(value: &Value, ty: &RustType) -> String {
     match ty {
+        RustType::I8 => narrowed_literal(value, "toByte"),
+        RustType::I16 | RustType::U8 => narrowed_literal(value, "toShort"),
         RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
-        RustType::F64 => {
-            let n = value.as_f64().unwrap_or(0.0);
-            if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                format!("{}", n)
-            }
-        }
+        RustType::U16 => super::integer_literal(value),
+        // `-9223372036854775808L` negates a literal that is out of range.
+        RustType::I64 if value.as_i64() == Some(i64::MIN) => "Long.MIN_VALUE".to_string(),
+        RustType::I64 | RustType::U32 => format!("{}L", super::integer_literal(value)),
+        RustType::U64 => format!("{}uL", super::integer_literal(value)),
+        RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "Double.POSITIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NegInfinity) => "Double.NEGATIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NaN) => "Double.NaN".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
+        RustType::F32 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "Float.POSITIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NegInfinity) => "Float.NEGATIVE_INFINITY".to_string(),
+            Some(SpecialFloat::NaN) => "Float.NaN".to_string(),
+            None => format!("{}f", super::format_float(value.as_f64().unwrap_or(0.0))),
+        },
         RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
-        RustType::String => format!("\"{}\"", value.as_str().unwrap_or("")),
-        RustType::Char => {
-            let s = value.as_str().unwrap_or("?");
-            let c = s.chars().next().unwrap_or('?');
-            format!("'{}'", c)
-        }
+        // `$` would start a string template.
+        RustType::String | RustType::Str => super::string_literal(value, Language::Kt).replace('$', "\\$"),
+        RustType::Char => super::char_literal(value, Language::Kt),
         RustType::Vec(inner) => {
-            if let Some(arr) = value.as_array() {
-                let items: Vec<String> = arr.iter().map(|v| render_value_kt(v, inner)).collect();
-                format!("mutableListOf({})", items.join(", "))
+            match value.as_array() {
+                Some(arr) if !arr.is_empty() => {
+                    let items: Vec<String> = arr.iter().map(|v| render_value_kt(v, inner)).collect();
+                    format!("mutableListOf({})", items.join(", "))
+                }
+                _ => format!("mutableListOf<{}>()", translate_type_kt(inner)),
+            }
+        }
+        RustType::Option(_) if value.is_null() => "null".to_string(),
+        RustType::Option(inner) => render_value_kt(value, inner),
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("{} to {}", render_value_kt(&k, key), render_value_kt(v, inner)))
+                .collect();
+            if entries.is_empty() {
+                format!("mutableMapOf<{}, {}>()", translate_type_kt(key), translate_type_kt(inner))
             } else {
-                "mutableListOf()".to_string()
+                format!("mutableMapOf({})", entries.join(", "))
             }
         }
-        RustType::MutRef(inner) => render_value_kt(value, inner),
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_kt(value, inner),
+        // Rejected by `Language::supports`.
+        RustType::Tuple(_) => String::new(),
         RustType::Void => "Unit".to_string(),
     }
 }
@@ -59,6 +109,7 @@ pub(super) fn generate_kotlin(
     sig: &FunctionSignature,
     difficulty: Difficulty,
     output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
     let params_str: Vec<String> = sig
         .params
@@ -67,7 +118,7 @@ pub(super) fn generate_kotlin(
             format!(
                 "{}: {}",
                 p.name,
-                super::translate_type(unwrap_mut_ref(&p.ty), Language::Kt)
+                super::translate_type(unwrap_ref(&p.ty), Language::Kt)
             )
         })
         .collect();
@@ -87,7 +138,7 @@ pub(super) fn generate_kotlin(
     if let Some(inputs) = get_first_test_inputs(challenge) {
         if is_void_with_mut_ref(sig) {
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
+                let inner_ty = unwrap_ref(&p.ty);
                 if let Some(val) = inputs.get(&p.name) {
                     main_body.push_str(&format!(
                         "    val {} = {}\n",
@@ -110,7 +161,7 @@ pub(super) fn generate_kotlin(
                     main_body.push_str(&format!(
                         "    val {} = {}\n",
                         p.name,
-                        super::render_value(val, unwrap_mut_ref(&p.ty), Language::Kt)
+                        super::render_value(val, unwrap_ref(&p.ty), Language::Kt)
                     ));
                     args.push(p.name.clone());
                 }
@@ -128,55 +179,63 @@ pub(super) fn generate_kotlin(
         r#"package codle
 
 fun {}({}){} {{
-    TODO()
-}}
+{}}}
 
 fun main() {{
 {}}}"#,
         sig.name,
         params_str.join(", "),
         ret_str,
+        options.body_or("    ", "    TODO()\n"),
         main_body,
     );
 
     let tests_code = generate_kotlin_tests(sig, &challenge.tests);
 
-    let metadata = ProjectMetadata::new(
+    let mut metadata = ProjectMetadata::new(
         challenge.name.clone(),
         Language::Kt,
         difficulty,
         sig.name.clone(),
-        Some(chrono::Local::now().to_rfc3339()),
+        options.initialized_at(),
         challenge.difficulty,
     );
+    metadata.challenge_index = options.challenge_index;
     let metadata_content = metadata_json(&metadata);
 
     let setup_sh = format!(
         r#"#!/bin/bash
 set -e
 
+echo "[codle] step: checking required tools"
 {}
 
+echo "[codle] step: running gradle init"
 gradle init --type kotlin-application --dsl kotlin --project-name "{}" --package codle --no-incubating --overwrite
 
+echo "[codle] step: updating app/build.gradle.kts"
 cat >> app/build.gradle.kts << 'TESTLOG'
 
 tasks.withType<Test> {{
     testLogging {{
         events("passed", "failed", "skipped")
+        exceptionFormat = org.gradle.api.tasks.testing.logging.TestExceptionFormat.FULL
     }}
 }}
 TESTLOG
 
+echo "[codle] step: writing app/src/main/kotlin/codle/App.kt"
 cat > app/src/main/kotlin/codle/App.kt << 'SOLUTION'
 {}
 SOLUTION
 
+echo "[codle] step: writing app/src/test/kotlin/codle/AppTest.kt"
 mkdir -p app/src/test/kotlin/codle
 cat > app/src/test/kotlin/codle/AppTest.kt << 'TESTS'
 {}
 TESTS
 
+echo "[codle] step: writing .codle.json"
 cat > .codle.json << 'METADATA'
 {}
 METADATA
@@ -184,8 +243,8 @@ METADATA
 echo "Run: ./gradlew run"
 echo "Test: ./gradlew test"
 "#,
-        require_commands(&["gradle"]),
-        sig.name,
+        require_commands(Language::Kt.required_commands()),
+        build_tool_name(&sig.name),
         escape_for_heredoc(&app_kt),
         escape_for_heredoc(&tests_code),
         metadata_content
@@ -194,17 +253,67 @@ echo "Test: ./gradlew test"
     write_setup_script(output_dir, &setup_sh)
 }
 
+/// `assertEquals` on `actual`, except that a float is compared within
+/// `float_epsilon`, element by element for a list of floats. An unordered
+/// list is compared by how often each element occurs, or sorted if it holds
+/// floats.
+fn result_assertion(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    let value = &test.expected;
+    let unordered = super::compares_unordered(test, ty);
+    let mut expected = render_value_kt(value, ty);
+    if super::compares_approximately(ty, value) {
+        return format!("        assertEquals({}, {}, {})\n", expected, actual, super::typed_float_epsilon(ty));
+    }
+    if !super::is_float_vec(ty) {
+        if unordered {
+            return format!(
+                "        assertEquals({}.groupingBy {{ it }}.eachCount(), {}.groupingBy {{ it }}.eachCount())\n",
+                expected, actual
+            );
+        }
+        return format!("        assertEquals({}, {})\n", expected, actual);
+    }
+    let mut code = String::new();
+    let actual = if unordered {
+        code.push_str(&format!("        val actual = {}.sorted()\n", actual));
+        expected = format!("{}.sorted()", expected);
+        "actual"
+    } else {
+        actual
+    };
+    code.push_str(&format!(
+        r#"        val expected = {e}
+        assertEquals(expected.size, {a}.size, "expected: $expected but was: ${a}")
+        for (i in expected.indices) {{
+            assertEquals(expected[i], {a}[i], {eps}, "expected: $expected but was: ${a}")
+        }}
+"#,
+        e = expected,
+        a = actual,
+        eps = super::typed_float_epsilon(ty)
+    ));
+    code
+}
+
 pub(super) fn generate_kotlin_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
     let mut test_fns = Vec::new();
+    let extra_imports = if tests.iter().any(|t| super::expects_nan(&sig.return_type, &t.expected)) {
+        "import kotlin.test.assertTrue\n"
+    } else {
+        ""
+    };
 
     for (i, test) in tests.iter().enumerate() {
-        let test_num = i + 1;
+        let method_suffix = match test.identifier() {
+            Some(name) => format!("_{}", name),
+            None => (i + 1).to_string(),
+        };
         let mut body = String::new();
 
         if let Some(inputs) = test.input.as_object() {
             if is_void_with_mut_ref(sig) {
                 for p in &sig.params {
-                    let inner_ty = unwrap_mut_ref(&p.ty);
+                    let inner_ty = unwrap_ref(&p.ty);
                     if let Some(val) = inputs.get(&p.name) {
                         body.push_str(&format!(
                             "        val {} = {}\n",
@@ -219,9 +328,18 @@ pub(super) fn generate_kotlin_tests(sig: &FunctionSignature, tests: &[TestCase])
                     sig.name,
                     call_args.join(", ")
                 ));
-                if let Some(inner_ty) = get_first_mut_ref_inner_type(sig) {
-                    let expected = super::render_value(&test.expected, inner_ty, Language::Kt);
-                    body.push_str(&format!("        assertEquals({}, result)\n", expected));
+                // The first `&mut` parameter's value is returned; any others
+                // are lists the function updates in place.
+                let returned = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_)));
+                let outputs = super::mut_ref_outputs(sig, test);
+                for (p, output) in &outputs {
+                    let actual = if returned.is_some_and(|r| r.name == p.name) { "result" } else { &p.name };
+                    let code = result_assertion(actual, output, unwrap_ref(&p.ty));
+                    if outputs.len() > 1 {
+                        body.push_str(&super::scoped(&code, "        ", "    ", "run {"));
+                    } else {
+                        body.push_str(&code);
+                    }
                 }
             } else {
                 let mut args = Vec::new();
@@ -230,7 +348,7 @@ pub(super) fn generate_kotlin_tests(sig: &FunctionSignature, tests: &[TestCase])
                         body.push_str(&format!(
                             "        val {} = {}\n",
                             p.name,
-                            super::render_value(val, unwrap_mut_ref(&p.ty), Language::Kt)
+                            super::render_value(val, unwrap_ref(&p.ty), Language::Kt)
                         ));
                         args.push(p.name.clone());
                     }
@@ -240,8 +358,11 @@ pub(super) fn generate_kotlin_tests(sig: &FunctionSignature, tests: &[TestCase])
                     sig.name,
                     args.join(", ")
                 ));
-                let expected = super::render_value(&test.expected, &sig.return_type, Language::Kt);
-                body.push_str(&format!("        assertEquals({}, result)\n", expected));
+                if super::expects_nan(&sig.return_type, &test.expected) {
+                    body.push_str("        assertTrue(result.isNaN(), \"expected: NaN but was: $result\")\n");
+                } else {
+                    body.push_str(&result_assertion("result", test, &sig.return_type));
+                }
             }
         }
 
@@ -249,7 +370,7 @@ pub(super) fn generate_kotlin_tests(sig: &FunctionSignature, tests: &[TestCase])
             r#"    @Test
     fun test{}() {{
 {}    }}"#,
-            test_num, body
+            method_suffix, body
         ));
     }
 
@@ -258,10 +379,11 @@ pub(super) fn generate_kotlin_tests(sig: &FunctionSignature, tests: &[TestCase])
 
 import kotlin.test.Test
 import kotlin.test.assertEquals
-
+{}
 class AppTest {{
 {}
 }}"#,
+        extra_imports,
         test_fns.join("\n\n")
     )
 }
@@ -4,14 +4,69 @@ mod kotlin;
 mod java;
 mod c;
 mod cpp;
+mod fortran;
+mod go;
+mod javascript;
+mod csharp;
+mod typescript;
+mod template;
 
 use std::fs;
+use std::io::{Read, Write};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use serde_json::Value;
 
-use crate::models::{Challenge, Difficulty, FunctionSignature, Language, RustType};
+use crate::models::{validate_challenge, Challenge, Difficulty, FunctionSignature, Language, Param, RustType, TestCase};
+
+pub use template::generate_from_template;
+
+/// Optional tweaks to what `generate_scaffold` writes.
+#[derive(Debug, Default, Clone)]
+pub struct ScaffoldOptions {
+    /// Replaces the stub body of the generated function, e.g. with a
+    /// challenge's reference solution.
+    pub solution_body: Option<String>,
+    /// Renames the function with `idiomatic_function_name`.
+    pub idiomatic_names: bool,
+    /// Also writes `.editorconfig` from `editorconfig_for`.
+    pub editorconfig: bool,
+    /// Also writes a benchmark harness that runs the solution on the largest
+    /// test input. Only Rust, Python, C and C++ have one.
+    pub with_bench: bool,
+    /// Recorded in `.codle.json`; non-zero for a bonus challenge.
+    pub challenge_index: usize,
+    /// Recorded in `.codle.json` instead of the current time, when
+    /// re-scaffolding a project that was started earlier.
+    pub initialized_at: Option<String>,
+}
+
+impl ScaffoldOptions {
+    /// The solution body with every line prefixed by `indent`, or `stub` if no
+    /// body was given.
+    fn body_or(&self, indent: &str, stub: &str) -> String {
+        match &self.solution_body {
+            Some(body) => body
+                .lines()
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        "\n".to_string()
+                    } else {
+                        format!("{}{}\n", indent, line)
+                    }
+                })
+                .collect(),
+            None => stub.to_string(),
+        }
+    }
+
+    fn initialized_at(&self) -> Option<String> {
+        Some(self.initialized_at.clone().unwrap_or_else(|| chrono::Local::now().to_rfc3339()))
+    }
+}
 
 #[derive(Debug)]
 pub struct TestSummary {
@@ -19,12 +74,90 @@ pub struct TestSummary {
     pub failed: usize,
     pub total: usize,
     pub output: String,
+    /// Plain-language explanation of a failure the raw output hides, e.g. a
+    /// crash that left no per-test results.
+    pub diagnosis: Option<String>,
+    /// The compiler's error messages when the solution or its tests didn't
+    /// build, so no test ran.
+    pub compile_error: Option<String>,
+}
+
+/// Prefix of the line every generated setup.sh echoes before each step.
+const SETUP_STEP_MARKER: &str = "[codle] step: ";
+
+/// The last step setup.sh announced in `output`. Scripts run with `set -e`,
+/// so after a failure this is the step that failed.
+pub fn last_setup_step(output: &str) -> Option<&str> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(SETUP_STEP_MARKER))
+        .next_back()
+}
+
+/// `command.output()`, but kills the process once `timeout` has passed.
+pub fn output_with_timeout(command: &mut Command, timeout: Option<Duration>) -> Result<Output, String> {
+    let Some(timeout) = timeout else {
+        return command.output().map_err(|e| e.to_string());
+    };
+
+    // In its own process group, a timeout can also kill whatever the command
+    // started, e.g. the test binary `make test` runs.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // Drain both pipes while waiting so a chatty process can't fill one and
+    // block before it exits.
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            #[cfg(unix)]
+            let _ = Command::new("kill")
+                .args(["-KILL", "--", &format!("-{}", child.id())])
+                .status();
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("timed out after {:?}", timeout));
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+        reader.and_then(|r| r.join().ok()).unwrap_or_default()
+    };
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
 }
 
 // --- Shared helpers ---
 
+/// Writes `setup.sh` with LF line endings regardless of platform or of what the
+/// templates contain: a `#!/bin/bash\r` shebang fails to exec, so the script
+/// must never be CRLF-converted.
 fn write_setup_script(output_dir: &Path, content: &str) -> Result<(), String> {
     let setup_path = output_dir.join("setup.sh");
+    let content = content.replace("\r\n", "\n").replace('\r', "\n");
     fs::write(&setup_path, content).map_err(|e| format!("Failed to write setup.sh: {}", e))?;
 
     #[cfg(unix)]
@@ -41,6 +174,54 @@ fn write_setup_script(output_dir: &Path, content: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Writes out the files that setup.sh in `dir` would write, without running
+/// any of its commands, so an archived scaffold opens in an editor as is.
+/// Only quoted heredocs are copied: an unquoted one expands a toolchain
+/// value (C#'s target framework), and an append to a file a tool would
+/// create is skipped. Rust also gets the Cargo.toml that `cargo init` makes.
+pub fn write_setup_files(dir: &Path, lang: Language, sig: &FunctionSignature) -> Result<(), String> {
+    let script = fs::read_to_string(dir.join("setup.sh"))
+        .map_err(|e| format!("Failed to read setup.sh: {}", e))?;
+    let write = |path: &Path, content: &str, append: bool| -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let result = if append {
+            fs::OpenOptions::new().append(true).open(path).and_then(|mut f| f.write_all(content.as_bytes()))
+        } else {
+            fs::write(path, content)
+        };
+        result.map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    };
+
+    if lang == Language::Rs {
+        write(&dir.join("Cargo.toml"), &rust::cargo_toml(sig), false)?;
+    }
+
+    let mut lines = script.lines();
+    while let Some(line) = lines.next() {
+        let Some((append, path, tag)) = heredoc_start(line) else {
+            continue;
+        };
+        let body: Vec<&str> = lines.by_ref().take_while(|l| *l != tag.trim_matches('\'')).collect();
+        let path = dir.join(path);
+        let quoted = tag.starts_with('\'');
+        if !quoted || (append && !path.exists()) {
+            continue;
+        }
+        write(&path, &format!("{}\n", body.join("\n")), append)?;
+    }
+    Ok(())
+}
+
+/// The `>>`, path and tag of a `cat > path << 'TAG'` line.
+fn heredoc_start(line: &str) -> Option<(bool, &str, &str)> {
+    match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["cat", op @ (">" | ">>"), path, "<<", tag] => Some((*op == ">>", *path, *tag)),
+        _ => None,
+    }
+}
+
 fn escape_for_heredoc(content: &str) -> String {
     content.to_string()
 }
@@ -61,6 +242,288 @@ fi"#,
     checks.join("\n")
 }
 
+/// Package name handed to `cargo init --name` and `gradle init --project-name`.
+/// Kept separate from the directory slug since both tools reject names that
+/// aren't identifier-like (leading digits, punctuation, reserved words).
+fn build_tool_name(name: &str) -> String {
+    const RESERVED: &[&str] = &[
+        "test", "core", "std", "alloc", "proc_macro", "self", "super", "crate", "fn", "mod",
+        "type", "use", "struct", "enum", "impl", "trait", "match", "loop", "while", "for",
+    ];
+
+    let mut sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let starts_with_digit = sanitized.chars().next().is_none_or(|c| c.is_ascii_digit());
+    if starts_with_digit || RESERVED.contains(&sanitized.as_str()) {
+        sanitized.insert_str(0, "codle_");
+    }
+    sanitized
+}
+
+/// Non-finite floats can't be written in JSON, so challenges spell them as the
+/// strings `"inf"`, `"-inf"` and `"nan"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpecialFloat {
+    Infinity,
+    NegInfinity,
+    NaN,
+}
+
+fn special_float(value: &Value) -> Option<SpecialFloat> {
+    match value.as_str()?.trim().to_ascii_lowercase().as_str() {
+        "inf" | "+inf" | "infinity" => Some(SpecialFloat::Infinity),
+        "-inf" | "-infinity" => Some(SpecialFloat::NegInfinity),
+        "nan" => Some(SpecialFloat::NaN),
+        _ => None,
+    }
+}
+
+/// NaN never compares equal to itself, so these assertions need an `is_nan`
+/// check instead of equality.
+fn expects_nan(ty: &RustType, expected: &Value) -> bool {
+    is_float(ty) && special_float(expected) == Some(SpecialFloat::NaN)
+}
+
+fn is_float(ty: &RustType) -> bool {
+    matches!(ty, RustType::F32 | RustType::F64)
+}
+
+/// Absolute tolerance for `Vec<f64>` results, which are compared element by
+/// element since computed floats rarely match the expected digits exactly.
+const FLOAT_EPSILON: &str = "1e-9";
+
+/// `f32` only carries about 7 significant digits, too few for `FLOAT_EPSILON`.
+const F32_EPSILON: &str = "1e-5";
+
+/// The tolerance for a float result of type `ty`, or for the elements of a
+/// float vector.
+fn float_epsilon(ty: &RustType) -> &'static str {
+    match ty {
+        RustType::F32 => F32_EPSILON,
+        RustType::Vec(inner) | RustType::Option(inner) => float_epsilon(inner),
+        _ => FLOAT_EPSILON,
+    }
+}
+
+/// `float_epsilon` written as a `float` literal (`1e-5f`) for `f32`, for
+/// Java and Kotlin assertions that won't mix `float` and `double`.
+fn typed_float_epsilon(ty: &RustType) -> String {
+    match ty {
+        RustType::Vec(inner) | RustType::Option(inner) => typed_float_epsilon(inner),
+        RustType::F32 => format!("{}f", F32_EPSILON),
+        _ => FLOAT_EPSILON.to_string(),
+    }
+}
+
+/// Whether a result of type `ty` is compared within `float_epsilon`: any
+/// finite float. Infinities still compare exactly, and NaN has its own check.
+fn compares_approximately(ty: &RustType, expected: &Value) -> bool {
+    is_float(ty) && special_float(expected).is_none()
+}
+
+fn is_float_vec(ty: &RustType) -> bool {
+    matches!(ty, RustType::Vec(inner) if is_float(inner))
+}
+
+/// Whether `test` compares its `ty` result as a multiset, ignoring order.
+fn compares_unordered(test: &TestCase, ty: &RustType) -> bool {
+    test.unordered && matches!(ty, RustType::Vec(_))
+}
+
+/// The distinct elements of an expected array, with how often each occurs,
+/// for backends that check an unordered result by counting.
+fn element_counts(expected: &Value) -> Vec<(&Value, usize)> {
+    let mut counts: Vec<(&Value, usize)> = Vec::new();
+    for item in expected.as_array().into_iter().flatten() {
+        match counts.iter_mut().find(|(value, _)| *value == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+    counts
+}
+
+/// Whether `ty` is or contains a type matching `pred`.
+fn contains_type(ty: &RustType, pred: &impl Fn(&RustType) -> bool) -> bool {
+    pred(ty)
+        || match ty {
+            RustType::Vec(inner)
+            | RustType::Option(inner)
+            | RustType::Ref(inner)
+            | RustType::MutRef(inner) => {
+                contains_type(inner, pred)
+            }
+            RustType::Map(key, value) => contains_type(key, pred) || contains_type(value, pred),
+            RustType::Tuple(elems) => elems.iter().any(|e| contains_type(e, pred)),
+            _ => false,
+        }
+}
+
+/// Whether any parameter or the return type of `sig` contains a type
+/// matching `pred`.
+fn uses_type(sig: &FunctionSignature, pred: impl Fn(&RustType) -> bool) -> bool {
+    sig.params.iter().any(|p| contains_type(&p.ty, &pred)) || contains_type(&sig.return_type, &pred)
+}
+
+/// Whether `sig` uses an integer type that C and C++ only name through
+/// `<stdint.h>`, such as `uint8_t`.
+fn uses_fixed_width_int(sig: &FunctionSignature) -> bool {
+    uses_type(sig, |ty| {
+        matches!(ty, RustType::I8 | RustType::I16 | RustType::U8 | RustType::U16 | RustType::U32)
+    })
+}
+
+fn uses_map(sig: &FunctionSignature) -> bool {
+    uses_type(sig, |ty| matches!(ty, RustType::Map(..)))
+}
+
+/// The entries of a `HashMap` value. JSON object keys are always strings, so
+/// each is read back as a `key_ty` value: `"3"` becomes `3` for an integer key.
+fn map_entries<'a>(value: &'a Value, key_ty: &RustType) -> Vec<(Value, &'a Value)> {
+    value
+        .as_object()
+        .map(|obj| obj.iter().map(|(k, v)| (map_key(k, key_ty), v)).collect())
+        .unwrap_or_default()
+}
+
+/// The items of a tuple value, a JSON array, each with its element type. A
+/// missing item is `null`.
+fn tuple_items<'a>(value: &Value, elems: &'a [RustType]) -> Vec<(Value, &'a RustType)> {
+    elems
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| (value.get(i).cloned().unwrap_or(Value::Null), ty))
+        .collect()
+}
+
+fn map_key(key: &str, key_ty: &RustType) -> Value {
+    match key_ty {
+        RustType::String | RustType::Char => Value::String(key.to_string()),
+        RustType::Bool => Value::Bool(key == "true"),
+        _ => key.trim().parse::<serde_json::Number>().map(Value::Number).unwrap_or(Value::Null),
+    }
+}
+
+/// `s` with backslashes, `quote`, and newline, tab and carriage return
+/// written as the backslash escapes that Rust, Java, Kotlin, C, C++ and Python
+/// all share. Other control characters get `lang`'s numeric escape.
+fn escape_literal(s: &str, quote: char, lang: Language) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c if c == quote => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_ascii_control() => escaped.push_str(&match lang {
+                // `\x` would also take in any hex digits that follow.
+                Language::C | Language::Cpp => format!("\\{:03o}", c as u32),
+                Language::Java | Language::Kt | Language::Cs => format!("\\u{:04x}", c as u32),
+                _ => format!("\\x{:02x}", c as u32),
+            }),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A double-quoted string literal in the syntax shared by Rust, Java, C, C++,
+/// C#, Python and JavaScript. Kotlin also needs `$` escaped.
+fn string_literal(value: &Value, lang: Language) -> String {
+    format!("\"{}\"", escape_literal(value.as_str().unwrap_or(""), '"', lang))
+}
+
+/// A single-quoted char literal in the C-family syntax shared by Rust, Java,
+/// Kotlin, C, C++, C# and Go.
+fn char_literal(value: &Value, lang: Language) -> String {
+    let c = value.as_str().and_then(|s| s.chars().next()).unwrap_or('?');
+    format!("'{}'", escape_literal(&c.to_string(), '\'', lang))
+}
+
+/// The digits of a 64-bit integer. A `u64` above `i64::MAX` only reads back
+/// through `as_u64`.
+fn integer_literal(value: &Value) -> String {
+    value
+        .as_i64()
+        .map(|n| n.to_string())
+        .or_else(|| value.as_u64().map(|n| n.to_string()))
+        .unwrap_or_else(|| "0".to_string())
+}
+
+/// A float literal every backend accepts. Rust's `Display` never uses an
+/// exponent, so `1e-30` would otherwise come out as 30 zeros and `-1e300` as
+/// 301 digits; those magnitudes use exponent form (`1e-30`), which Java,
+/// Kotlin, C, C++ and Python all read as a double.
+fn format_float(n: f64) -> String {
+    let magnitude = n.abs();
+    if magnitude != 0.0 && !(1e-5..1e16).contains(&magnitude) {
+        format!("{:e}", n)
+    } else if n.fract() == 0.0 {
+        format!("{:.1}", n)
+    } else {
+        format!("{}", n)
+    }
+}
+
+/// Suffix for `test_<suffix>` functions: the case name, or its 1-based number.
+fn test_fn_suffix(test: &TestCase, index: usize) -> String {
+    test.identifier().unwrap_or_else(|| (index + 1).to_string())
+}
+
+/// Label for PASS/FAIL lines in the C-style harnesses, e.g. `3 (empty_input)`.
+fn test_case_label(test: &TestCase, index: usize) -> String {
+    match test.identifier() {
+        Some(name) => format!("{} ({})", index + 1, name),
+        None => (index + 1).to_string(),
+    }
+}
+
+/// `value` as the C-style harnesses print it on a failed test: JSON-like,
+/// with `, ` between elements, quoted strings and chars, and `null` for a
+/// missing option.
+fn shown_value(value: &Value, ty: &RustType) -> String {
+    match ty {
+        RustType::Vec(inner) => {
+            let items: Vec<String> = value
+                .as_array()
+                .map(|arr| arr.iter().map(|v| shown_value(v, inner)).collect())
+                .unwrap_or_default();
+            format!("[{}]", items.join(", "))
+        }
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("{}: {}", shown_value(&k, key), shown_value(v, inner)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        RustType::Option(_) if value.is_null() => "null".to_string(),
+        RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => shown_value(value, inner),
+        RustType::String | RustType::Str | RustType::Char => format!("\"{}\"", value.as_str().unwrap_or("")),
+        RustType::F32 | RustType::F64 => match special_float(value) {
+            Some(SpecialFloat::Infinity) => "inf".to_string(),
+            Some(SpecialFloat::NegInfinity) => "-inf".to_string(),
+            Some(SpecialFloat::NaN) => "nan".to_string(),
+            None => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
 fn has_mut_ref_params(sig: &FunctionSignature) -> bool {
     sig.params.iter().any(|p| matches!(&p.ty, RustType::MutRef(_)))
 }
@@ -73,18 +536,197 @@ fn get_first_test_inputs(challenge: &Challenge) -> Option<&serde_json::Map<Strin
     challenge.tests.first().and_then(|t| t.input.as_object())
 }
 
-fn unwrap_mut_ref(ty: &RustType) -> &RustType {
+/// Inputs of the test case with the most input data, which makes the most
+/// useful benchmark. Size is measured by the length of the serialized JSON.
+fn largest_test_inputs(challenge: &Challenge) -> Option<&serde_json::Map<String, Value>> {
+    challenge
+        .tests
+        .iter()
+        .max_by_key(|t| t.input.to_string().len())
+        .and_then(|t| t.input.as_object())
+}
+
+/// The type behind a `&T` or `&mut T` parameter, which is how most backends
+/// declare its value.
+fn unwrap_ref(ty: &RustType) -> &RustType {
     match ty {
-        RustType::MutRef(inner) => inner,
+        RustType::Ref(inner) | RustType::MutRef(inner) => inner,
         other => other,
     }
 }
 
+/// `sig` with shared `&T` parameters taken by value. Only Rust and C++ have a
+/// borrow to declare; elsewhere the backends see a plain `T`. Outside Rust, a
+/// `&str` is a borrowed `String`.
+fn by_value_for(sig: &FunctionSignature, lang: Language) -> FunctionSignature {
+    let mut sig = sig.clone();
+    if lang != Language::Rs {
+        for param in &mut sig.params {
+            param.ty = owned_strings(&param.ty);
+        }
+    }
+    if !matches!(lang, Language::Rs | Language::Cpp) {
+        for param in &mut sig.params {
+            if let RustType::Ref(inner) = &param.ty {
+                param.ty = (**inner).clone();
+            }
+        }
+    }
+    sig
+}
+
+/// `ty` with every `str` replaced by `String`.
+fn owned_strings(ty: &RustType) -> RustType {
+    let owned = |inner: &RustType| Box::new(owned_strings(inner));
+    match ty {
+        RustType::Str => RustType::String,
+        RustType::Vec(inner) => RustType::Vec(owned(inner)),
+        RustType::Option(inner) => RustType::Option(owned(inner)),
+        RustType::Map(key, value) => RustType::Map(owned(key), owned(value)),
+        RustType::Ref(inner) => RustType::Ref(owned(inner)),
+        RustType::MutRef(inner) => RustType::MutRef(owned(inner)),
+        RustType::Tuple(elems) => RustType::Tuple(elems.iter().map(owned_strings).collect()),
+        other => other.clone(),
+    }
+}
+
+/// The `&mut` parameters a test checks, each with `test` narrowed to that
+/// parameter's expected value, so it's asserted on like a return value.
+fn mut_ref_outputs<'a>(sig: &'a FunctionSignature, test: &TestCase) -> Vec<(&'a Param, TestCase)> {
+    crate::models::expected_outputs(sig, &test.expected)
+        .into_iter()
+        .map(|(p, expected)| (p, TestCase { expected: expected.clone(), ..test.clone() }))
+        .collect()
+}
+
+/// The types the tests compare: the return type, or each `&mut` parameter's
+/// for a void function.
+fn checked_types(sig: &FunctionSignature) -> Vec<&RustType> {
+    if is_void_with_mut_ref(sig) {
+        sig.params
+            .iter()
+            .filter(|p| matches!(&p.ty, RustType::MutRef(_)))
+            .map(|p| unwrap_ref(&p.ty))
+            .collect()
+    } else {
+        vec![&sig.return_type]
+    }
+}
+
+/// `code`, lines indented by `indent` plus `unit`, in a block opened by
+/// `open` at `indent`, so several outputs' checks can each declare the same
+/// variables.
+fn scoped(code: &str, indent: &str, unit: &str, open: &str) -> String {
+    let body: String = code
+        .lines()
+        .map(|line| if line.is_empty() { "\n".to_string() } else { format!("{}{}\n", unit, line) })
+        .collect();
+    format!("{indent}{open}\n{body}{indent}}}\n", indent = indent, open = open, body = body)
+}
+
 fn get_first_mut_ref_inner_type(sig: &FunctionSignature) -> Option<&RustType> {
     sig.params
         .iter()
         .find(|p| matches!(&p.ty, RustType::MutRef(_)))
-        .map(|p| unwrap_mut_ref(&p.ty))
+        .map(|p| unwrap_ref(&p.ty))
+}
+
+/// The first type in `sig` that `lang` can't express, if any.
+fn unsupported_type(sig: &FunctionSignature, lang: Language) -> Option<&RustType> {
+    sig.params
+        .iter()
+        .map(|p| &p.ty)
+        .find(|ty| !lang.supports_param(&owned_strings(ty)))
+        .or_else(|| Some(&sig.return_type).filter(|ty| !lang.supports(ty)))
+}
+
+pub fn check_language_support(sig: &FunctionSignature, lang: Language) -> Result<(), String> {
+    let Some(ty) = unsupported_type(sig, lang) else {
+        return Ok(());
+    };
+
+    let alternatives: Vec<&str> = Language::all()
+        .iter()
+        .filter(|other| unsupported_type(sig, **other).is_none())
+        .map(|other| other.display_name())
+        .collect();
+
+    let mut message = format!(
+        "The {} backend doesn't support the type '{}'.",
+        lang.display_name(),
+        rust::translate_type_rs(ty)
+    );
+    if !alternatives.is_empty() {
+        message.push_str(&format!(" Try one of: {}", alternatives.join(", ")));
+    }
+    Err(message)
+}
+
+/// The snake_case challenge function name in `lang`'s own convention: camelCase
+/// for Java, Kotlin, Go, JavaScript and TypeScript, PascalCase for C#, unchanged elsewhere.
+pub fn idiomatic_function_name(name: &str, lang: Language) -> String {
+    match lang {
+        Language::Cs => {
+            let camel = idiomatic_function_name(name, Language::Java);
+            let mut chars = camel.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        }
+        Language::Java | Language::Kt | Language::Go | Language::Js | Language::Ts => {
+            let mut parts = name.split('_').filter(|part| !part.is_empty());
+            let mut camel = parts.next().unwrap_or_default().to_string();
+            for part in parts {
+                let mut chars = part.chars();
+                if let Some(first) = chars.next() {
+                    camel.extend(first.to_uppercase());
+                    camel.push_str(chars.as_str());
+                }
+            }
+            camel
+        }
+        _ => name.to_string(),
+    }
+}
+
+macro_rules! editorconfig {
+    ($sections:literal) => {
+        concat!(
+            "root = true\n\n",
+            "[*]\n",
+            "charset = utf-8\n",
+            "end_of_line = lf\n",
+            "insert_final_newline = true\n",
+            "trim_trailing_whitespace = true\n\n",
+            "[*.{json,sh}]\n",
+            "indent_style = space\n",
+            "indent_size = 2\n\n",
+            $sections
+        )
+    };
+}
+
+/// `.editorconfig` matching the indentation of the files each backend writes.
+pub fn editorconfig_for(lang: Language) -> &'static str {
+    match lang {
+        Language::Rs => editorconfig!("[*.{rs,toml}]\nindent_style = space\nindent_size = 4\n"),
+        Language::Py => editorconfig!("[*.py]\nindent_style = space\nindent_size = 4\n"),
+        Language::Kt => editorconfig!("[*.{kt,kts,gradle}]\nindent_style = space\nindent_size = 4\n"),
+        Language::Java => editorconfig!("[*.{java,gradle}]\nindent_style = space\nindent_size = 4\n"),
+        Language::C => editorconfig!(
+            "[*.{c,h}]\nindent_style = space\nindent_size = 4\n\n[Makefile]\nindent_style = tab\n"
+        ),
+        Language::Cpp => editorconfig!(
+            "[*.{cpp,hpp}]\nindent_style = space\nindent_size = 4\n\n[Makefile]\nindent_style = tab\n"
+        ),
+        Language::Fortran => editorconfig!(
+            "[*.f90]\nindent_style = space\nindent_size = 2\n\n[Makefile]\nindent_style = tab\n"
+        ),
+        Language::Go => editorconfig!("[*.go]\nindent_style = tab\nindent_size = 4\n"),
+        Language::Js => editorconfig!("[*.js]\nindent_style = space\nindent_size = 4\n"),
+        Language::Ts => editorconfig!("[*.ts]\nindent_style = space\nindent_size = 4\n"),
+        Language::Cs => editorconfig!(
+            "[*.cs]\nindent_style = space\nindent_size = 4\n\n[*.csproj]\nindent_style = space\nindent_size = 2\n"
+        ),
+    }
 }
 
 // --- Dispatch functions ---
@@ -97,6 +739,11 @@ pub fn translate_type(ty: &RustType, lang: Language) -> String {
         Language::Java => java::translate_type_java(ty),
         Language::C => c::translate_type_c(ty),
         Language::Cpp => cpp::translate_type_cpp(ty),
+        Language::Fortran => fortran::translate_type_fortran(ty),
+        Language::Go => go::translate_type_go(ty),
+        Language::Js => javascript::translate_type_js(ty),
+        Language::Cs => csharp::translate_type_csharp(ty),
+        Language::Ts => javascript::translate_type_js(ty),
     }
 }
 
@@ -108,6 +755,10 @@ pub fn render_value(value: &Value, ty: &RustType, lang: Language) -> String {
         Language::Java => java::render_value_java(value, ty),
         Language::C => c::render_value_c(value, ty),
         Language::Cpp => cpp::render_value_cpp(value, ty),
+        Language::Fortran => fortran::render_value_fortran(value, ty),
+        Language::Go => go::render_value_go(value, ty),
+        Language::Js | Language::Ts => javascript::render_value_js(value, ty),
+        Language::Cs => csharp::render_value_csharp(value, ty),
     }
 }
 
@@ -117,39 +768,304 @@ pub fn generate_scaffold(
     lang: Language,
     difficulty: Difficulty,
     output_dir: &Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
+    // Every generator looks up each parameter in a test's input, so a missing
+    // one would call the function with too few arguments.
+    validate_challenge(challenge, sig)?;
+    let mut sig = by_value_for(sig, lang);
+    if options.idiomatic_names {
+        sig.name = idiomatic_function_name(&sig.name, lang);
+    }
+    let sig = &sig;
+
+    if options.with_bench && !matches!(lang, Language::Rs | Language::Py | Language::C | Language::Cpp) {
+        return Err(format!("No benchmark harness is available for {}", lang.display_name()));
+    }
+
+    if options.editorconfig {
+        fs::write(output_dir.join(".editorconfig"), editorconfig_for(lang))
+            .map_err(|e| format!("Failed to write .editorconfig: {}", e))?;
+    }
+
     match lang {
-        Language::Rs => rust::generate_rust(challenge, sig, difficulty, output_dir),
-        Language::Py => python::generate_python(challenge, sig, difficulty, output_dir),
-        Language::Kt => kotlin::generate_kotlin(challenge, sig, difficulty, output_dir),
-        Language::Java => java::generate_java(challenge, sig, difficulty, output_dir),
-        Language::C => c::generate_c(challenge, sig, difficulty, output_dir),
-        Language::Cpp => cpp::generate_cpp(challenge, sig, difficulty, output_dir),
+        Language::Rs => rust::generate_rust(challenge, sig, difficulty, output_dir, options),
+        Language::Py => python::generate_python(challenge, sig, difficulty, output_dir, options),
+        Language::Kt => kotlin::generate_kotlin(challenge, sig, difficulty, output_dir, options),
+        Language::Java => java::generate_java(challenge, sig, difficulty, output_dir, options),
+        Language::C => c::generate_c(challenge, sig, difficulty, output_dir, options),
+        Language::Cpp => cpp::generate_cpp(challenge, sig, difficulty, output_dir, options),
+        Language::Fortran => {
+            fortran::generate_fortran(challenge, sig, difficulty, output_dir, options)
+        }
+        Language::Go => go::generate_go(challenge, sig, difficulty, output_dir, options),
+        Language::Js => {
+            javascript::generate_javascript(challenge, sig, difficulty, output_dir, options)
+        }
+        Language::Cs => csharp::generate_csharp(challenge, sig, difficulty, output_dir, options),
+        Language::Ts => {
+            typescript::generate_typescript(challenge, sig, difficulty, output_dir, options)
+        }
     }
 }
 
-pub fn run_tests(lang: Language) -> Result<TestSummary, String> {
-    let (cmd, args) = lang.test_command();
+/// The test file (for Rust, the test module) the generator for `lang` writes.
+fn generate_tests(challenge: &Challenge, sig: &FunctionSignature, lang: Language) -> String {
+    let sig = &by_value_for(sig, lang);
+    match lang {
+        Language::Rs => rust::generate_rust_tests(sig, &challenge.tests),
+        Language::Py => python::generate_python_tests(sig, &challenge.tests, challenge.chars_as_string),
+        Language::Kt => kotlin::generate_kotlin_tests(sig, &challenge.tests),
+        Language::Java => java::generate_java_tests(sig, &challenge.tests),
+        Language::C => c::generate_c_tests(sig, &challenge.tests, challenge.c_string_len),
+        Language::Cpp => cpp::generate_cpp_tests(sig, &challenge.tests),
+        Language::Fortran => fortran::generate_fortran_tests(sig, &challenge.tests),
+        Language::Go => go::generate_go_tests(sig, &challenge.tests),
+        Language::Js => javascript::generate_javascript_tests(sig, &challenge.tests),
+        Language::Cs => csharp::generate_csharp_tests(sig, &challenge.tests),
+        Language::Ts => typescript::generate_typescript_tests(sig, &challenge.tests),
+    }
+}
+
+/// Rewrites the generated tests of an existing project from `challenge`,
+/// leaving the solution alone. Rust's tests share `src/main.rs` with the
+/// solution, so only its trailing `#[cfg(test)]` module is replaced.
+pub fn regenerate_tests(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    lang: Language,
+    project_dir: &Path,
+) -> Result<(), String> {
+    validate_challenge(challenge, sig)?;
+    let tests = generate_tests(challenge, sig, lang);
+    let path = project_dir.join(lang.test_filename());
+
+    // setup.sh's heredocs end every file with a newline.
+    let content = if lang == Language::Rs {
+        let main_rs = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let solution = match main_rs.find("\n#[cfg(test)]\nmod tests {") {
+            Some(start) => main_rs[..start].to_string(),
+            None => format!("{}\n", main_rs.trim_end()),
+        };
+        format!("{}{}\n", solution, tests)
+    } else {
+        format!("{}\n", tests)
+    };
 
-    let output = Command::new(cmd)
-        .args(args)
-        .output()
-        .map_err(|e| format!("Failed to run {}: {}", cmd, e))?;
+    if lang == Language::C {
+        if let Some(data) = c::generate_c_test_data(&by_value_for(sig, lang), &challenge.tests) {
+            let header = project_dir.join(c::DATA_HEADER);
+            fs::write(&header, format!("{}\n", data))
+                .map_err(|e| format!("Failed to write {}: {}", header.display(), e))?;
+        }
+    }
+
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Builds the tests, then runs the language's test command inside
+/// `project_dir`, killing it after `timeout`. A failed build is reported the
+/// way the test command would have reported it.
+pub fn run_tests(lang: Language, project_dir: &Path, timeout: Option<Duration>) -> Result<TestSummary, String> {
+    if let Some((cmd, args)) = lang.build_command() {
+        let build = Command::new(cmd)
+            .args(args)
+            .current_dir(project_dir)
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", cmd, e))?;
+        if !build.status.success() {
+            let stdout = String::from_utf8_lossy(&build.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&build.stderr).to_string();
+            return parse_test_output(lang, &stdout, &stderr);
+        }
+    }
+
+    let (cmd, args) = lang.test_command();
+    let output = match output_with_timeout(Command::new(cmd).args(args).current_dir(project_dir), timeout) {
+        Ok(output) => output,
+        Err(e) if timeout.is_some() && e.starts_with("timed out") => {
+            stop_daemons(lang, project_dir);
+            return Err(format!("Tests {}", e));
+        }
+        Err(e) => return Err(format!("Failed to run {}: {}", cmd, e)),
+    };
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    parse_test_output(lang, &stdout, &stderr)
+}
+
+/// Gradle runs the tests in a daemon outside the process group a timeout
+/// kills, so a hung test would keep spinning there; `--stop` shuts it down.
+/// `dotnet` leaves build servers behind the same way.
+fn stop_daemons(lang: Language, project_dir: &Path) {
+    let (cmd, args): (&str, &[&str]) = match lang {
+        Language::Kt | Language::Java => ("./gradlew", &["--stop"]),
+        Language::Cs => ("dotnet", &["build-server", "shutdown"]),
+        _ => return,
+    };
+    let _ = Command::new(cmd).args(args).current_dir(project_dir).stdin(Stdio::null()).output();
+}
+
+fn parse_test_output(lang: Language, stdout: &str, stderr: &str) -> Result<TestSummary, String> {
     let combined = format!("{}\n{}", stdout, stderr);
 
-    match lang {
-        Language::Rs => rust::parse_rust_output(&stdout, &stderr, &combined),
-        Language::Py => python::parse_pytest_output(&stdout, &stderr, &combined),
-        Language::Kt | Language::Java => parse_gradle_output(&stdout, &stderr, &combined),
-        Language::C | Language::Cpp => c::parse_c_output(&stdout, &stderr, &combined),
+    let mut summary = match lang {
+        Language::Rs => rust::parse_rust_output(stdout, stderr, &combined),
+        Language::Py => python::parse_pytest_output(stdout, stderr, &combined),
+        Language::Kt | Language::Java => parse_gradle_output(stdout, stderr, &combined),
+        Language::C | Language::Cpp | Language::Fortran => {
+            c::parse_c_output(stdout, stderr, &combined)
+        }
+        Language::Go => go::parse_go_output(stdout, stderr, &combined),
+        Language::Js | Language::Ts => javascript::parse_jest_output(stdout, stderr, &combined),
+        Language::Cs => csharp::parse_dotnet_output(stdout, stderr, &combined),
+    }?;
+    summary.diagnosis = detect_crash(&combined).or_else(|| match lang {
+        Language::C | Language::Cpp | Language::Fortran => c::diagnose_make_failure(&combined),
+        _ => None,
+    });
+    if summary.total == 0 {
+        summary.compile_error = detect_compile_error(lang, &combined);
+    }
+    Ok(summary)
+}
+
+/// The tests `lang`'s runner reported as failing in `output`, labelled the
+/// way the scaffold names them: the test number or the case's name, e.g.
+/// `3` or `empty_input`. C-style harnesses print both, as in `3 (empty_input)`.
+pub fn failed_tests(lang: Language, output: &str) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        let label = match lang {
+            // test tests::test_3 ... FAILED
+            Language::Rs => line
+                .strip_suffix(" ... FAILED")
+                .and_then(|name| name.rsplit("::").next())
+                .and_then(|name| name.strip_prefix("test_")),
+            // test_solution.py::test_3 FAILED, or FAILED test_solution.py::test_3 - ...
+            Language::Py => {
+                let mut words = line.split_whitespace();
+                match (words.next(), words.next()) {
+                    (Some("FAILED"), Some(id)) | (Some(id), Some("FAILED")) => id.split("::").nth(1),
+                    _ => None,
+                }
+                .and_then(|name| name.strip_prefix("test_"))
+            }
+            // AppTest > test3() FAILED
+            Language::Kt | Language::Java => line
+                .strip_suffix("() FAILED")
+                .and_then(|rest| rest.rsplit("> ").next())
+                .and_then(|name| name.strip_prefix("test")),
+            // Test 3 (empty_input): FAIL (expected 1, got 2)
+            Language::C | Language::Cpp | Language::Fortran => line
+                .strip_prefix("Test ")
+                .and_then(|rest| rest.split_once(": FAIL"))
+                .map(|(label, _)| label),
+            // --- FAIL: Test3 (0.00s)
+            Language::Go => line
+                .strip_prefix("--- FAIL: Test")
+                .and_then(|rest| rest.split_whitespace().next()),
+            // ✕ test_3 (3 ms)
+            Language::Js | Language::Ts => line
+                .strip_prefix("\u{2715} test_")
+                .and_then(|rest| rest.split(" (").next()),
+            // Failed SolutionTests.Test3 [4 ms]
+            Language::Cs => line
+                .strip_prefix("Failed SolutionTests.Test")
+                .and_then(|rest| rest.split(" [").next()),
+        };
+        if let Some(label) = label.map(|l| l.trim_start_matches('_')).filter(|l| !l.is_empty()) {
+            if !labels.iter().any(|known| known == label) {
+                labels.push(label.to_string());
+            }
+        }
+    }
+    labels
+}
+
+/// At most this many lines of compiler output end up in `compile_error`.
+const MAX_COMPILE_ERROR_LINES: usize = 20;
+
+/// The error messages in `output` from `lang`'s compiler, each with the
+/// indented source excerpt that follows it. `None` if nothing looks like a
+/// build failure.
+fn detect_compile_error(lang: Language, output: &str) -> Option<String> {
+    let starts_error = |line: &str| match lang {
+        Language::Rs => {
+            line.starts_with("error[E")
+                || (line.starts_with("error: ")
+                    && !line.starts_with("error: could not compile")
+                    && !line.starts_with("error: test failed"))
+        }
+        Language::C | Language::Cpp => line.contains(": error: ") || line.contains(": fatal error: "),
+        Language::Fortran => line.starts_with("Error: ") || line.starts_with("Fatal Error: "),
+        Language::Kt | Language::Java => {
+            output.contains("BUILD FAILED") && (line.starts_with("e: ") || line.contains(": error: "))
+        }
+        // Failing tests log indented `file.go:N:` lines; the compiler's aren't.
+        Language::Go => line.contains(".go:") && !line.starts_with(char::is_whitespace),
+        Language::Py => line.trim_start().starts_with("E ") && line.contains("Error"),
+        Language::Js => line.contains("SyntaxError") || line.contains("Cannot find module"),
+        // tsc's `solution.ts(3,5): error TS2322: ...`, which ts-jest also prints.
+        Language::Ts => line.contains("error TS") || line.contains("Cannot find module"),
+        Language::Cs => line.contains(": error CS"),
+    };
+    // Source excerpts are indented or start with a gutter like `4 |`.
+    let continues_error = |line: &str| {
+        line.starts_with(char::is_whitespace)
+            || line.split_once(" |").is_some_and(|(gutter, _)| gutter.trim().parse::<usize>().is_ok())
+    };
+
+    let mut lines = Vec::new();
+    let mut in_error = false;
+    for line in output.lines() {
+        if starts_error(line) {
+            in_error = true;
+        } else if !(in_error && !line.trim().is_empty() && continues_error(line)) {
+            in_error = false;
+        }
+        if in_error {
+            lines.push(line.trim_end());
+        }
     }
+    if lines.is_empty() {
+        return None;
+    }
+    if lines.len() > MAX_COMPILE_ERROR_LINES {
+        lines.truncate(MAX_COMPILE_ERROR_LINES);
+        lines.push("...");
+    }
+    Some(lines.join("\n"))
 }
 
 // --- Shared output parsers ---
 
+/// Output fragments left behind when a solution blows the stack: Rust's abort
+/// message, Python's, Java's and Node's recursion errors, Go's stack limit
+/// panic, .NET's stack overflow, and a segfaulting C/C++/Fortran test runner as reported by the shell
+/// or make.
+const CRASH_SIGNATURES: &[&str] = &[
+    "has overflowed its stack",
+    "RecursionError",
+    "StackOverflowError",
+    "Maximum call stack size exceeded",
+    "goroutine stack exceeds",
+    "Stack overflow.",
+    "Segmentation fault",
+    "SIGSEGV",
+    "Error 139",
+];
+
+fn detect_crash(output: &str) -> Option<String> {
+    CRASH_SIGNATURES
+        .iter()
+        .any(|signature| output.contains(signature))
+        .then(|| "Your solution crashed (likely stack overflow/recursion).".to_string())
+}
+
 fn parse_gradle_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<TestSummary, String> {
     let mut passed = 0;
     let mut failed = 0;
@@ -195,5 +1111,1869 @@ fn parse_gradle_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<T
         failed,
         total,
         output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::models::{parse_signature, TestCase};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codle-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn fixture_challenge(signature: &str, tests: Value) -> Challenge {
+        serde_json::from_value(json!({
+            "name": "Fixture",
+            "difficulty": 1,
+            "short_description": "",
+            "description": "",
+            "function_signature": signature,
+            "tests": tests,
+        }))
+        .unwrap()
+    }
+
+    fn scaffold_setup_script(name: &str, challenge: &Challenge, lang: Language) -> String {
+        let dir = scratch_dir(name);
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        generate_scaffold(challenge, &sig, lang, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+            .unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        script
+    }
+
+    fn float_case(expected: Value) -> (FunctionSignature, Vec<TestCase>) {
+        let sig = parse_signature("fn ratio(a: i32, b: i32) -> f64").unwrap();
+        let tests = vec![TestCase {
+            name: None,
+            input: json!({"a": 1, "b": 0}),
+            expected,
+            unordered: false,
+        }];
+        (sig, tests)
+    }
+
+    #[test]
+    fn test_infinity_expected() {
+        let (sig, tests) = float_case(json!("inf"));
+        assert!(rust::generate_rust_tests(&sig, &tests)
+            .contains("assert_eq!(result, f64::INFINITY);"));
+        assert!(python::generate_python_tests(&sig, &tests, false)
+            .contains("assert result == float(\"inf\")"));
+        assert!(c::generate_c_tests(&sig, &tests, false).contains("if (result == INFINITY)"));
+        assert!(java::generate_java_tests(&sig, &tests)
+            .contains("assertEquals(Double.POSITIVE_INFINITY, result);"));
+        assert_eq!(
+            render_value(&json!("-inf"), &RustType::F64, Language::Cpp),
+            "-std::numeric_limits<double>::infinity()"
+        );
+    }
+
+    #[test]
+    fn test_nan_expected_uses_is_nan() {
+        let (sig, tests) = float_case(json!("nan"));
+        assert!(rust::generate_rust_tests(&sig, &tests).contains("assert!(result.is_nan());"));
+
+        let python = python::generate_python_tests(&sig, &tests, false);
+        assert!(python.starts_with("import math\n"));
+        assert!(python.contains("assert math.isnan(result)"));
+
+        assert!(c::generate_c_tests(&sig, &tests, false).contains("if (isnan(result))"));
+        assert!(cpp::generate_cpp_tests(&sig, &tests).contains("if (std::isnan(result))"));
+        assert!(java::generate_java_tests(&sig, &tests)
+            .contains("assertTrue(Double.isNaN(result), \"expected: NaN but was: \" + result);"));
+        assert!(kotlin::generate_kotlin_tests(&sig, &tests).contains("assertTrue(result.isNaN(), \"expected: NaN but was: $result\")"));
+    }
+
+    #[test]
+    fn test_c_empty_array_param() {
+        let challenge = fixture_challenge(
+            "fn running_sum(nums: Vec<i32>) -> Vec<i32>",
+            json!([{"input": {"nums": []}, "expected": []}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let tests = c::generate_c_tests(&sig, &challenge.tests, false);
+        assert!(tests.contains("int nums_arr[] = {0};\n        int nums_len = 0;\n"));
+        assert!(!tests.contains("= {};"));
+
+        if !tools_installed("c_empty_array_param", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-empty-array");
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+            .unwrap();
+        let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let build = Command::new("gcc")
+            .args(["-std=c11", "-pedantic", "-Werror", "-DCODLE_TEST", "-o", "test_runner"])
+            .args(["solution.c", "test_solution.c"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let run = Command::new("./test_runner").current_dir(&dir).output();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(setup.status.success());
+        assert!(build.status.success(), "{}", String::from_utf8_lossy(&build.stderr));
+        assert!(String::from_utf8_lossy(&run.unwrap().stdout).contains("Test 1: PASS"));
+    }
+
+    #[test]
+    fn test_idiomatic_names_for_java() {
+        assert_eq!(idiomatic_function_name("two_sum", Language::Java), "twoSum");
+        assert_eq!(idiomatic_function_name("find_median_sorted_arrays", Language::Kt), "findMedianSortedArrays");
+        assert_eq!(idiomatic_function_name("two_sum", Language::Cpp), "two_sum");
+
+        let challenge = fixture_challenge(
+            "fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>",
+            json!([{"input": {"nums": [2, 7], "target": 9}, "expected": [0, 1]}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let dir = scratch_dir("idiomatic-java");
+        let options = ScaffoldOptions {
+            idiomatic_names: true,
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::Java, Difficulty::Easy, &dir, &options).unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(script.contains("public static int[] twoSum(int[] nums, int target)"));
+        assert!(script.contains("int[] result = App.twoSum(nums, target);"));
+        assert!(!script.contains("two_sum("));
+    }
+
+    #[test]
+    fn test_python_chars_as_string() {
+        let sig = parse_signature("fn reverse_string(s: &mut Vec<char>)").unwrap();
+        let tests = vec![TestCase {
+            name: None,
+            input: json!({"s": ["h", "i"]}),
+            expected: json!(["i", "h"]),
+            unordered: false,
+        }];
+
+        let strict = python::generate_python_tests(&sig, &tests, false);
+        assert!(strict.contains("assert s == [\"i\", \"h\"]"));
+
+        let code = python::generate_python_tests(&sig, &tests, true);
+        assert!(code.contains("    result = reverse_string(s)\n"));
+        assert!(code.contains("    assert \"\".join(actual) == \"ih\"\n"));
+
+        if !tools_installed("python_chars_as_string", &["python3"]) {
+            return;
+        }
+        let dir = scratch_dir("python-chars-as-string");
+        fs::write(dir.join("solution.py"), "def reverse_string(s):\n    return \"\".join(reversed(s))\n").unwrap();
+        fs::write(dir.join("test_solution.py"), &code).unwrap();
+        let run = Command::new("python3")
+            .args(["-c", "import test_solution as t; t.test_1(); print('ok')"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(String::from_utf8_lossy(&run.stdout).trim(), "ok");
+    }
+
+    #[test]
+    fn test_editorconfig_indent_per_language() {
+        let python = editorconfig_for(Language::Py);
+        assert!(python.contains("[*.py]\nindent_style = space\nindent_size = 4\n"));
+
+        let fortran = editorconfig_for(Language::Fortran);
+        assert!(fortran.contains("[*.f90]\nindent_style = space\nindent_size = 2\n"));
+        assert!(fortran.contains("[Makefile]\nindent_style = tab\n"));
+
+        let challenge = fixture_challenge(
+            "fn add(a: i32, b: i32) -> i32",
+            json!([{"input": {"a": 1, "b": 2}, "expected": 3}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let dir = scratch_dir("editorconfig");
+        let options = ScaffoldOptions {
+            editorconfig: true,
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::Py, Difficulty::Easy, &dir, &options).unwrap();
+        let written = fs::read_to_string(dir.join(".editorconfig")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(written, python);
+    }
+
+    /// A leftover `src/` or a second run of setup.sh used to make `cargo init`
+    /// fail with "`cargo init` cannot be run on existing Cargo packages".
+    #[test]
+    fn test_rust_setup_tolerates_existing_package() {
+        let challenge = fixture_challenge(
+            "fn add(a: i32, b: i32) -> i32",
+            json!([{"input": {"a": 1, "b": 2}, "expected": 3}]),
+        );
+        let script = scaffold_setup_script("rust-existing-check", &challenge, Language::Rs);
+        assert!(script.contains(
+            "if [ ! -f Cargo.toml ]; then\n    cargo init --vcs none --name \"add\"\nfi\nmkdir -p src\n"
+        ));
+
+        if !tools_installed("rust_setup_tolerates_existing_package", Language::Rs.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("rust-existing-package");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        generate_scaffold(&challenge, &sig, Language::Rs, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+            .unwrap();
+        let first = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let second = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let main_rs = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+        let has_git = dir.join(".git").exists();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(first.status.success(), "{}", String::from_utf8_lossy(&first.stderr));
+        assert!(second.status.success(), "{}", String::from_utf8_lossy(&second.stderr));
+        assert!(main_rs.contains("fn add(a: i32, b: i32) -> i32"));
+        assert!(!has_git);
+    }
+
+    fn board_challenge() -> Challenge {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges/medium/surrounded_regions.json");
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Pulls the file written by `cat > <path> << '<tag>'` out of a setup.sh.
+    fn heredoc_body<'a>(script: &'a str, path: &str, tag: &str) -> &'a str {
+        let start = script.find(&format!("cat > {} << '{}'\n", path, tag)).unwrap();
+        let body = &script[start..];
+        let body = &body[body.find('\n').unwrap() + 1..];
+        &body[..body.find(&format!("\n{}\n", tag)).unwrap()]
+    }
+
+    fn has_commands(commands: &[&str]) -> bool {
+        commands
+            .iter()
+            .all(|cmd| Command::new(cmd).arg("--version").output().is_ok())
+    }
+
+    /// `has_commands`, printing what `label` skips when they're missing so
+    /// `--nocapture` shows what a run didn't cover.
+    fn tools_installed(label: &str, commands: &[&str]) -> bool {
+        let installed = has_commands(commands);
+        if !installed {
+            println!("{}: skipped, needs {}", label, commands.join(", "));
+        }
+        installed
+    }
+
+    /// `fn first_ten_primes() -> Vec<i32>` takes no arguments, which C must
+    /// declare as `(void)`. Every backend's scaffold is checked for the
+    /// declaration, and run where its toolchain is installed.
+    #[test]
+    fn test_zero_param_function_builds() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges/easy/first_ten_primes.json");
+        let challenge: Challenge = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert!(sig.params.is_empty());
+
+        let primes = "2, 3, 5, 7, 11, 13, 17, 19, 23, 29";
+        let mut bodies = Vec::new();
+        for &lang in Language::all() {
+            let (declaration, body) = match lang {
+                Language::Rs => ("fn first_ten_primes() -> Vec<i32> {", None),
+                Language::Py => ("def first_ten_primes() -> list[int]:", None),
+                Language::Kt => {
+                    ("fun first_ten_primes(): MutableList<Int> {", Some(format!("return mutableListOf({})", primes)))
+                }
+                Language::Java => ("public static int[] first_ten_primes() {", None),
+                Language::C => ("int* first_ten_primes(void) {", None),
+                Language::Cpp => ("std::vector<int> first_ten_primes() {", None),
+                Language::Fortran => ("function first_ten_primes() result(res)", Some(format!("res = [{}]", primes))),
+                Language::Go => ("func first_ten_primes() []int {", Some(format!("return []int{{{}}}", primes))),
+                Language::Js => ("function first_ten_primes() {", Some(format!("return [{}];", primes))),
+                Language::Cs => {
+                    ("public static List<int> first_ten_primes()", Some(format!("return new List<int> {{ {} }};", primes)))
+                }
+                Language::Ts => ("export function first_ten_primes(): number[] {", Some(format!("return [{}];", primes))),
+            };
+            let dir = scratch_dir(&format!("zero-param-{}", lang.extension()));
+            generate_scaffold(&challenge, &sig, lang, Difficulty::Easy, &dir, &ScaffoldOptions::default()).unwrap();
+            let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+            let _ = fs::remove_dir_all(&dir);
+            assert!(script.contains(declaration), "{:?}", lang);
+            let call = if lang == Language::C { "first_ten_primes(void);" } else { "first_ten_primes()" };
+            assert!(script.contains(call), "{:?}", lang);
+
+            let body = body.or_else(|| challenge.reference_solutions.get(&lang).cloned()).unwrap();
+            bodies.push((lang, body));
+        }
+        let bodies: Vec<(Language, &str)> = bodies.iter().map(|(lang, body)| (*lang, body.as_str())).collect();
+        check_solutions("zero-param", &challenge, &bodies);
+    }
+
+    /// Every C return type is printed with its own conversion, so both the
+    /// solution and the tests build without `-Wformat` warnings.
+    #[test]
+    fn test_c_printf_formats_match_return_type() {
+        if !tools_installed("c_printf_formats_match_return_type", Language::C.required_commands()) {
+            return;
+        }
+        let cases = [
+            ("fn next_letter(c: char) -> char", json!({"c": "a"}), json!("b"), "    return c + 1;"),
+            ("fn half(n: usize) -> usize", json!({"n": 8}), json!(4), "    return n / 2;"),
+            ("fn negate(n: i64) -> i64", json!({"n": 5000000000i64}), json!(-5000000000i64), "    return -n;"),
+            ("fn third(x: f64) -> f64", json!({"x": 1.5}), json!(0.5), "    return x / 3;"),
+            ("fn greet(name: String) -> String", json!({"name": "Ada"}), json!("Hi"), "    (void)name;\n    return \"Hi\";"),
+            (
+                "fn pair(n: i32) -> Vec<i32>",
+                json!({"n": 3}),
+                json!([3, 4]),
+                "    int* out = malloc(2 * sizeof(int));\n    out[0] = n;\n    out[1] = n + 1;\n    return out;",
+            ),
+        ];
+        for (i, (signature, input, expected, body)) in cases.into_iter().enumerate() {
+            let challenge = fixture_challenge(signature, json!([{"input": input, "expected": expected}]));
+            let sig = parse_signature(signature).unwrap();
+            let dir = scratch_dir(&format!("c-printf-{}", i));
+            let options = ScaffoldOptions {
+                solution_body: Some(body.to_string()),
+                ..ScaffoldOptions::default()
+            };
+            generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+            let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+            let build = Command::new("make").args(["solution", "test_runner"]).current_dir(&dir).output().unwrap();
+            let passed = run_tests(Language::C, &dir, None).is_ok_and(|s| (s.passed, s.failed) == (1, 0));
+            let _ = fs::remove_dir_all(&dir);
+
+            assert!(setup.status.success() && build.status.success(), "{}", signature);
+            let stderr = String::from_utf8_lossy(&build.stderr);
+            assert!(!stderr.contains("warning"), "{}: {}", signature, stderr);
+            assert!(passed, "{}", signature);
+        }
+    }
+
+    fn run_setup_and_tests(dir: &Path, lang: Language, expected_passed: usize) -> bool {
+        let setup = Command::new("bash").arg("setup.sh").current_dir(dir).output().unwrap();
+        setup.status.success()
+            && run_tests(lang, dir, None)
+                .is_ok_and(|summary| (summary.passed, summary.failed) == (expected_passed, 0))
+    }
+
+    /// How `check_solutions` can check a language on this machine.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Toolchain {
+        /// setup.sh and the real test command.
+        Full,
+        /// Without the test framework: the Python tests called directly,
+        /// App.java compiled with javac, or the JavaScript run with node.
+        Light,
+        Missing,
+    }
+
+    /// setup.sh downloads pytest, JUnit, Jest or xUnit for these, so they
+    /// only get a full run with `CODLE_TEST_NETWORK` set.
+    fn toolchain(lang: Language) -> Toolchain {
+        let fetches_framework = matches!(
+            lang,
+            Language::Py | Language::Kt | Language::Java | Language::Js | Language::Ts | Language::Cs
+        );
+        if has_commands(lang.required_commands())
+            && (!fetches_framework || std::env::var_os("CODLE_TEST_NETWORK").is_some())
+        {
+            return Toolchain::Full;
+        }
+        let light = match lang {
+            Language::Py => has_commands(&["python3"]),
+            Language::Java => has_commands(&["javac"]),
+            Language::Js => has_commands(&["node"]),
+            _ => false,
+        };
+        if light { Toolchain::Light } else { Toolchain::Missing }
+    }
+
+    /// The lighter check for `Toolchain::Light`, on a scaffold whose
+    /// setup.sh hasn't run.
+    fn run_light_check(dir: &Path, lang: Language) -> bool {
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let run = |cmd: &str, args: &[&str]| Command::new(cmd).args(args).current_dir(dir).status().unwrap().success();
+        match lang {
+            Language::Py => {
+                fs::write(dir.join("solution.py"), heredoc_body(&script, "solution.py", "SOLUTION")).unwrap();
+                fs::write(dir.join("test_solution.py"), heredoc_body(&script, "test_solution.py", "TESTS")).unwrap();
+                let call_all = "import test_solution as t\nfor name in dir(t):\n    if name.startswith('test_'): getattr(t, name)()";
+                run("python3", &["-c", call_all])
+            }
+            Language::Java => {
+                let app = heredoc_body(&script, "app/src/main/java/codle/App.java", "SOLUTION");
+                fs::create_dir_all(dir.join("codle")).unwrap();
+                fs::write(dir.join("codle/App.java"), app).unwrap();
+                run("javac", &["codle/App.java"])
+            }
+            Language::Js => {
+                fs::write(dir.join("solution.js"), heredoc_body(&script, "solution.js", "SOLUTION")).unwrap();
+                fs::write(dir.join("solution.test.js"), heredoc_body(&script, "solution.test.js", "TESTS")).unwrap();
+                run("node", &["solution.js"]) && run("node", &["--check", "solution.test.js"])
+            }
+            _ => unreachable!("no light check for {:?}", lang),
+        }
+    }
+
+    /// Scaffolds `challenge` in each language with its solution body and
+    /// checks that every test passes, as far as `toolchain` allows. Languages
+    /// it can't check are printed, so `--nocapture` shows what was skipped.
+    fn check_solutions(label: &str, challenge: &Challenge, bodies: &[(Language, &str)]) {
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        for &(lang, body) in bodies {
+            let toolchain = toolchain(lang);
+            if toolchain == Toolchain::Missing {
+                println!("{}: skipped {}, needs {}", label, lang.display_name(), lang.required_commands().join(", "));
+                continue;
+            }
+            let dir = scratch_dir(&format!("{}-{}", label, lang.extension()));
+            let options = ScaffoldOptions {
+                solution_body: Some(body.to_string()),
+                ..ScaffoldOptions::default()
+            };
+            generate_scaffold(challenge, &sig, lang, Difficulty::Easy, &dir, &options).unwrap();
+            let passed = match toolchain {
+                Toolchain::Full => run_setup_and_tests(&dir, lang, challenge.tests.len()),
+                _ => run_light_check(&dir, lang),
+            };
+            let _ = fs::remove_dir_all(&dir);
+            assert!(passed, "{}: {:?} ({:?})", label, lang, toolchain);
+        }
+    }
+
+    /// The expected averages are rounded to 12 digits, so every backend has to
+    /// compare `Vec<f64>` results within a tolerance to pass.
+    #[test]
+    fn test_float_vec_compared_with_tolerance() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges/easy/running_average.json");
+        let challenge: Challenge = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+
+        let python = python::generate_python_tests(&sig, &challenge.tests, false);
+        assert!(python.contains("    assert result == pytest.approx([1.0, 1.5, 2.333333333333], abs=1e-9)\n"));
+        let kotlin = kotlin::generate_kotlin_tests(&sig, &challenge.tests);
+        assert!(kotlin.contains("assertEquals(expected[i], result[i], 1e-9, \"expected: $expected but was: $result\")"));
+        let java = java::generate_java_tests(&sig, &challenge.tests);
+        assert!(java.contains("assertArrayEquals(new double[] {10.0}, result, 1e-9);"));
+        let fortran = fortran::generate_fortran_tests(&sig, &challenge.tests);
+        assert!(fortran.contains(".or. abs(actual - "));
+
+        let bodies: Vec<(Language, &str)> = [Language::Rs, Language::C, Language::Cpp]
+            .into_iter()
+            .map(|lang| (lang, challenge.reference_solutions[&lang].as_str()))
+            .collect();
+        check_solutions("float-vec", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_c_string_len_param() {
+        let mut challenge = fixture_challenge(
+            "fn count_vowels(s: String) -> i32",
+            json!([
+                {"input": {"s": "codle"}, "expected": 2},
+                {"input": {"s": ""}, "expected": 0},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert!(c::generate_c_tests(&sig, &challenge.tests, false).contains("int count_vowels(const char* s);"));
+
+        challenge.c_string_len = true;
+        let tests = c::generate_c_tests(&sig, &challenge.tests, true);
+        assert!(tests.contains("int count_vowels(const char* s, int s_len);"));
+        assert!(tests.contains("        int s_len = 5;\n        int result = count_vowels(s, s_len);"));
+
+        if !tools_installed("c_string_len_param", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-string-len");
+        let options = ScaffoldOptions {
+            solution_body: Some(
+                "int count = 0;\nfor (int i = 0; i < s_len; i++) {\n    char c = s[i];\n    count += c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u';\n}\nreturn count;"
+                    .to_string(),
+            ),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        let passed = run_setup_and_tests(&dir, Language::C, 2);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(passed);
+    }
+
+    #[test]
+    fn test_large_c_array_read_from_header() {
+        let nums: Vec<i32> = (1..=2000).collect();
+        let challenge = fixture_challenge(
+            "fn sum(nums: Vec<i32>) -> i32",
+            json!([
+                {"input": {"nums": nums}, "expected": 2001000},
+                {"input": {"nums": [1, 2]}, "expected": 3},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let tests = c::generate_c_tests(&sig, &challenge.tests, false);
+        assert!(tests.contains("#include \"test_data.h\""));
+        assert!(tests.contains("        int* nums_arr = codle_test1_nums;\n        int nums_len = 2000;\n"));
+        assert!(tests.contains("        int nums_arr[] = {1, 2};\n"));
+        assert!(!tests.contains("1999, 2000"));
+        let data = c::generate_c_test_data(&sig, &challenge.tests).unwrap();
+        assert!(data.contains("int codle_test1_nums[] = {1, 2, 3,"));
+        assert!(!data.contains("codle_test2"));
+
+        if !tools_installed("large_c_array_read_from_header", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-large-array");
+        let options = ScaffoldOptions {
+            solution_body: Some(
+                "int total = 0;\nfor (int i = 0; i < nums_len; i++) total += nums[i];\nreturn total;".to_string(),
+            ),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        let passed = run_setup_and_tests(&dir, Language::C, 2);
+        let run = Command::new("make").arg("run").current_dir(&dir).output().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(passed);
+        assert!(run.status.success());
+        assert!(String::from_utf8_lossy(&run.stdout).contains("2001000"));
+        assert!(!String::from_utf8_lossy(&run.stderr).contains("warning"));
+    }
+
+    #[test]
+    fn test_64_bit_integers_rendered_per_language() {
+        let big = json!(2432902008176640000u64);
+        let max = json!(u64::MAX);
+        assert_eq!(render_value(&big, &RustType::I64, Language::Rs), "2432902008176640000");
+        assert_eq!(render_value(&max, &RustType::U64, Language::Py), "18446744073709551615");
+        assert_eq!(render_value(&big, &RustType::I64, Language::Java), "2432902008176640000L");
+        assert_eq!(render_value(&big, &RustType::I64, Language::Kt), "2432902008176640000L");
+        assert_eq!(render_value(&max, &RustType::U64, Language::Kt), "18446744073709551615uL");
+        assert_eq!(translate_type(&RustType::U64, Language::Kt), "ULong");
+        assert!(!Language::Java.supports(&RustType::U64));
+        assert!(!Language::Fortran.supports(&RustType::Vec(Box::new(RustType::U64))));
+        assert!(!Language::Js.supports(&RustType::I64));
+        assert!(!Language::Ts.supports(&RustType::Option(Box::new(RustType::U64))));
+        assert!(Language::Ts.supports(&RustType::U32));
+        assert_eq!(render_value(&max, &RustType::U64, Language::C), "18446744073709551615ULL");
+        assert_eq!(render_value(&big, &RustType::I64, Language::Cpp), "2432902008176640000LL");
+        assert_eq!(render_value(&big, &RustType::I64, Language::Fortran), "2432902008176640000_8");
+        assert_eq!(translate_type(&RustType::U64, Language::C), "unsigned long long");
+        assert_eq!(translate_type(&RustType::I64, Language::Java), "long");
+        assert_eq!(translate_type(&RustType::I64, Language::Kt), "Long");
+        assert_eq!(translate_type(&RustType::I64, Language::Py), "int");
+
+        let min = json!(i64::MIN);
+        assert_eq!(render_value(&min, &RustType::I64, Language::C), "(-9223372036854775807LL - 1)");
+        assert_eq!(render_value(&min, &RustType::I64, Language::Cpp), "(-9223372036854775807LL - 1)");
+        assert_eq!(render_value(&min, &RustType::I64, Language::Kt), "Long.MIN_VALUE");
+        assert_eq!(render_value(&min, &RustType::I64, Language::Fortran), "(-9223372036854775807_8 - 1_8)");
+        assert_eq!(render_value(&min, &RustType::I64, Language::Java), "-9223372036854775808L");
+        assert_eq!(render_value(&min, &RustType::I64, Language::Rs), "-9223372036854775808");
+
+        let challenge = fixture_challenge(
+            "fn factorial(n: i32) -> i64",
+            json!([{"input": {"n": 20}, "expected": 2432902008176640000u64}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let tests = c::generate_c_tests(&sig, &challenge.tests, false);
+        assert!(tests.contains("long long result = factorial(n);"));
+        assert!(tests.contains("got %lld)"));
+
+        if !tools_installed("64_bit_integers_rendered_per_language", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-i64");
+        let options = ScaffoldOptions {
+            solution_body: Some("long long f = 1;\nfor (int i = 2; i <= n; i++) f *= i;\nreturn f;".to_string()),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        let passed = run_setup_and_tests(&dir, Language::C, 1);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(passed);
+    }
+
+    #[test]
+    fn test_fixed_width_integers_per_language() {
+        let big = json!(4000000000u64);
+        assert_eq!(render_value(&json!(-5), &RustType::I8, Language::Java), "(byte) -5");
+        assert_eq!(render_value(&json!(200), &RustType::U8, Language::Java), "(short) 200");
+        assert_eq!(render_value(&big, &RustType::U32, Language::Java), "4000000000L");
+        assert_eq!(render_value(&json!(-5), &RustType::I8, Language::Kt), "(-5).toByte()");
+        assert_eq!(render_value(&json!(300), &RustType::I16, Language::Kt), "300.toShort()");
+        assert_eq!(render_value(&big, &RustType::U32, Language::C), "4000000000U");
+        assert_eq!(render_value(&big, &RustType::U32, Language::Py), "4000000000");
+        assert_eq!(render_value(&json!(7), &RustType::U16, Language::Go), "uint16(7)");
+        assert_eq!(render_value(&json!(7), &RustType::U8, Language::Cs), "(byte)7");
+        assert_eq!(translate_type(&RustType::U8, Language::C), "uint8_t");
+        assert_eq!(translate_type(&RustType::I16, Language::C), "int16_t");
+        assert_eq!(translate_type(&RustType::U16, Language::Java), "int");
+        assert_eq!(translate_type(&RustType::I8, Language::Kt), "Byte");
+        assert_eq!(translate_type(&RustType::U32, Language::Py), "int");
+
+        let challenge = fixture_challenge(
+            "fn reverse_bits(n: u8) -> u8",
+            json!([
+                {"input": {"n": 1}, "expected": 128},
+                {"input": {"n": 6}, "expected": 96}
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert!(c::generate_c_tests(&sig, &challenge.tests, false).contains("#include <stdint.h>\n"));
+        let plain = parse_signature("fn f(n: i32) -> i32").unwrap();
+        assert!(!c::generate_c_tests(&plain, &challenge.tests, false).contains("stdint"));
+
+        let body = "uint8_t r = 0;\nfor (int i = 0; i < 8; i++) {\n    r = (r << 1) | (n & 1);\n    n >>= 1;\n}\nreturn r;";
+        check_solutions("u8", &challenge, &[(Language::C, body), (Language::Cpp, body)]);
+
+        // The solution's own `main` prints the result as a number, not a char.
+        if !tools_installed("fixed_width_integers_per_language", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-u8-run");
+        let options = ScaffoldOptions {
+            solution_body: Some(body.to_string()),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let run = Command::new("make").arg("run").current_dir(&dir).output().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(setup.status.success());
+        assert!(String::from_utf8_lossy(&run.stdout).contains("128"));
+    }
+
+    #[test]
+    fn test_option_return_per_language() {
+        let challenge = fixture_challenge(
+            "fn first_even(nums: Vec<i32>) -> Option<i32>",
+            json!([
+                {"input": {"nums": [1, 4, 6]}, "expected": 4},
+                {"input": {"nums": [1, 3]}, "expected": null},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let ty = &sig.return_type;
+        assert_eq!(render_value(&json!(4), ty, Language::Rs), "Some(4)");
+        assert_eq!(render_value(&Value::Null, ty, Language::Rs), "None");
+        assert_eq!(translate_type(ty, Language::Py), "int | None");
+        assert_eq!(render_value(&Value::Null, ty, Language::Py), "None");
+        assert_eq!(translate_type(ty, Language::Kt), "Int?");
+        assert_eq!(render_value(&Value::Null, ty, Language::Kt), "null");
+        assert_eq!(translate_type(ty, Language::Java), "Integer");
+        assert_eq!(render_value(&json!(4), ty, Language::Java), "Integer.valueOf(4)");
+        assert_eq!(translate_type(ty, Language::Cpp), "std::optional<int>");
+        assert_eq!(render_value(&Value::Null, ty, Language::Cpp), "std::nullopt");
+        assert!(check_language_support(&sig, Language::C).is_err());
+
+        assert!(python::generate_python_tests(&sig, &challenge.tests, false).contains("    assert result is None\n"));
+        assert!(java::generate_java_tests(&sig, &challenge.tests).contains("        assertNull(result);\n"));
+
+        let bodies = [
+            (Language::Rs, "nums.into_iter().find(|x| x % 2 == 0)"),
+            (Language::Cpp, "for (int x : nums) if (x % 2 == 0) return x;\nreturn std::nullopt;"),
+            (Language::Java, "for (int x : nums) if (x % 2 == 0) return x;\nreturn null;"),
+        ];
+        check_solutions("option", &challenge, &bodies);
+    }
+
+    /// The solutions return the evens in input order, which differs from the
+    /// expected order, so only a comparison that ignores order passes.
+    #[test]
+    fn test_unordered_results_compared_as_multisets() {
+        let challenge = fixture_challenge(
+            "fn evens(nums: Vec<i32>) -> Vec<i32>",
+            json!([
+                {"input": {"nums": [2, 3, 4, 2]}, "expected": [4, 2, 2], "unordered": true},
+                {"input": {"nums": [1]}, "expected": [], "unordered": true},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+
+        let rust = rust::generate_rust_tests(&sig, &challenge.tests);
+        assert!(rust.contains("        let mut actual = result.clone();\n        actual.sort_by("));
+        assert!(rust.contains("        let mut expected: Vec<i32> = vec![4, 2, 2];\n        expected.sort_by("));
+        assert!(python::generate_python_tests(&sig, &challenge.tests, false)
+            .contains("    assert sorted(result) == sorted([4, 2, 2])\n"));
+        assert!(java::generate_java_tests(&sig, &challenge.tests).contains("        java.util.Arrays.sort(expected);\n"));
+        assert!(kotlin::generate_kotlin_tests(&sig, &challenge.tests).contains(".groupingBy { it }.eachCount()"));
+        assert!(go::generate_go_tests(&sig, &challenge.tests).contains("\tsort.Slice(result, "));
+        assert!(javascript::generate_javascript_tests(&sig, &challenge.tests)
+            .contains("expect(actual).toEqual(unordered([4, 2, 2]));"));
+        assert!(fortran::generate_fortran_tests(&sig, &challenge.tests).contains("    if (ok) ok = count(actual == 2) == 2\n"));
+
+        let bodies = [
+            (Language::Rs, "nums.into_iter().filter(|x| x % 2 == 0).collect()"),
+            (Language::Cpp, "std::vector<int> out;\nfor (int x : nums) if (x % 2 == 0) out.push_back(x);\nreturn out;"),
+            (Language::C, "int* out = malloc(sizeof(int) * (nums_len + 1));\nint n = 0;\nfor (int i = 0; i < nums_len; i++) if (nums[i] % 2 == 0) out[n++] = nums[i];\nreturn out;"),
+            (Language::Py, "return [x for x in nums if x % 2 == 0]"),
+        ];
+        check_solutions("unordered", &challenge, &bodies);
+    }
+
+    /// The solutions insert "banana"'s letters in a different order from the
+    /// expected JSON object, so each backend must compare maps as sets.
+    #[test]
+    fn test_map_return_per_language() {
+        let challenge = fixture_challenge(
+            "fn counts(s: String) -> HashMap<char, i32>",
+            json!([
+                {"input": {"s": "banana"}, "expected": {"a": 3, "b": 1, "n": 2}},
+                {"input": {"s": ""}, "expected": {}},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let ty = &sig.return_type;
+        let expected = &challenge.tests[0].expected;
+        assert_eq!(
+            render_value(expected, ty, Language::Rs),
+            "HashMap::from([('a', 3), ('b', 1), ('n', 2)])"
+        );
+        assert_eq!(render_value(&json!({}), ty, Language::Rs), "HashMap::<char, i32>::new()");
+        assert_eq!(render_value(expected, ty, Language::Py), r#"{"a": 3, "b": 1, "n": 2}"#);
+        assert_eq!(translate_type(ty, Language::Java), "Map<Character, Integer>");
+        assert_eq!(
+            render_value(expected, ty, Language::Java),
+            "new HashMap<>(Map.of('a', 3, 'b', 1, 'n', 2))"
+        );
+        assert_eq!(render_value(expected, ty, Language::Kt), "mutableMapOf('a' to 3, 'b' to 1, 'n' to 2)");
+        assert_eq!(render_value(expected, ty, Language::Cpp), "{{'a', 3}, {'b', 1}, {'n', 2}}");
+        assert_eq!(render_value(expected, ty, Language::Go), "map[rune]int{'a': 3, 'b': 1, 'n': 2}");
+        assert_eq!(render_value(expected, ty, Language::Js), r#"new Map([["a", 3], ["b", 1], ["n", 2]])"#);
+        assert!(check_language_support(&sig, Language::C).is_err());
+        assert!(check_language_support(&sig, Language::Fortran).is_err());
+
+        let int_keys = parse_signature("fn f() -> HashMap<i64, bool>").unwrap().return_type;
+        assert_eq!(render_value(&json!({"-2": true}), &int_keys, Language::Py), "{-2: True}");
+
+        assert!(javascript::generate_javascript_tests(&sig, &challenge.tests)
+            .contains(r#"expect(result).toEqual(new Map([["a", 3], ["b", 1], ["n", 2]]));"#));
+        assert!(go::generate_go_tests(&sig, &challenge.tests).contains("reflect.DeepEqual(result, expected)"));
+
+        let bodies = [
+            (Language::Rs, "let mut counts = HashMap::new();\nfor c in s.chars() {\n    *counts.entry(c).or_insert(0) += 1;\n}\ncounts"),
+            (Language::Cpp, "std::unordered_map<char, int> counts;\nfor (char c : s) counts[c]++;\nreturn counts;"),
+            (Language::Java, "Map<Character, Integer> counts = new HashMap<>();\nfor (char c : s.toCharArray()) counts.merge(c, 1, Integer::sum);\nreturn counts;"),
+            (Language::Py, "counts = {}\nfor c in s:\n    counts[c] = counts.get(c, 0) + 1\nreturn counts"),
+        ];
+        check_solutions("map", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_f32_per_language() {
+        let scale = fixture_challenge(
+            "fn scale(v: Vec<f32>, k: f32) -> Vec<f32>",
+            json!([{"input": {"v": [0.1, 0.2], "k": 3.0}, "expected": [0.3, 0.6]}]),
+        );
+        let mean = fixture_challenge(
+            "fn mean(v: Vec<f32>) -> f32",
+            json!([{"input": {"v": [0.1, 0.2, 0.4]}, "expected": 0.233333333}]),
+        );
+        let scale_sig = parse_signature(&scale.function_signature).unwrap();
+        let mean_sig = parse_signature(&mean.function_signature).unwrap();
+        let v = &json!([0.1, 0.2]);
+        let ty = &scale_sig.return_type;
+        assert_eq!(render_value(v, ty, Language::Cpp), "{0.1f, 0.2f}");
+        assert_eq!(render_value(v, ty, Language::Java), "new float[] {0.1f, 0.2f}");
+        assert_eq!(render_value(v, ty, Language::Kt), "mutableListOf(0.1f, 0.2f)");
+        assert_eq!(render_value(v, ty, Language::C), "{0.1f, 0.2f}");
+        assert_eq!(render_value(&json!(3.0), &RustType::F32, Language::Go), "float32(3.0)");
+        assert_eq!(render_value(&json!("inf"), &RustType::F32, Language::Rs), "f32::INFINITY");
+        assert_eq!(translate_type(ty, Language::Py), "list[float]");
+        assert!(kotlin::generate_kotlin_tests(&scale_sig, &scale.tests)
+            .contains("assertEquals(expected[i], result[i], 1e-5f, \"expected: $expected but was: $result\")"));
+        assert!(kotlin::generate_kotlin_tests(&mean_sig, &mean.tests)
+            .contains("assertEquals(0.233333333f, result, 1e-5f)"));
+        assert!(java::generate_java_tests(&scale_sig, &scale.tests)
+            .contains("assertArrayEquals(new float[] {0.3f, 0.6f}, result, 1e-5f);"));
+        assert!(go::generate_go_tests(&mean_sig, &mean.tests)
+            .contains("math.Abs(float64(result-expected)) >= 1e-5"));
+        assert!(python::generate_python_tests(&scale_sig, &scale.tests, false)
+            .contains("assert result == pytest.approx([0.3, 0.6], abs=1e-5)"));
+
+        let bodies = [
+            (Language::Rs, "v.iter().map(|x| x * k).collect()", "v.iter().sum::<f32>() / v.len() as f32"),
+            (
+                Language::C,
+                "float* out = malloc(v_len * sizeof(float));\nfor (int i = 0; i < v_len; i++) out[i] = v[i] * k;\nreturn out;",
+                "float s = 0;\nfor (int i = 0; i < v_len; i++) s += v[i];\nreturn s / v_len;",
+            ),
+            (
+                Language::Cpp,
+                "std::vector<float> out;\nfor (float x : v) out.push_back(x * k);\nreturn out;",
+                "float s = 0;\nfor (float x : v) s += x;\nreturn s / v.size();",
+            ),
+            (
+                Language::Java,
+                "float[] out = new float[v.length];\nfor (int i = 0; i < v.length; i++) out[i] = v[i] * k;\nreturn out;",
+                "float s = 0;\nfor (float x : v) s += x;\nreturn s / v.length;",
+            ),
+        ];
+        let scale_bodies: Vec<(Language, &str)> = bodies.iter().map(|&(lang, body, _)| (lang, body)).collect();
+        let mean_bodies: Vec<(Language, &str)> = bodies.iter().map(|&(lang, _, body)| (lang, body)).collect();
+        check_solutions("f32-scale", &scale, &scale_bodies);
+        check_solutions("f32-mean", &mean, &mean_bodies);
+    }
+
+    #[test]
+    fn test_str_params() {
+        let challenge = fixture_challenge(
+            "fn count(text: &str, word: &str, extra: &String) -> usize",
+            json!([{"input": {"text": "a b a", "word": "a", "extra": "!"}, "expected": 2}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert_eq!(render_value(&json!("a"), &sig.params[1].ty, Language::Rs), r#""a""#);
+        assert_eq!(render_value(&json!("a"), &sig.params[2].ty, Language::Rs), r#""a".to_string()"#);
+
+        let tests = rust::generate_rust_tests(&sig, &challenge.tests);
+        assert!(tests.contains("        let text = \"a b a\";\n"));
+        assert!(tests.contains("        let result = count(text, word, &extra);\n"));
+        let java = by_value_for(&sig, Language::Java);
+        assert_eq!(java.params[0].ty, RustType::String);
+        assert!(java::generate_java_tests(&java, &challenge.tests).contains("String text = \"a b a\";"));
+        let cpp = by_value_for(&sig, Language::Cpp);
+        assert_eq!(translate_type(&cpp.params[0].ty, Language::Cpp), "const std::string&");
+
+        if tools_installed("str_params", Language::Rs.required_commands()) {
+            let dir = scratch_dir("str-params");
+            let options = ScaffoldOptions {
+                solution_body: Some("text.split(' ').filter(|w| *w == word).count() + extra.len() - 1".to_string()),
+                ..ScaffoldOptions::default()
+            };
+            generate_scaffold(&challenge, &sig, Language::Rs, Difficulty::Easy, &dir, &options).unwrap();
+            let passed = run_setup_and_tests(&dir, Language::Rs, 1);
+            let _ = fs::remove_dir_all(&dir);
+            assert!(passed);
+        }
+    }
+
+    #[test]
+    fn test_multiple_mut_ref_outputs() {
+        let challenge = fixture_challenge(
+            "fn swap_all(a: &mut Vec<i32>, b: &mut Vec<i32>)",
+            json!([
+                {"input": {"a": [1, 2], "b": [3, 4]}, "expected": {"a": [3, 4], "b": [1, 2]}},
+                {"input": {"a": [5], "b": [6]}, "expected": {"b": [5]}},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert!(crate::models::validate_challenge(&challenge, &sig).is_ok());
+
+        let rust = rust::generate_rust_tests(&sig, &challenge.tests);
+        assert!(rust.contains("        assert_eq!(a, vec![3, 4]);\n        assert_eq!(b, vec![1, 2]);\n"));
+        assert!(!rust.contains("assert_eq!(a, vec![6])"));
+        let python = python::generate_python_tests(&sig, &challenge.tests, false);
+        assert!(python.contains("    assert a == [3, 4]\n    assert b == [1, 2]\n"));
+        let java = java::generate_java_tests(&sig, &challenge.tests);
+        assert!(java.contains("        {\n            assertArrayEquals(new int[] {3, 4}, result);\n        }\n"));
+        assert!(java.contains("            assertArrayEquals(new int[] {1, 2}, b);\n"));
+        let kotlin = kotlin::generate_kotlin_tests(&sig, &challenge.tests);
+        assert!(kotlin.contains("        run {\n            assertEquals(mutableListOf(1, 2), b)\n        }\n"));
+        let go = go::generate_go_tests(&sig, &challenge.tests);
+        assert!(go.contains("\t{\n\t\texpected := []int{1, 2}\n"));
+        let js = javascript::generate_javascript_tests(&sig, &challenge.tests);
+        assert!(js.contains("    {\n        expect(b).toEqual([1, 2]);\n    }\n"));
+        let fortran = fortran::generate_fortran_tests(&sig, &challenge.tests);
+        assert!(fortran.contains("    all_ok = all_ok .and. ok\n"));
+
+        // A plain `expected` still checks only the first `&mut` parameter.
+        let first_only = fixture_challenge(
+            "fn swap_all(a: &mut Vec<i32>, b: &mut Vec<i32>)",
+            json!([{"input": {"a": [1], "b": [2]}, "expected": [2]}]),
+        );
+        assert!(rust::generate_rust_tests(&sig, &first_only.tests).contains("        assert_eq!(a, vec![2]);\n    }"));
+
+        let bodies = [
+            (Language::Rs, "std::mem::swap(a, b);"),
+            (Language::C, "for (int i = 0; i < a_len; i++) { int t = a[i]; a[i] = b[i]; b[i] = t; }"),
+            (Language::Cpp, "std::swap(a, b);"),
+        ];
+        check_solutions("mut-ref-outputs", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_char_values_escaped() {
+        let cases = [
+            ("\n", [r"'\n'", r"'\n'", r"'\n'", r"'\n'", r"'\n'", r#""\n""#]),
+            ("'", [r"'\''", r"'\''", r"'\''", r"'\''", r"'\''", r#""'""#]),
+            ("\\", [r"'\\'", r"'\\'", r"'\\'", r"'\\'", r"'\\'", r#""\\""#]),
+            ("\u{1}", [r"'\x01'", r"'\001'", r"'\001'", r"'\u0001'", r"'\u0001'", r#""\x01""#]),
+        ];
+        let langs = [Language::Rs, Language::C, Language::Cpp, Language::Java, Language::Kt, Language::Py];
+        for (c, literals) in cases {
+            for (lang, literal) in langs.iter().zip(literals) {
+                assert_eq!(render_value(&json!(c), &RustType::Char, *lang), literal, "{:?} {:?}", lang, c);
+            }
+        }
+        assert_eq!(render_value(&json!("\u{0}9"), &RustType::String, Language::C), r#""\0009""#);
+
+        let challenge = fixture_challenge(
+            "fn same(c: char) -> char",
+            json!([
+                {"input": {"c": "'"}, "expected": "'"},
+                {"input": {"c": "\\"}, "expected": "\\"},
+                {"input": {"c": "\u{1}"}, "expected": "\u{1}"},
+            ]),
+        );
+        let bodies = [(Language::Rs, "c"), (Language::C, "return c;"), (Language::Cpp, "return c;")];
+        check_solutions("char-escape", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_string_values_escaped() {
+        let value = json!("he said \"hi\"\n\\ $x\t%d");
+        let c_family = r#""he said \"hi\"\n\\ $x\t%d""#;
+        assert_eq!(render_value(&value, &RustType::String, Language::Rs), format!("{}.to_string()", c_family));
+        assert_eq!(render_value(&value, &RustType::String, Language::Py), c_family);
+        assert_eq!(render_value(&value, &RustType::String, Language::C), c_family);
+        assert_eq!(render_value(&value, &RustType::String, Language::Cpp), c_family);
+        assert_eq!(render_value(&value, &RustType::String, Language::Java), c_family);
+        assert_eq!(
+            render_value(&value, &RustType::String, Language::Kt),
+            r#""he said \"hi\"\n\\ \$x\t%d""#
+        );
+        assert_eq!(
+            render_value(&json!("it's\n"), &RustType::String, Language::Fortran),
+            "'it''s' // achar(10)"
+        );
+        assert_eq!(render_value(&json!("\n"), &RustType::Char, Language::Java), r"'\n'");
+        assert_eq!(render_value(&json!("'"), &RustType::Char, Language::C), r"'\''");
+
+        let challenge = fixture_challenge(
+            "fn echo(s: String) -> String",
+            json!([{"input": {"s": value}, "expected": value}]),
+        );
+        let bodies = [
+            (Language::Rs, "s"),
+            (Language::C, "return (char*)s;"),
+            (Language::Cpp, "return s;"),
+            (Language::Py, "return s"),
+        ];
+        check_solutions("escape", &challenge, &bodies);
+    }
+
+    /// The expected value is a third rounded to 12 decimals, so an exact
+    /// comparison with the computed value would fail.
+    #[test]
+    fn test_float_result_compared_with_tolerance() {
+        let challenge = fixture_challenge(
+            "fn third(x: f64) -> f64",
+            json!([{"input": {"x": 1.0}, "expected": 0.333333333333}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert!(rust::generate_rust_tests(&sig, &challenge.tests).contains("(result - expected).abs() < 1e-9"));
+        assert!(python::generate_python_tests(&sig, &challenge.tests, false)
+            .contains("    assert result == pytest.approx(0.333333333333, abs=1e-9)\n"));
+        assert!(kotlin::generate_kotlin_tests(&sig, &challenge.tests)
+            .contains("        assertEquals(0.333333333333, result, 1e-9)\n"));
+        assert!(java::generate_java_tests(&sig, &challenge.tests)
+            .contains("        assertEquals(0.333333333333, result, 1e-9);\n"));
+        assert!(fortran::generate_fortran_tests(&sig, &challenge.tests)
+            .contains(".or. abs(actual - 0.333333333333d0) < 1d-9"));
+
+        let bodies = [
+            (Language::Rs, "x / 3.0"),
+            (Language::C, "return x / 3.0;"),
+            (Language::Cpp, "return x / 3.0;"),
+        ];
+        check_solutions("float-result", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_char_grid_java_deep_equality() {
+        let challenge = board_challenge();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let tests = java::generate_java_tests(&sig, &challenge.tests);
+        assert!(tests.contains(
+            "assertTrue(java.util.Arrays.deepEquals(new char[][] {new char[] {'X'}}, result), \"expected: \" + java.util.Arrays.deepToString(new char[][] {new char[] {'X'}}) + \" but was: \" + java.util.Arrays.deepToString(result));"
+        ));
+        assert!(!tests.contains("assertArrayEquals"));
+
+        if !tools_installed("char_grid_java_deep_equality", &["javac", "java"]) {
+            return;
+        }
+        let body = "for (int i = 0; i < board.length; i++)\n    for (int j = 0; j < board[i].length; j++)\n        if (board[i][j] == 'O' && i > 0 && j > 0 && i < board.length - 1 && j < board[i].length - 1) board[i][j] = 'X';\nreturn board;";
+        let dir = scratch_dir("java-char-grid");
+        let options = ScaffoldOptions {
+            solution_body: Some(body.to_string()),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::Java, Difficulty::Easy, &dir, &options).unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        fs::write(dir.join("App.java"), heredoc_body(&script, "app/src/main/java/codle/App.java", "SOLUTION")).unwrap();
+        let compile = Command::new("javac").args(["-d", "out", "App.java"]).current_dir(&dir).output().unwrap();
+        let run = Command::new("java").args(["-cp", "out", "codle.App"]).current_dir(&dir).output().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(compile.status.success(), "{}", String::from_utf8_lossy(&compile.stderr));
+        assert_eq!(
+            String::from_utf8_lossy(&run.stdout).trim(),
+            "[[X, X, X, X], [X, X, X, X], [X, X, X, X], [X, O, X, X]]"
+        );
+    }
+
+    #[test]
+    fn test_char_grid_cpp_passes() {
+        let challenge = board_challenge();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert!(cpp::generate_cpp_tests(&sig, &challenge.tests)
+            .contains("if (board == std::vector<std::vector<char>>{{'X'}})"));
+
+        if !tools_installed("char_grid_cpp_passes", Language::Cpp.required_commands()) {
+            return;
+        }
+        let body = "\
+int m = board.size(), n = board[0].size();
+std::vector<std::pair<int, int>> stack;
+for (int i = 0; i < m; i++)
+    for (int j = 0; j < n; j++)
+        if (i == 0 || j == 0 || i == m - 1 || j == n - 1) stack.push_back({i, j});
+while (!stack.empty()) {
+    std::pair<int, int> cell = stack.back();
+    stack.pop_back();
+    int i = cell.first, j = cell.second;
+    if (i < 0 || j < 0 || i >= m || j >= n || board[i][j] != 'O') continue;
+    board[i][j] = 'S';
+    stack.push_back({i + 1, j});
+    stack.push_back({i - 1, j});
+    stack.push_back({i, j + 1});
+    stack.push_back({i, j - 1});
+}
+for (auto& row : board)
+    for (auto& cell : row) cell = cell == 'S' ? 'O' : 'X';";
+        let dir = scratch_dir("cpp-char-grid");
+        let options = ScaffoldOptions {
+            solution_body: Some(body.to_string()),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::Cpp, Difficulty::Easy, &dir, &options).unwrap();
+        let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let build = Command::new("make").arg("all").current_dir(&dir).output().unwrap();
+        let summary = run_tests(Language::Cpp, &dir, None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(setup.status.success());
+        assert!(build.status.success(), "{}", String::from_utf8_lossy(&build.stderr));
+        assert_eq!((summary.passed, summary.failed), (4, 0), "{}", summary.output);
+    }
+
+    #[test]
+    fn test_c_matrix_param() {
+        let sig = parse_signature("fn f(grid: Vec<Vec<i32>>) -> i32").unwrap();
+        assert_eq!(c::c_param_list(&sig, false), "int** grid, int grid_len, int* grid_col_lens");
+        assert!(check_language_support(&sig, Language::C).is_ok());
+
+        let challenge = fixture_challenge(
+            "fn grid_sum(grid: Vec<Vec<i32>>) -> i32",
+            json!([
+                {"input": {"grid": [[1, 2], [3]]}, "expected": 6},
+                {"input": {"grid": [[], [4]]}, "expected": 4},
+                {"input": {"grid": []}, "expected": 0},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let tests = c::generate_c_tests(&sig, &challenge.tests, false);
+        assert!(tests.contains("        int grid_arr_row1[] = {3};\n"));
+        assert!(tests.contains("        int* grid_arr[] = {grid_arr_row0, grid_arr_row1};\n"));
+        assert!(tests.contains("        int grid_col_lens[] = {2, 1};\n"));
+        assert!(tests.contains("int result = grid_sum(grid_arr, grid_len, grid_col_lens);"));
+
+        if !tools_installed("c_matrix_param", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-matrix");
+        let options = ScaffoldOptions {
+            solution_body: Some(
+                "int sum = 0;\nfor (int i = 0; i < grid_len; i++)\n    for (int j = 0; j < grid_col_lens[i]; j++) sum += grid[i][j];\nreturn sum;"
+                    .to_string(),
+            ),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let build = Command::new("make")
+            .args(["-B", "all", "test_runner", "CFLAGS=-Wall -Wextra -Werror -std=c11"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let summary = run_tests(Language::C, &dir, None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(setup.status.success());
+        assert!(build.status.success(), "{}", String::from_utf8_lossy(&build.stderr));
+        assert_eq!((summary.passed, summary.failed), (3, 0), "{}", summary.output);
+    }
+
+    #[test]
+    fn test_shared_reference_param() {
+        let challenge = fixture_challenge(
+            "fn count(haystack: &Vec<i32>, needle: i32) -> i32",
+            json!([
+                {"input": {"haystack": [1, 2, 2], "needle": 2}, "expected": 2},
+                {"input": {"haystack": [], "needle": 1}, "expected": 0},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let ty = &sig.params[0].ty;
+        assert_eq!(translate_type(ty, Language::Rs), "&[i32]");
+        let slice = parse_signature("fn count(haystack: &[i32], needle: i32) -> i32").unwrap();
+        assert_eq!(slice.params[0].ty, *ty);
+        assert_eq!(translate_type(ty, Language::Cpp), "const std::vector<int>&");
+        assert_eq!(translate_type(ty, Language::Py), "list[int]");
+        assert_eq!(translate_type(ty, Language::Java), "int[]");
+        assert!(rust::generate_rust_tests(&sig, &challenge.tests).contains("count(&haystack, needle)"));
+
+        let bodies = [
+            (Language::Rs, "haystack.iter().filter(|&&x| x == needle).count() as i32"),
+            (Language::Cpp, "int n = 0;\nfor (int x : haystack) n += x == needle;\nreturn n;"),
+            (Language::C, "int n = 0;\nfor (int i = 0; i < haystack_len; i++) n += haystack[i] == needle;\nreturn n;"),
+        ];
+        check_solutions("shared-ref", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_c_string_array_compared_with_strcmp() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges/easy/reverse_words.json");
+        let challenge: Challenge = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let tests = c::generate_c_tests(&sig, &challenge.tests, false);
+        assert!(tests.contains("char** reverse_words(char* words[], int words_len);"));
+        assert!(tests.contains("if (strcmp(result[0], \"blue\") != 0) test_passed = 0;"));
+
+        if !tools_installed("c_string_array_compared_with_strcmp", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-string-array");
+        let options = ScaffoldOptions {
+            solution_body: challenge.reference_solutions.get(&Language::C).cloned(),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let summary = run_tests(Language::C, &dir, None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(setup.status.success());
+        assert_eq!((summary.passed, summary.failed), (4, 0), "{}", summary.output);
+    }
+
+    fn bench_setup_script(name: &str, challenge: &Challenge, lang: Language) -> String {
+        let dir = scratch_dir(name);
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let options = ScaffoldOptions {
+            with_bench: true,
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(challenge, &sig, lang, Difficulty::Easy, &dir, &options).unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        script
+    }
+
+    fn bench_challenge() -> Challenge {
+        fixture_challenge(
+            "fn running_sum(nums: Vec<i32>) -> Vec<i32>",
+            json!([
+                {"input": {"nums": [1, 2]}, "expected": [1, 3]},
+                {"input": {"nums": [1, 2, 3, 4, 5]}, "expected": [1, 3, 6, 10, 15]},
+                {"input": {"nums": []}, "expected": []},
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_rust_criterion_bench_generated() {
+        let script = bench_setup_script("rust-bench", &bench_challenge(), Language::Rs);
+        let bench = heredoc_body(&script, "benches/solution.rs", "BENCH");
+        let main_rs = heredoc_body(&script, "src/main.rs", "SOLUTION");
+
+        assert!(script.contains("criterion = \"0.5\"\n\n[[bench]]\nname = \"solution\"\nharness = false\n"));
+        assert!(main_rs.starts_with("pub fn running_sum(nums: Vec<i32>) -> Vec<i32> {"));
+        assert!(bench.contains("#[path = \"../src/main.rs\"]"));
+        assert!(bench.contains("|| (vec![1, 2, 3, 4, 5],),"));
+        assert!(bench.contains("|(nums,)| solution::running_sum(black_box(nums)),"));
+        assert!(bench.contains("criterion_main!(benches);"));
+        assert!(script.contains("echo \"Bench: cargo bench\""));
+    }
+
+    #[test]
+    fn test_c_bench_runs() {
+        let challenge = bench_challenge();
+        let script = bench_setup_script("c-bench-script", &challenge, Language::C);
+        assert!(heredoc_body(&script, "bench_solution.c", "BENCH")
+            .contains("        int nums_arr[] = {1, 2, 3, 4, 5};\n        int nums_len = 5;\n        free(running_sum(nums_arr, nums_len));\n"));
+
+        if !tools_installed("c_bench_runs", Language::C.required_commands()) {
+            return;
+        }
+        let dir = scratch_dir("c-bench");
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let options = ScaffoldOptions {
+            with_bench: true,
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let output = Command::new("make").args(["-s", "bench"]).current_dir(&dir).output().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("running_sum: "));
+    }
+
+    #[test]
+    fn test_bench_unavailable_for_java() {
+        let challenge = bench_challenge();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let dir = scratch_dir("java-bench");
+        let options = ScaffoldOptions {
+            with_bench: true,
+            ..ScaffoldOptions::default()
+        };
+        let result = generate_scaffold(&challenge, &sig, Language::Java, Difficulty::Easy, &dir, &options);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(result.unwrap_err().contains("Java"));
+    }
+
+    #[test]
+    fn test_setup_steps_marked() {
+        let challenge = fixture_challenge(
+            "fn add(a: i32, b: i32) -> i32",
+            json!([{"input": {"a": 1, "b": 2}, "expected": 3}]),
+        );
+        for lang in Language::all() {
+            let script = scaffold_setup_script("setup-steps", &challenge, *lang);
+            assert!(script.contains("echo \"[codle] step: checking required tools\"\n"), "{:?}", lang);
+            let lines: Vec<&str> = script.lines().collect();
+            for (i, line) in lines.iter().enumerate() {
+                if line.starts_with("cat >") {
+                    // A `mkdir -p` for the file's directory belongs to the same step.
+                    let before = if lines[i - 1].starts_with("mkdir -p") { i - 2 } else { i - 1 };
+                    assert!(lines[before].starts_with("echo \"[codle] step: "), "{:?}: {}", lang, line);
+                }
+            }
+        }
+
+        let output = "[codle] step: checking required tools\n[codle] step: running gradle init\nFAILURE: Could not resolve";
+        assert_eq!(last_setup_step(output), Some("running gradle init"));
+        assert_eq!(last_setup_step("no markers"), None);
+    }
+
+    #[test]
+    fn test_output_with_timeout_kills_slow_process() {
+        let err = output_with_timeout(
+            Command::new("sleep").arg("5"),
+            Some(Duration::from_millis(200)),
+        )
+        .unwrap_err();
+        assert!(err.starts_with("timed out"));
+
+        // Whatever the command started goes too, like the test binary under
+        // `make test`.
+        let marker = scratch_dir("timeout-group").join("survived");
+        let script = format!("(sleep 1; touch {}) & wait", marker.display());
+        output_with_timeout(Command::new("sh").args(["-c", &script]), Some(Duration::from_millis(200)))
+            .unwrap_err();
+        thread::sleep(Duration::from_millis(1500));
+        let survived = marker.exists();
+        let _ = fs::remove_dir_all(marker.parent().unwrap());
+        assert!(!survived);
+
+        let output = output_with_timeout(
+            Command::new("echo").arg("done"),
+            Some(Duration::from_secs(5)),
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+    }
+
+    #[test]
+    fn test_rust_stack_overflow_diagnosed() {
+        let stdout = "\nrunning 1 test\ntest tests::test_1 ... ";
+        let stderr = "   Compiling fib v0.1.0 (/tmp/fib)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.17s
+     Running unittests src/main.rs (target/debug/deps/fib-8a3c1d80a8c1303d)
+
+thread 'tests::test_1' (16429) has overflowed its stack
+fatal runtime error: stack overflow, aborting
+error: test failed, to rerun pass `--bin fib`
+
+Caused by:
+  process didn't exit successfully: `/tmp/fib/target/debug/deps/fib-8a3c1d80a8c1303d` (signal: 6, SIGABRT: process abort signal)";
+        let summary = parse_test_output(Language::Rs, stdout, stderr).unwrap();
+        assert_eq!(summary.total, 0);
+        assert_eq!(
+            summary.diagnosis.as_deref(),
+            Some("Your solution crashed (likely stack overflow/recursion).")
+        );
+    }
+
+    #[test]
+    fn test_missing_build_file_diagnosed() {
+        let stderr = "make: *** No rule to make target 'test_solution.c', needed by 'test_runner'.  Stop.";
+        let summary = parse_test_output(Language::C, "", stderr).unwrap();
+        assert_eq!(summary.total, 0);
+        assert_eq!(
+            summary.diagnosis.as_deref(),
+            Some("Build files are missing (test_solution.c). Re-run ./setup.sh to regenerate them.")
+        );
+
+        let no_makefile = parse_test_output(Language::Cpp, "", "make: *** No rule to make target 'test'.  Stop.")
+            .unwrap();
+        assert_eq!(
+            no_makefile.diagnosis.as_deref(),
+            Some("Build files are missing (Makefile). Re-run ./setup.sh to regenerate them.")
+        );
+
+        let header = "test_solution.cpp:7:10: fatal error: solution.hpp: No such file or directory\n    7 | #include \"solution.hpp\"\ncompilation terminated.\nmake: *** [Makefile:16: test_runner] Error 1";
+        let summary = parse_test_output(Language::Cpp, "", header).unwrap();
+        assert_eq!(
+            summary.diagnosis.as_deref(),
+            Some("Build files are missing (solution.hpp). Re-run ./setup.sh to regenerate them.")
+        );
+
+        let compile_error = "solution.c:4:5: error: expected ';' before 'return'\nmake: *** [Makefile:14: test_runner] Error 1";
+        assert_eq!(parse_test_output(Language::C, "", compile_error).unwrap().diagnosis, None);
+    }
+
+    #[test]
+    fn test_compile_errors_detected() {
+        let cargo = "   Compiling add v0.1.0 (/tmp/add)
+error[E0425]: cannot find value `c` in this scope
+ --> src/main.rs:2:9
+  |
+2 |     a + c
+  |         ^ help: a local variable with a similar name exists: `a`
+
+For more information about this error, try `rustc --explain E0425`.
+error: could not compile `add` (bin \"add\" test) due to 1 previous error";
+        let summary = parse_test_output(Language::Rs, "", cargo).unwrap();
+        assert_eq!(
+            summary.compile_error.as_deref(),
+            Some("error[E0425]: cannot find value `c` in this scope\n --> src/main.rs:2:9\n  |\n2 |     a + c\n  |         ^ help: a local variable with a similar name exists: `a`")
+        );
+
+        let gradle = "> Task :app:compileKotlin FAILED\ne: file:///p/app/src/main/kotlin/codle/App.kt:4:5 Unresolved reference: c\n\nFAILURE: Build failed with an exception.\n\nBUILD FAILED in 2s";
+        assert_eq!(
+            parse_test_output(Language::Kt, gradle, "").unwrap().compile_error.as_deref(),
+            Some("e: file:///p/app/src/main/kotlin/codle/App.kt:4:5 Unresolved reference: c")
+        );
+
+        let go = "# codle [codle.test]\n./solution.go:4:9: undefined: c\nFAIL\tcodle [build failed]";
+        assert_eq!(
+            parse_test_output(Language::Go, go, "").unwrap().compile_error.as_deref(),
+            Some("./solution.go:4:9: undefined: c")
+        );
+
+        // A crash or a failing test isn't a build failure.
+        let failing = "    solution_test.go:9: got 1, want 2\n--- FAIL: Test1 (0.00s)";
+        assert_eq!(parse_test_output(Language::Go, failing, "").unwrap().compile_error, None);
+
+        if !tools_installed("compile_errors_detected", Language::C.required_commands()) {
+            return;
+        }
+        let challenge = fixture_challenge(
+            "fn add(a: i32, b: i32) -> i32",
+            json!([{"input": {"a": 1, "b": 2}, "expected": 3}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let dir = scratch_dir("compile-error-c");
+        let options = ScaffoldOptions {
+            solution_body: Some("return a + c;".to_string()),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+        Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+        let summary = run_tests(Language::C, &dir, None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let errors = summary.compile_error.unwrap();
+        assert!(errors.starts_with("solution.c:"), "{}", errors);
+        assert!(errors.contains("error: 'c' undeclared"), "{}", errors);
+    }
+
+    #[test]
+    fn test_python_recursion_error_diagnosed() {
+        let stdout = "test_solution.py::test_1 FAILED                                          [100%]
+
+=================================== FAILURES ===================================
+____________________________________ test_1 ____________________________________
+E   RecursionError: maximum recursion depth exceeded
+!!! Recursion detected (same locals & position)
+=========================== short test summary info ============================
+FAILED test_solution.py::test_1 - RecursionError: maximum recursion depth exceeded
+============================== 1 failed in 0.05s ===============================";
+        let summary = parse_test_output(Language::Py, stdout, "").unwrap();
+        assert_eq!((summary.passed, summary.failed), (0, 1));
+        assert!(summary.diagnosis.is_some());
+
+        let clean = parse_test_output(Language::Py, "1 passed in 0.01s", "").unwrap();
+        assert_eq!(clean.diagnosis, None);
+    }
+
+    #[test]
+    fn test_named_case_sets_test_function_name() {
+        let sig = parse_signature("fn is_valid(s: String) -> bool").unwrap();
+        let tests = vec![
+            TestCase {
+                name: Some("Handles empty input!".to_string()),
+                input: json!({"s": ""}),
+                expected: json!(true),
+                unordered: false,
+            },
+            TestCase {
+                name: None,
+                input: json!({"s": "("}),
+                expected: json!(false),
+                unordered: false,
+            },
+        ];
+
+        let rust = rust::generate_rust_tests(&sig, &tests);
+        assert!(rust.contains("fn test_handles_empty_input()"));
+        assert!(rust.contains("fn test_2()"));
+
+        let python = python::generate_python_tests(&sig, &tests, false);
+        assert!(python.contains("def test_handles_empty_input():"));
+
+        let java = java::generate_java_tests(&sig, &tests);
+        assert!(java.contains("void test_handles_empty_input()"));
+        assert!(java.contains("void test2()"));
+
+        let c = c::generate_c_tests(&sig, &tests, false);
+        assert!(c.contains("Test 1 (handles_empty_input): PASS"));
+    }
+
+    #[test]
+    fn test_setup_script_has_no_carriage_returns() {
+        let dir = scratch_dir("setup-lf");
+        write_setup_script(&dir, "#!/bin/bash\r\nset -e\r\necho hi\r\n").unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(!script.contains('\r'));
+        assert!(script.starts_with("#!/bin/bash\nset -e\n"));
+    }
+
+    #[test]
+    fn test_build_tool_name() {
+        assert_eq!(build_tool_name("two_sum"), "two_sum");
+        assert_eq!(build_tool_name("TwoSum"), "twosum");
+        assert_eq!(build_tool_name("3sum"), "codle_3sum");
+        assert_eq!(build_tool_name("two-sum!"), "two_sum_");
+        assert_eq!(build_tool_name("test"), "codle_test");
+        assert_eq!(build_tool_name(""), "codle_");
+    }
+
+    #[test]
+    fn test_unsupported_type_rejected_for_c() {
+        let sig = parse_signature("fn transpose(grid: Vec<Vec<i32>>) -> Vec<Vec<i32>>").unwrap();
+        let err = check_language_support(&sig, Language::C).unwrap_err();
+        assert!(err.contains("Vec<Vec<i32>>"));
+        assert!(err.contains("Rust"));
+        assert!(check_language_support(&sig, Language::Rs).is_ok());
+        assert!(check_language_support(&sig, Language::Py).is_ok());
+    }
+
+    #[test]
+    fn test_c_tests_build_from_solution_source() {
+        let challenge = fixture_challenge(
+            "fn max_area(height: Vec<i32>) -> i32",
+            json!([{"input": {"height": [1, 2]}, "expected": 1}]),
+        );
+        let script = scaffold_setup_script("c-single-source", &challenge, Language::C);
+        assert!(script.contains("$(CC) $(CFLAGS) -DCODLE_TEST -o $(TEST_TARGET) $(SRC) $(TEST_SRC)"));
+        assert!(script.contains("#ifndef CODLE_TEST\nint main()"));
+        assert!(!heredoc_body(&script, "Makefile", "MAKEFILE").contains("solution_lib"));
+    }
+
+    #[test]
+    fn test_cpp_tests_build_from_solution_source() {
+        let challenge = fixture_challenge(
+            "fn max_area(height: Vec<i32>) -> i32",
+            json!([{"input": {"height": [1, 2]}, "expected": 1}]),
+        );
+        let script = scaffold_setup_script("cpp-single-source", &challenge, Language::Cpp);
+        assert!(script.contains("$(CXX) $(CXXFLAGS) -DCODLE_TEST -o $(TEST_TARGET) $(SRC) $(TEST_SRC)"));
+        assert!(script.contains("#ifndef CODLE_TEST\nint main()"));
+        assert!(!heredoc_body(&script, "Makefile", "MAKEFILE").contains("solution_lib"));
+    }
+
+    /// The tests link `solution.c` itself, so an edit to it is what `make
+    /// test` sees, with no second copy to fall out of date.
+    #[test]
+    fn test_editing_solution_changes_make_test() {
+        let challenge = fixture_challenge(
+            "fn add(a: i32, b: i32) -> i32",
+            json!([{"input": {"a": 1, "b": 2}, "expected": 3}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+
+        for lang in [Language::C, Language::Cpp] {
+            if !tools_installed("editing_solution_changes_make_test", lang.required_commands()) {
+                continue;
+            }
+            let dir = scratch_dir(&format!("single-source-{}", lang.extension()));
+            fs::write(dir.join(format!("solution_lib.{}", lang.extension())), "stale copy").unwrap();
+            let options = ScaffoldOptions {
+                solution_body: Some("return a - b;".to_string()),
+                ..ScaffoldOptions::default()
+            };
+            generate_scaffold(&challenge, &sig, lang, Difficulty::Easy, &dir, &options).unwrap();
+            let setup = Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+            assert!(setup.status.success());
+            let stale_copy_removed = !dir.join(format!("solution_lib.{}", lang.extension())).exists();
+
+            let before = run_tests(lang, &dir, None).unwrap();
+            let solution = dir.join(lang.solution_filename());
+            let fixed = fs::read_to_string(&solution).unwrap().replace("return a - b;", "return a + b;");
+            fs::write(&solution, fixed).unwrap();
+            let after = run_tests(lang, &dir, None).unwrap();
+            let _ = fs::remove_dir_all(&dir);
+
+            assert!(stale_copy_removed, "{:?}", lang);
+            assert_eq!((before.passed, before.failed), (0, 1), "{:?}", lang);
+            assert_eq!((after.passed, after.failed), (1, 0), "{:?}", lang);
+        }
+    }
+
+    #[test]
+    fn test_floats_rendered_per_language() {
+        let float_vec = RustType::Vec(Box::new(RustType::F64));
+        let cases = [
+            (Language::C, &float_vec, json!([1, 2, 3]), "{1.0, 2.0, 3.0}"),
+            (Language::Cpp, &float_vec, json!([1, 2.5, -3]), "{1.0, 2.5, -3.0}"),
+            (Language::Java, &float_vec, json!([1, 2, 3]), "new double[] {1.0, 2.0, 3.0}"),
+            (Language::Kt, &float_vec, json!([1, 2, 3]), "mutableListOf(1.0, 2.0, 3.0)"),
+            (Language::Java, &RustType::F64, json!(1e-30), "1e-30"),
+            (Language::Java, &RustType::F64, json!(-1e30), "-1e30"),
+            (Language::Java, &RustType::F64, json!(-2.5e-7), "-2.5e-7"),
+            (Language::Java, &RustType::F64, json!(0.001), "0.001"),
+            (Language::Kt, &float_vec, json!([1e-30, -1e30, 0]), "mutableListOf(1e-30, -1e30, 0.0)"),
+        ];
+        for (lang, ty, input, expected) in cases {
+            assert_eq!(render_value(&input, ty, lang), expected, "{:?} {}", lang, input);
+        }
+    }
+
+    #[test]
+    fn test_empty_expected_per_language() {
+        let challenge = fixture_challenge(
+            "fn find_duplicates(nums: Vec<i32>) -> Vec<i32>",
+            json!([{"input": {"nums": [1, 2, 3]}, "expected": []}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let cases = [
+            (Language::Rs, "assert_eq!(result, Vec::<i32>::new());"),
+            (Language::Py, "assert result == []"),
+            (Language::Kt, "assertEquals(mutableListOf<Int>(), result)"),
+            (Language::Java, "assertArrayEquals(new int[] {}, result);"),
+            (Language::C, "(void)result;"),
+            (Language::Cpp, "if (result == std::vector<int>{})"),
+            (Language::Go, "\texpected := []int{}\n"),
+            (Language::Go, "reflect.DeepEqual(result, expected)"),
+        ];
+        for (lang, assertion) in cases {
+            let code = generate_tests(&challenge, &sig, lang);
+            assert!(code.contains(assertion), "{:?} missing {:?}:\n{}", lang, assertion, code);
+        }
+        assert!(!generate_tests(&challenge, &sig, Language::C).contains("result == {}"));
+    }
+
+    #[test]
+    fn test_tuple_return_passes() {
+        let challenge = fixture_challenge(
+            "fn min_max(nums: Vec<i32>) -> (i32, i32)",
+            json!([
+                {"input": {"nums": [3, 1, 2]}, "expected": [1, 3]},
+                {"input": {"nums": [-5]}, "expected": [-5, -5]},
+            ]),
+        );
+        let bodies = [
+            (Language::Rs, "(*nums.iter().min().unwrap(), *nums.iter().max().unwrap())"),
+            (Language::Py, "return (min(nums), max(nums))"),
+            (
+                Language::Java,
+                "int lo = nums[0], hi = nums[0];\nfor (int n : nums) { lo = Math.min(lo, n); hi = Math.max(hi, n); }\nreturn new Pair(lo, hi);",
+            ),
+        ];
+        check_solutions("tuple-return", &challenge, &bodies);
+    }
+
+    #[test]
+    fn test_go_scaffold() {
+        let challenge = fixture_challenge(
+            "fn scale(nums: Vec<i64>, factor: f64) -> Vec<f64>",
+            json!([{"input": {"nums": [1, 2], "factor": 0.5}, "expected": [0.5, 1.0]}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert_eq!(translate_type(&sig.params[0].ty, Language::Go), "[]int64");
+        assert_eq!(render_value(&json!([1, 2]), &sig.params[0].ty, Language::Go), "[]int64{int64(1), int64(2)}");
+        assert_eq!(render_value(&json!("-inf"), &RustType::F64, Language::Go), "math.Inf(-1)");
+        assert!(check_language_support(&parse_signature("fn f() -> Option<i32>").unwrap(), Language::Go).is_err());
+
+        let dir = scratch_dir("go-scaffold");
+        generate_scaffold(&challenge, &sig, Language::Go, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+            .unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let solution = heredoc_body(&script, "solution.go", "SOLUTION");
+        assert!(solution.starts_with("package main\n\nimport \"fmt\"\n\nfunc scale(nums []int64, factor float64) []float64 {\n"));
+        assert!(solution.contains("\tresult := scale(nums, factor)\n\tfmt.Println(result)\n"));
+        let tests = heredoc_body(&script, "solution_test.go", "TESTS");
+        assert!(tests.starts_with("package main\n\nimport (\n\t\"math\"\n\t\"testing\"\n)\n\nfunc Test1(t *testing.T) {\n"));
+        assert!(tests.contains("math.Abs(result[i]-expected[i]) >= 1e-9"));
+        assert!(script.contains("cat > go.mod << 'GOMOD'\nmodule codle\n"));
+    }
+
+    #[test]
+    fn test_parse_go_output() {
+        let stdout = "=== RUN   Test1\n--- PASS: Test1 (0.00s)\n=== RUN   Test_empty\n    solution_test.go:12: got [1], want []\n--- FAIL: Test_empty (0.00s)\nFAIL\nFAIL\tcodle\t0.002s\n";
+        let summary = parse_test_output(Language::Go, stdout, "").unwrap();
+        assert_eq!((summary.passed, summary.failed, summary.total), (1, 1, 2));
+
+        let build_failed = "# codle [codle.test]\n./solution.go:4:2: undefined: x\nFAIL\tcodle [build failed]\n";
+        assert_eq!(parse_test_output(Language::Go, "", build_failed).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_javascript_scaffold() {
+        let challenge = fixture_challenge(
+            "fn first_even(nums: Vec<i32>) -> Option<i32>",
+            json!([
+                {"input": {"nums": [1, 4]}, "expected": 4},
+                {"input": {"nums": [1]}, "expected": null},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert_eq!(translate_type(&sig.return_type, Language::Js), "number | null");
+        let tests = javascript::generate_javascript_tests(&sig, &challenge.tests);
+        assert!(tests.starts_with("const { first_even } = require(\"./solution\");\n\ntest(\"test_1\", () => {\n"));
+        assert!(tests.contains("    const nums = [1, 4];\n    const result = first_even(nums);\n    expect(result).toBe(4);\n"));
+        assert!(tests.contains("    expect(result).toBeNull();\n"));
+
+        if !tools_installed("javascript_scaffold", &["node"]) {
+            return;
+        }
+        let dir = scratch_dir("js-scaffold");
+        let options = ScaffoldOptions {
+            solution_body: Some("return nums.find((x) => x % 2 === 0) ?? null;".to_string()),
+            ..ScaffoldOptions::default()
+        };
+        generate_scaffold(&challenge, &sig, Language::Js, Difficulty::Easy, &dir, &options).unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        fs::write(dir.join("solution.js"), heredoc_body(&script, "solution.js", "SOLUTION")).unwrap();
+        fs::write(dir.join("solution.test.js"), heredoc_body(&script, "solution.test.js", "TESTS")).unwrap();
+        let run = Command::new("node").arg("solution.js").current_dir(&dir).output().unwrap();
+        let check = Command::new("node").args(["--check", "solution.test.js"]).current_dir(&dir).output().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+        assert_eq!(String::from_utf8_lossy(&run.stdout), "4\n");
+        assert!(check.status.success());
+    }
+
+    #[test]
+    fn test_parse_jest_output() {
+        let stderr = "FAIL ./solution.test.js\n  \u{2715} test_2 (3 ms)\n\nTests:       1 failed, 2 passed, 3 total\nSnapshots:   0 total\n";
+        let summary = parse_test_output(Language::Js, "", stderr).unwrap();
+        assert_eq!((summary.passed, summary.failed, summary.total), (2, 1, 3));
+
+        let stack = "RangeError: Maximum call stack size exceeded\nTests:       1 failed, 1 total\n";
+        assert!(parse_test_output(Language::Js, "", stack).unwrap().diagnosis.is_some());
+    }
+
+    #[test]
+    fn test_typescript_scaffold() {
+        let challenge = fixture_challenge(
+            "fn top(scores: Vec<Option<f64>>, names: Vec<String>) -> Option<f64>",
+            json!([
+                {"input": {"scores": [0.5, null], "names": []}, "expected": 0.5},
+                {"input": {"scores": [], "names": ["a"]}, "expected": null},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert_eq!(translate_type(&sig.params[0].ty, Language::Ts), "(number | null)[]");
+        assert_eq!(translate_type(&sig.params[0].ty, Language::Js), "(number | null)[]");
+        assert_eq!(idiomatic_function_name("top_k", Language::Ts), "topK");
+
+        let dir = scratch_dir("ts-scaffold");
+        generate_scaffold(&challenge, &sig, Language::Ts, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+            .unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let solution = heredoc_body(&script, "solution.ts", "SOLUTION");
+        assert!(solution.starts_with("export function top(scores: (number | null)[], names: string[]): number | null {\n"));
+        let tests = heredoc_body(&script, "solution.test.ts", "TESTS");
+        assert!(tests.starts_with("import { top } from \"./solution\";\n\ntest(\"test_1\", () => {\n"));
+        assert!(tests.contains("    const scores: (number | null)[] = [0.5, null];\n    const names: string[] = [];\n"));
+        assert!(tests.contains("    expect(Math.abs(result! - 0.5)).toBeLessThan(1e-9);\n"));
+        assert!(tests.contains("    expect(result).toBeNull();\n"));
+        assert!(script.contains("\"preset\": \"ts-jest\""));
+        assert!(script.contains("cat > tsconfig.json << 'TSCONFIG'\n"));
+
+        let tsc = "solution.ts(2,5): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+        let summary = parse_test_output(Language::Ts, tsc, "").unwrap();
+        assert_eq!(summary.compile_error.as_deref(), Some(tsc.trim_end()));
+    }
+
+    #[test]
+    fn test_csharp_scaffold() {
+        let challenge = fixture_challenge(
+            "fn dedupe(nums: &mut Vec<i64>, label: &str) -> Option<f64>",
+            json!([
+                {"input": {"nums": [1, 1], "label": "a\tb"}, "expected": 0.5},
+                {"input": {"nums": [], "label": ""}, "expected": null},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        assert_eq!(translate_type(&sig.params[0].ty, Language::Cs), "List<long>");
+        assert_eq!(translate_type(&sig.return_type, Language::Cs), "double?");
+        assert_eq!(render_value(&json!([1, 2]), &sig.params[0].ty, Language::Cs), "new List<long> { 1L, 2L }");
+        assert_eq!(render_value(&json!("\u{1}"), &RustType::Char, Language::Cs), "'\\u0001'");
+        assert_eq!(render_value(&json!("-inf"), &RustType::F32, Language::Cs), "float.NegativeInfinity");
+        assert_eq!(idiomatic_function_name("two_sum", Language::Cs), "TwoSum");
+
+        let dir = scratch_dir("cs-scaffold");
+        generate_scaffold(&challenge, &sig, Language::Cs, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+            .unwrap();
+        let script = fs::read_to_string(dir.join("setup.sh")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let program = heredoc_body(&script, "Solution/Program.cs", "SOLUTION");
+        assert!(program.contains("    public static double? dedupe(ref List<long> nums, string label)\n    {\n        throw new NotImplementedException();\n"));
+        assert!(program.contains("        var result = Program.dedupe(ref nums, label);\n        Console.WriteLine(JsonSerializer.Serialize(result));\n"));
+        let tests = heredoc_body(&script, "Tests/SolutionTests.cs", "TESTS");
+        assert!(tests.contains("public class SolutionTests\n{\n    [Fact]\n    public void Test1()\n    {\n        List<long> nums = new List<long> { 1L, 1L };\n        string label = \"a\\tb\";\n"));
+        assert!(tests.contains("Assert.True(Math.Abs(result!.Value - 0.5) < 1e-9"));
+        assert!(tests.contains("        List<long> nums = new List<long>();\n"));
+        assert!(tests.contains("        Assert.Null(result);\n"));
+        assert!(script.contains("<PackageReference Include=\"xunit\""));
+        assert!(script.contains("dotnet sln add Solution/Solution.csproj Tests/Tests.csproj\n"));
+    }
+
+    #[test]
+    fn test_parse_dotnet_output() {
+        let stdout = "  Failed SolutionTests.Test2 [4 ms]\n  Error Message:\n   Assert.Equal() Failure\n\n\
+                      Failed!  - Failed:     1, Passed:     2, Skipped:     0, Total:     3, Duration: 12 ms - Tests.dll (net8.0)\n";
+        let summary = parse_test_output(Language::Cs, stdout, "").unwrap();
+        assert_eq!((summary.passed, summary.failed, summary.total), (2, 1, 3));
+
+        let build = "/tmp/p/Solution/Program.cs(7,9): error CS0103: The name 'x' does not exist in the current context [/tmp/p/Solution/Solution.csproj]\n\nBuild FAILED.\n";
+        let summary = parse_test_output(Language::Cs, build, "").unwrap();
+        assert_eq!(summary.total, 0);
+        assert!(summary.compile_error.unwrap().contains("error CS0103"));
+    }
+
+    #[test]
+    fn test_failed_tests_listed() {
+        let cases = [
+            (Language::Rs, "test tests::test_1 ... ok\ntest tests::test_empty ... FAILED\ntest tests::test_3 ... FAILED\n"),
+            (
+                Language::Py,
+                "test_solution.py::test_1 PASSED [ 33%]\ntest_solution.py::test_empty FAILED [ 66%]\n\
+                 FAILED test_solution.py::test_empty - assert 3 == 4\nFAILED test_solution.py::test_3 - assert 1 == 2",
+            ),
+            (Language::Java, "AppTest > test1() PASSED\nAppTest > test_empty() FAILED\nAppTest > test3() FAILED"),
+            (Language::Go, "--- PASS: Test1 (0.00s)\n--- FAIL: Test_empty (0.00s)\n--- FAIL: Test3 (0.00s)\nFAIL"),
+            (Language::Js, "  \u{2713} test_1 (2 ms)\n  \u{2715} test_empty (3 ms)\n  \u{2715} test_3 (1 ms)"),
+            (Language::Ts, "  \u{2713} test_1 (2 ms)\n  \u{2715} test_empty (3 ms)\n  \u{2715} test_3 (1 ms)"),
+            (Language::Cs, "  Failed SolutionTests.Test_empty [3 ms]\n  Error Message:\n  Failed SolutionTests.Test3 [1 ms]"),
+        ];
+        for (lang, output) in cases {
+            assert_eq!(failed_tests(lang, output), vec!["empty", "3"], "{:?}", lang);
+        }
+        assert_eq!(
+            failed_tests(Language::C, "Test 1: PASS\nTest 2 (empty): FAIL (expected 1, got 2)\nTest 3: FAIL\n2 failed"),
+            vec!["2 (empty)", "3"]
+        );
+        assert!(failed_tests(Language::Rs, "test result: ok. 3 passed; 0 failed").is_empty());
+    }
+
+    #[test]
+    fn test_failures_show_expected_and_got() {
+        let challenge = fixture_challenge(
+            "fn rev(nums: Vec<i32>, names: &mut Vec<String>) -> Vec<i32>",
+            json!([{"input": {"nums": [1, 3], "names": ["a"]}, "expected": [3, 1]}]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        let java = java::generate_java_tests(&sig, &challenge.tests);
+        assert!(java.contains("assertArrayEquals(new int[] {3, 1}, result);"));
+        assert!(scaffold_setup_script("shown-java", &challenge, Language::Java).contains("exceptionFormat \"full\""));
+
+        if !tools_installed("failures_show_expected_and_got", &["gcc", "g++", "make"]) {
+            return;
+        }
+        for (lang, body) in [
+            (Language::C, "(void)names; (void)names_len; (void)nums_len; return nums;"),
+            (Language::Cpp, "(void)names; return nums;"),
+        ] {
+            let dir = scratch_dir(&format!("shown-{}", lang.extension()));
+            let options = ScaffoldOptions { solution_body: Some(body.to_string()), ..ScaffoldOptions::default() };
+            generate_scaffold(&challenge, &sig, lang, Difficulty::Easy, &dir, &options).unwrap();
+            Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+            let summary = run_tests(lang, &dir, None).unwrap();
+            let _ = fs::remove_dir_all(&dir);
+            assert_eq!(summary.failed, 1, "{:?}: {}", lang, summary.output);
+            assert!(summary.output.contains("Test 1: FAIL\n  expected: [3, 1]\n  got:      [1, 3]\n"), "{:?}: {}", lang, summary.output);
+        }
+    }
+
+    #[test]
+    fn test_c_string_and_bool_failures_show_values() {
+        let shout = fixture_challenge("fn shout(s: String) -> String", json!([{"input": {"s": "hi"}, "expected": "HI"}]));
+        let even = fixture_challenge("fn is_even(n: i32) -> bool", json!([{"input": {"n": 4}, "expected": true}]));
+        let shout_tests = c::generate_c_tests(&parse_signature(&shout.function_signature).unwrap(), &shout.tests, false);
+        assert!(shout_tests.contains("if (result != NULL && strcmp(result, \"HI\") == 0)"));
+
+        if !tools_installed("c_string_and_bool_failures_show_values", Language::C.required_commands()) {
+            return;
+        }
+        for (name, challenge, body, line) in [
+            ("shout", &shout, "(void)s; return \"hi\";", "Test 1: FAIL (expected \"HI\", got \"hi\")"),
+            ("shout-null", &shout, "(void)s; return NULL;", "Test 1: FAIL (expected \"HI\", got \"(null)\")"),
+            ("even", &even, "return n % 2 != 0;", "Test 1: FAIL (expected true, got false)"),
+        ] {
+            let dir = scratch_dir(&format!("c-scalar-{}", name));
+            let sig = parse_signature(&challenge.function_signature).unwrap();
+            let options = ScaffoldOptions { solution_body: Some(body.to_string()), ..ScaffoldOptions::default() };
+            generate_scaffold(challenge, &sig, Language::C, Difficulty::Easy, &dir, &options).unwrap();
+            Command::new("bash").arg("setup.sh").current_dir(&dir).output().unwrap();
+            let summary = run_tests(Language::C, &dir, None).unwrap();
+            let _ = fs::remove_dir_all(&dir);
+            assert!(summary.output.contains(line), "{}: {}", name, summary.output);
+        }
+    }
+
+    #[test]
+    fn test_missing_input_rejected_before_generating() {
+        let challenge = fixture_challenge(
+            "fn add(a: i32, b: i32) -> i32",
+            json!([
+                {"input": {"a": 1, "b": 2}, "expected": 3},
+                {"name": "no b", "input": {"a": 1}, "expected": 1},
+            ]),
+        );
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        for lang in Language::all() {
+            let dir = scratch_dir(&format!("missing-input-{}", lang.extension()));
+            let err = generate_scaffold(&challenge, &sig, *lang, Difficulty::Easy, &dir, &ScaffoldOptions::default())
+                .unwrap_err();
+            assert_eq!(err, "test inputs don't match the parameters of add:\n  test 2 (no b): missing b", "{:?}", lang);
+            assert!(!dir.join("setup.sh").exists());
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+}
@@ -6,18 +6,35 @@ use crate::models::{
 };
 use super::{
     write_setup_script, require_commands, escape_for_heredoc,
-    is_void_with_mut_ref, get_first_test_inputs, unwrap_mut_ref,
+    is_void_with_mut_ref, get_first_test_inputs, largest_test_inputs, unwrap_ref, SpecialFloat,
+    ScaffoldOptions,
 };
 
 pub(super) fn translate_type_py(ty: &RustType) -> String {
     match ty {
-        RustType::I32 | RustType::Usize => "int".to_string(),
-        RustType::F64 => "float".to_string(),
+        RustType::I8
+        | RustType::I16
+        | RustType::I32
+        | RustType::I64
+        | RustType::U8
+        | RustType::U16
+        | RustType::U32
+        | RustType::U64
+        | RustType::Usize => "int".to_string(),
+        RustType::F32 | RustType::F64 => "float".to_string(),
         RustType::Bool => "bool".to_string(),
-        RustType::String => "str".to_string(),
+        RustType::String | RustType::Str => "str".to_string(),
         RustType::Char => "str".to_string(),
         RustType::Vec(inner) => format!("list[{}]", translate_type_py(inner)),
-        RustType::MutRef(inner) => translate_type_py(inner),
+        RustType::Option(inner) => format!("{} | None", translate_type_py(inner)),
+        RustType::Map(key, value) => {
+            format!("dict[{}, {}]", translate_type_py(key), translate_type_py(value))
+        }
+        RustType::Tuple(elems) => {
+            let elems: Vec<String> = elems.iter().map(translate_type_py).collect();
+            format!("tuple[{}]", elems.join(", "))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => translate_type_py(inner),
         RustType::Void => "None".to_string(),
     }
 }
@@ -25,14 +42,15 @@ pub(super) fn translate_type_py(ty: &RustType) -> String {
 pub(super) fn render_value_py(value: &Value, ty: &RustType) -> String {
     match ty {
         RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
-        RustType::F64 => {
-            let n = value.as_f64().unwrap_or(0.0);
-            if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                format!("{}", n)
-            }
+        RustType::I8 | RustType::I16 | RustType::I64 | RustType::U8 | RustType::U16 | RustType::U32 | RustType::U64 => {
+            super::integer_literal(value)
         }
+        RustType::F32 | RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => "float(\"inf\")".to_string(),
+            Some(SpecialFloat::NegInfinity) => "float(\"-inf\")".to_string(),
+            Some(SpecialFloat::NaN) => "float(\"nan\")".to_string(),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
         RustType::Bool => {
             if value.as_bool().unwrap_or(false) {
                 "True".to_string()
@@ -40,8 +58,11 @@ pub(super) fn render_value_py(value: &Value, ty: &RustType) -> String {
                 "False".to_string()
             }
         }
-        RustType::String => format!("\"{}\"", value.as_str().unwrap_or("")),
-        RustType::Char => format!("\"{}\"", value.as_str().unwrap_or("?")),
+        RustType::String | RustType::Str => super::string_literal(value, Language::Py),
+        RustType::Char => {
+            let c = value.as_str().and_then(|s| s.chars().next()).unwrap_or('?');
+            super::string_literal(&Value::String(c.to_string()), Language::Py)
+        }
         RustType::Vec(inner) => {
             if let Some(arr) = value.as_array() {
                 let items: Vec<String> = arr.iter().map(|v| render_value_py(v, inner)).collect();
@@ -50,7 +71,23 @@ pub(super) fn render_value_py(value: &Value, ty: &RustType) -> String {
                 "[]".to_string()
             }
         }
-        RustType::MutRef(inner) => render_value_py(value, inner),
+        RustType::Option(_) if value.is_null() => "None".to_string(),
+        RustType::Option(inner) => render_value_py(value, inner),
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("{}: {}", render_value_py(&k, key), render_value_py(v, inner)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        RustType::Tuple(elems) => {
+            let items: Vec<String> = super::tuple_items(value, elems)
+                .iter()
+                .map(|(v, ty)| render_value_py(v, ty))
+                .collect();
+            format!("({})", items.join(", "))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_py(value, inner),
         RustType::Void => "None".to_string(),
     }
 }
@@ -60,6 +97,7 @@ pub(super) fn generate_python(
     sig: &FunctionSignature,
     difficulty: Difficulty,
     output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
     let params_str: Vec<String> = sig
         .params
@@ -68,7 +106,7 @@ pub(super) fn generate_python(
             format!(
                 "{}: {}",
                 p.name,
-                super::translate_type(unwrap_mut_ref(&p.ty), Language::Py)
+                super::translate_type(unwrap_ref(&p.ty), Language::Py)
             )
         })
         .collect();
@@ -82,7 +120,7 @@ pub(super) fn generate_python(
     if let Some(inputs) = get_first_test_inputs(challenge) {
         if is_void_with_mut_ref(sig) {
             for p in &sig.params {
-                let inner_ty = unwrap_mut_ref(&p.ty);
+                let inner_ty = unwrap_ref(&p.ty);
                 if let Some(val) = inputs.get(&p.name) {
                     main_body.push_str(&format!(
                         "    {} = {}\n",
@@ -103,7 +141,7 @@ pub(super) fn generate_python(
                     main_body.push_str(&format!(
                         "    {} = {}\n",
                         p.name,
-                        super::render_value(val, unwrap_mut_ref(&p.ty), Language::Py)
+                        super::render_value(val, unwrap_ref(&p.ty), Language::Py)
                     ));
                     args.push(p.name.clone());
                 }
@@ -119,80 +157,173 @@ pub(super) fn generate_python(
 
     let solution_py = format!(
         r#"def {}({}){}:
-    pass
-
+{}
 
 if __name__ == "__main__":
 {}"#,
         sig.name,
         params_str.join(", "),
         ret_hint,
+        options.body_or("    ", "    pass\n"),
         main_body,
     );
 
-    let tests_code = generate_python_tests(sig, &challenge.tests);
+    let tests_code = generate_python_tests(sig, &challenge.tests, challenge.chars_as_string);
 
-    let metadata = ProjectMetadata::new(
+    let mut metadata = ProjectMetadata::new(
         challenge.name.clone(),
         Language::Py,
         difficulty,
         sig.name.clone(),
-        Some(chrono::Local::now().to_rfc3339()),
+        options.initialized_at(),
         challenge.difficulty,
     );
+    metadata.challenge_index = options.challenge_index;
     let metadata_content = metadata_json(&metadata);
 
     let setup_sh = format!(
         r#"#!/bin/bash
 set -e
 
+echo "[codle] step: checking required tools"
 {}
 
+echo "[codle] step: creating virtualenv"
 python3 -m venv venv
 source venv/bin/activate
 
+echo "[codle] step: writing requirements.txt"
 cat > requirements.txt << 'EOF'
 pytest
 EOF
 
+echo "[codle] step: installing Python dependencies"
 pip install -r requirements.txt
 
+echo "[codle] step: writing solution.py"
 cat > solution.py << 'SOLUTION'
 {}
 SOLUTION
 
+echo "[codle] step: writing test_solution.py"
 cat > test_solution.py << 'TESTS'
 {}
 TESTS
 
+echo "[codle] step: writing .codle.json"
 cat > .codle.json << 'METADATA'
 {}
 METADATA
 
 echo "Run: source venv/bin/activate && python solution.py"
 echo "Test: source venv/bin/activate && pytest test_solution.py -v"
-"#,
-        require_commands(&["python3", "pip"]),
+{}"#,
+        require_commands(Language::Py.required_commands()),
         escape_for_heredoc(&solution_py),
         escape_for_heredoc(&tests_code),
-        metadata_content
+        metadata_content,
+        bench_section(challenge, sig, options)
     );
 
     write_setup_script(output_dir, &setup_sh)
 }
 
-pub(super) fn generate_python_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+fn is_char_vec(ty: &RustType) -> bool {
+    matches!(ty, RustType::Vec(inner) if **inner == RustType::Char)
+}
+
+/// A `Vec<char>` expected value as one Python string literal, e.g. `"olleh"`.
+fn joined_chars(value: &Value) -> String {
+    let joined: String = value
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    render_value_py(&Value::String(joined), &RustType::String)
+}
+
+/// The setup.sh step writing bench_solution.py, for `--with-bench`.
+fn bench_section(challenge: &Challenge, sig: &FunctionSignature, options: &ScaffoldOptions) -> String {
+    if !options.with_bench {
+        return String::new();
+    }
+
+    format!(
+        r#"
+echo "[codle] step: writing bench_solution.py"
+cat > bench_solution.py << 'BENCH'
+{}
+BENCH
+
+echo "Bench: source venv/bin/activate && python bench_solution.py"
+"#,
+        escape_for_heredoc(&generate_python_bench(challenge, sig))
+    )
+}
+
+/// `timeit` loop over the largest test input. The inputs are rebuilt on every
+/// call so an in-place solution never sees its own output.
+pub(super) fn generate_python_bench(challenge: &Challenge, sig: &FunctionSignature) -> String {
+    let inputs = largest_test_inputs(challenge);
+    let mut body = String::new();
+    for p in &sig.params {
+        let value = inputs.and_then(|i| i.get(&p.name)).cloned().unwrap_or(Value::Null);
+        body.push_str(&format!(
+            "    {} = {}\n",
+            p.name,
+            super::render_value(&value, unwrap_ref(&p.ty), Language::Py)
+        ));
+    }
+    let args: Vec<String> = sig.params.iter().map(|p| p.name.clone()).collect();
+    body.push_str(&format!("    return {}({})\n", sig.name, args.join(", ")));
+
+    format!(
+        r#"import timeit
+
+from solution import {name}
+
+RUNS = 1000
+
+
+def run():
+{body}
+
+if __name__ == "__main__":
+    total = timeit.timeit(run, number=RUNS)
+    print(f"{name}: {{total / RUNS * 1e6:.2f}} us per call over {{RUNS}} runs")"#,
+        name = sig.name,
+        body = body,
+    )
+}
+
+/// With `chars_as_string`, `Vec<char>` results are compared after `"".join`, so
+/// a solution may return a `str` instead of a list (or instead of mutating its
+/// list argument in place).
+pub(super) fn generate_python_tests(
+    sig: &FunctionSignature,
+    tests: &[TestCase],
+    chars_as_string: bool,
+) -> String {
     let mut test_fns = Vec::new();
-    test_fns.push(format!("from solution import {}\n", sig.name));
+    let mut imports = String::new();
+    if tests.iter().any(|t| super::expects_nan(&sig.return_type, &t.expected)) {
+        imports.push_str("import math\n");
+    }
+    let approximate = |ty: &RustType| {
+        super::is_float_vec(ty) || tests.iter().any(|t| super::compares_approximately(ty, &t.expected))
+    };
+    if super::checked_types(sig).into_iter().any(approximate) {
+        imports.push_str("import pytest\n");
+    }
+    test_fns.push(format!("{}from solution import {}\n", imports, sig.name));
 
     for (i, test) in tests.iter().enumerate() {
-        let test_num = i + 1;
+        let test_name = super::test_fn_suffix(test, i);
         let mut body = String::new();
 
         if let Some(inputs) = test.input.as_object() {
             if is_void_with_mut_ref(sig) {
                 for p in &sig.params {
-                    let inner_ty = unwrap_mut_ref(&p.ty);
+                    let inner_ty = unwrap_ref(&p.ty);
                     if let Some(val) = inputs.get(&p.name) {
                         body.push_str(&format!(
                             "    {} = {}\n",
@@ -202,15 +333,29 @@ pub(super) fn generate_python_tests(sig: &FunctionSignature, tests: &[TestCase])
                     }
                 }
                 let call_args: Vec<String> = sig.params.iter().map(|p| p.name.clone()).collect();
-                body.push_str(&format!("    {}({})\n", sig.name, call_args.join(", ")));
-                if let Some(p) = sig
-                    .params
-                    .iter()
-                    .find(|p| matches!(&p.ty, RustType::MutRef(_)))
-                {
-                    let inner = unwrap_mut_ref(&p.ty);
-                    let expected = super::render_value(&test.expected, inner, Language::Py);
-                    body.push_str(&format!("    assert {} == {}\n", p.name, expected));
+                let outputs = super::mut_ref_outputs(sig, test);
+                match outputs.as_slice() {
+                    [(p, output)] if chars_as_string && is_char_vec(unwrap_ref(&p.ty)) => {
+                        body.push_str(&format!(
+                            "    result = {}({})\n",
+                            sig.name,
+                            call_args.join(", ")
+                        ));
+                        body.push_str(&format!(
+                            "    actual = {} if result is None else result\n",
+                            p.name
+                        ));
+                        body.push_str(&format!(
+                            "    assert \"\".join(actual) == {}\n",
+                            joined_chars(&output.expected)
+                        ));
+                    }
+                    outputs => {
+                        body.push_str(&format!("    {}({})\n", sig.name, call_args.join(", ")));
+                        for (p, output) in outputs {
+                            body.push_str(&assertion(&p.name, output, unwrap_ref(&p.ty)));
+                        }
+                    }
                 }
             } else {
                 let mut args = Vec::new();
@@ -219,7 +364,7 @@ pub(super) fn generate_python_tests(sig: &FunctionSignature, tests: &[TestCase])
                         body.push_str(&format!(
                             "    {} = {}\n",
                             p.name,
-                            super::render_value(val, unwrap_mut_ref(&p.ty), Language::Py)
+                            super::render_value(val, unwrap_ref(&p.ty), Language::Py)
                         ));
                         args.push(p.name.clone());
                     }
@@ -229,8 +374,18 @@ pub(super) fn generate_python_tests(sig: &FunctionSignature, tests: &[TestCase])
                     sig.name,
                     args.join(", ")
                 ));
-                let expected = super::render_value(&test.expected, &sig.return_type, Language::Py);
-                body.push_str(&format!("    assert result == {}\n", expected));
+                if super::expects_nan(&sig.return_type, &test.expected) {
+                    body.push_str("    assert math.isnan(result)\n");
+                } else if chars_as_string && is_char_vec(&sig.return_type) {
+                    body.push_str(&format!(
+                        "    assert \"\".join(result) == {}\n",
+                        joined_chars(&test.expected)
+                    ));
+                } else if test.expected.is_null() {
+                    body.push_str("    assert result is None\n");
+                } else {
+                    body.push_str(&assertion("result", test, &sig.return_type));
+                }
             }
         }
 
@@ -238,49 +393,174 @@ pub(super) fn generate_python_tests(sig: &FunctionSignature, tests: &[TestCase])
             r#"
 def test_{}():
 {}"#,
-            test_num, body
+            test_name, body
         ));
     }
 
     test_fns.join("\n")
 }
 
-pub(super) fn parse_pytest_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
-    let mut passed = 0;
-    let mut failed = 0;
-
-    for line in combined.lines() {
-        if line.contains("passed") || line.contains("failed") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            for (i, part) in parts.iter().enumerate() {
-                if *part == "passed" && i > 0 {
-                    if let Ok(n) = parts[i - 1].parse::<usize>() {
-                        passed = n;
-                    }
-                }
-                if *part == "failed" && i > 0 {
-                    if let Ok(n) = parts[i - 1].parse::<usize>() {
-                        failed = n;
-                    }
-                }
-            }
-        }
+/// Wraps a float or float list in `pytest.approx` so it's compared within
+/// `FLOAT_EPSILON`, element by element for a list.
+/// `assert actual == expected`, comparing sorted copies for an unordered
+/// list.
+fn assertion(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    if super::compares_unordered(test, ty) {
+        let expected = format!("sorted({})", render_value_py(&test.expected, ty));
+        format!("    assert sorted({}) == {}\n", actual, approx(ty, &test.expected, expected))
+    } else {
+        let expected = render_value_py(&test.expected, ty);
+        format!("    assert {} == {}\n", actual, approx(ty, &test.expected, expected))
+    }
+}
+
+/// `expected`, the rendering of `value`, wrapped to match within
+/// `float_epsilon` if `ty` is a float type.
+fn approx(ty: &RustType, value: &Value, expected: String) -> String {
+    if super::compares_approximately(ty, value) || super::is_float_vec(ty) {
+        format!("pytest.approx({}, abs={})", expected, super::float_epsilon(ty))
+    } else {
+        expected
     }
+}
 
-    if passed == 0 && failed == 0 {
-        for line in combined.lines() {
-            if line.contains("PASSED") {
-                passed += 1;
-            } else if line.contains("FAILED") {
-                failed += 1;
+/// Reads pytest's closing `==== 1 failed, 3 passed, 1 skipped in 0.12s ====`
+/// line. Errors in setup or teardown count as failures; skipped and xfailed
+/// tests count as neither. A run interrupted by a collection error ran no
+/// tests, which leaves the total at 0 so the error is reported as a build
+/// failure. Without a summary line, `-v`'s PASSED and FAILED markers are
+/// counted instead.
+pub(super) fn parse_pytest_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
+    let (passed, failed) = match summary_counts(combined) {
+        Some(counts) if combined.contains("during collection") => (counts.passed, counts.failed),
+        Some(counts) => (counts.passed, counts.failed + counts.errors),
+        None => {
+            let mut passed = 0;
+            let mut failed = 0;
+            for line in combined.lines() {
+                if line.contains("PASSED") {
+                    passed += 1;
+                } else if line.contains("FAILED") {
+                    failed += 1;
+                }
             }
+            (passed, failed)
         }
-    }
+    };
 
     Ok(super::TestSummary {
         passed,
         failed,
         total: passed + failed,
         output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
     })
 }
+
+/// The outcome counts on pytest's summary line.
+#[derive(Debug, Default, PartialEq)]
+struct PytestCounts {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    errors: usize,
+}
+
+/// Counts from the last `=`-framed line that ends in a duration, e.g.
+/// `= 2 passed, 1 error in 0.05s =`, or `None` if there isn't one. Section
+/// headers like `==== FAILURES ====` are framed the same way but have no
+/// duration.
+fn summary_counts(output: &str) -> Option<PytestCounts> {
+    let line = output.lines().rev().find_map(|line| {
+        let inner = line.trim().strip_prefix('=')?.strip_suffix('=')?.trim_matches('=').trim();
+        let (counts, duration) = inner.rsplit_once(" in ")?;
+        duration.split_whitespace().next()?.trim_end_matches('s').parse::<f64>().ok()?;
+        Some(counts)
+    })?;
+
+    let mut counts = PytestCounts::default();
+    for item in line.split(',') {
+        let mut words = item.split_whitespace();
+        let (Some(n), Some(outcome)) = (words.next(), words.next()) else { continue };
+        let Ok(n) = n.parse::<usize>() else { continue };
+        match outcome {
+            "passed" | "xpassed" => counts.passed += n,
+            "failed" => counts.failed += n,
+            "skipped" | "xfailed" => counts.skipped += n,
+            "error" | "errors" => counts.errors += n,
+            _ => {}
+        }
+    }
+    Some(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(output: &str) -> (usize, usize, usize) {
+        let summary = parse_pytest_output(output, "", output).unwrap();
+        (summary.passed, summary.failed, summary.total)
+    }
+
+    #[test]
+    fn test_summary_line_counts() {
+        assert_eq!(counts("collected 7 items\n\ntest_solution.py .....ss\n\n===== 5 passed, 2 skipped in 0.12s ====="), (5, 0, 5));
+        assert_eq!(
+            counts("===== 1 failed, 3 passed, 1 xfailed, 2 warnings in 1.52s (0:00:01) ====="),
+            (3, 1, 4)
+        );
+        assert_eq!(
+            summary_counts("= 2 passed, 1 skipped, 1 error in 0.05s ="),
+            Some(PytestCounts { passed: 2, failed: 0, skipped: 1, errors: 1 })
+        );
+    }
+
+    #[test]
+    fn test_errors_count_as_failures() {
+        let output = "==================================== ERRORS ====================================
+____________________________ ERROR at setup of test_1 ____________________________
+E       fixture 'data' not found
+=========================== short test summary info ============================
+ERROR test_solution.py::test_1
+========================= 2 passed, 1 error in 0.03s ==========================";
+        assert_eq!(counts(output), (2, 1, 3));
+    }
+
+    #[test]
+    fn test_collection_error_runs_no_tests() {
+        let output = "==================================== ERRORS ====================================
+______________________ ERROR collecting test_solution.py _______________________
+E     File \"/tmp/p/solution.py\", line 2
+E       return a +
+E                 ^
+E   SyntaxError: invalid syntax
+=========================== short test summary info ============================
+ERROR test_solution.py
+!!!!!!!!!!!!!!!!!!!! Interrupted: 1 error during collection !!!!!!!!!!!!!!!!!!!!
+=============================== 1 error in 0.08s ===============================";
+        assert_eq!(counts(output), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_verbose_markers_not_double_counted() {
+        let output = "test_solution.py::test_1 PASSED                                          [ 33%]
+test_solution.py::test_2 FAILED                                          [ 66%]
+test_solution.py::test_3 SKIPPED (slow)                                  [100%]
+
+=================================== FAILURES ===================================
+___________________________________ test_2 ____________________________________
+E       assert 3 == 4
+=========================== short test summary info ============================
+FAILED test_solution.py::test_2 - assert 3 == 4
+==================== 1 failed, 1 passed, 1 skipped in 0.04s ====================";
+        assert_eq!(counts(output), (1, 1, 2));
+    }
+
+    #[test]
+    fn test_markers_counted_without_summary() {
+        let output = "test_solution.py::test_1 PASSED\ntest_solution.py::test_2 PASSED\ntest_solution.py::test_3 FAILED";
+        assert_eq!(counts(output), (2, 1, 3));
+    }
+}
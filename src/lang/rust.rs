@@ -5,60 +5,105 @@ use crate::models::{
     TestCase, metadata_json,
 };
 use super::{
-    write_setup_script, require_commands, escape_for_heredoc,
-    is_void_with_mut_ref, get_first_test_inputs, unwrap_mut_ref,
+    write_setup_script, require_commands, escape_for_heredoc, build_tool_name,
+    is_void_with_mut_ref, get_first_test_inputs, largest_test_inputs, unwrap_ref, SpecialFloat,
+    ScaffoldOptions,
 };
 
+/// Challenge signatures are written in Rust, so this is `RustType`'s
+/// `Display`, except that a borrowed `Vec` is taken as the slice `&[T]`.
 pub(super) fn translate_type_rs(ty: &RustType) -> String {
     match ty {
-        RustType::I32 => "i32".to_string(),
-        RustType::F64 => "f64".to_string(),
-        RustType::Usize => "usize".to_string(),
-        RustType::Bool => "bool".to_string(),
-        RustType::String => "String".to_string(),
-        RustType::Char => "char".to_string(),
-        RustType::Vec(inner) => format!("Vec<{}>", translate_type_rs(inner)),
-        RustType::MutRef(inner) => format!("&mut {}", translate_type_rs(inner)),
-        RustType::Void => "()".to_string(),
+        RustType::Ref(inner) => match inner.as_ref() {
+            RustType::Vec(elem) => format!("&[{}]", translate_type_rs(elem)),
+            _ => ty.to_string(),
+        },
+        _ => ty.to_string(),
     }
 }
 
 pub(super) fn render_value_rs(value: &Value, ty: &RustType) -> String {
     match ty {
         RustType::I32 | RustType::Usize => format!("{}", value.as_i64().unwrap_or(0)),
-        RustType::F64 => {
-            let n = value.as_f64().unwrap_or(0.0);
-            if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                format!("{}", n)
-            }
+        RustType::I8 | RustType::I16 | RustType::I64 | RustType::U8 | RustType::U16 | RustType::U32 | RustType::U64 => {
+            super::integer_literal(value)
         }
+        RustType::F32 | RustType::F64 => match super::special_float(value) {
+            Some(SpecialFloat::Infinity) => format!("{}::INFINITY", ty),
+            Some(SpecialFloat::NegInfinity) => format!("{}::NEG_INFINITY", ty),
+            Some(SpecialFloat::NaN) => format!("{}::NAN", ty),
+            None => super::format_float(value.as_f64().unwrap_or(0.0)),
+        },
         RustType::Bool => format!("{}", value.as_bool().unwrap_or(false)),
-        RustType::String => format!("\"{}\".to_string()", value.as_str().unwrap_or("")),
-        RustType::Char => {
-            let s = value.as_str().unwrap_or("?");
-            let c = s.chars().next().unwrap_or('?');
-            format!("'{}'", c)
-        }
+        RustType::String => format!("{}.to_string()", super::string_literal(value, Language::Rs)),
+        RustType::Str => super::string_literal(value, Language::Rs),
+        RustType::Char => super::char_literal(value, Language::Rs),
         RustType::Vec(inner) => {
-            if let Some(arr) = value.as_array() {
-                let items: Vec<String> = arr.iter().map(|v| render_value_rs(v, inner)).collect();
-                format!("vec![{}]", items.join(", "))
+            match value.as_array() {
+                Some(arr) if !arr.is_empty() => {
+                    let items: Vec<String> = arr.iter().map(|v| render_value_rs(v, inner)).collect();
+                    format!("vec![{}]", items.join(", "))
+                }
+                // `vec![]` can't be inferred when compared with `assert_eq!`
+                _ => format!("Vec::<{}>::new()", translate_type_rs(inner)),
+            }
+        }
+        RustType::Option(inner) => {
+            if value.is_null() {
+                "None".to_string()
+            } else {
+                format!("Some({})", render_value_rs(value, inner))
+            }
+        }
+        RustType::Map(key, inner) => {
+            let entries: Vec<String> = super::map_entries(value, key)
+                .into_iter()
+                .map(|(k, v)| format!("({}, {})", render_value_rs(&k, key), render_value_rs(v, inner)))
+                .collect();
+            if entries.is_empty() {
+                format!("HashMap::<{}, {}>::new()", translate_type_rs(key), translate_type_rs(inner))
             } else {
-                "vec![]".to_string()
+                format!("HashMap::from([{}])", entries.join(", "))
             }
         }
-        RustType::MutRef(inner) => render_value_rs(value, inner),
+        RustType::Tuple(elems) => {
+            let items: Vec<String> = super::tuple_items(value, elems)
+                .iter()
+                .map(|(v, ty)| render_value_rs(v, ty))
+                .collect();
+            format!("({})", items.join(", "))
+        }
+        RustType::Ref(inner) | RustType::MutRef(inner) => render_value_rs(value, inner),
         RustType::Void => "()".to_string(),
     }
 }
 
+/// The tests reach `HashMap` through `use super::*`.
+fn map_import(sig: &FunctionSignature) -> &'static str {
+    if super::uses_map(sig) {
+        "use std::collections::HashMap;\n\n"
+    } else {
+        ""
+    }
+}
+
+/// Parameter `name` as the generated code passes it, borrowed if `ty` is a
+/// reference. A `&str` is bound to a string literal, already a borrow.
+fn call_argument(name: &str, ty: &RustType) -> String {
+    match ty {
+        RustType::Ref(inner) if **inner == RustType::Str => name.to_string(),
+        RustType::Ref(_) => format!("&{}", name),
+        RustType::MutRef(_) => format!("&mut {}", name),
+        _ => name.to_string(),
+    }
+}
+
 pub(super) fn generate_rust(
     challenge: &Challenge,
     sig: &FunctionSignature,
     difficulty: Difficulty,
     output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
 ) -> Result<(), String> {
     let params_str: Vec<String> = sig
         .params
@@ -94,13 +139,7 @@ pub(super) fn generate_rust(
             let call_args: Vec<String> = sig
                 .params
                 .iter()
-                .map(|p| {
-                    if matches!(&p.ty, RustType::MutRef(_)) {
-                        format!("&mut {}", p.name)
-                    } else {
-                        p.name.clone()
-                    }
-                })
+                .map(|p| call_argument(&p.name, &p.ty))
                 .collect();
             main_body.push_str(&format!("    {}({});\n", sig.name, call_args.join(", ")));
             if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
@@ -115,7 +154,7 @@ pub(super) fn generate_rust(
                         p.name,
                         super::render_value(val, &p.ty, Language::Rs)
                     ));
-                    args.push(p.name.clone());
+                    args.push(call_argument(&p.name, &p.ty));
                 }
             }
             main_body.push_str(&format!(
@@ -129,64 +168,175 @@ pub(super) fn generate_rust(
 
     let tests_code = generate_rust_tests(sig, &challenge.tests);
 
+    // The bench compiles src/main.rs as a module, so the solution must be `pub`.
+    let visibility = if options.with_bench { "pub " } else { "" };
     let main_rs = format!(
-        r#"fn {}({}){} {{
-    todo!()
-}}
+        r#"{}{}fn {}({}){} {{
+{}}}
 
 fn main() {{
 {}}}
 {}"#,
+        map_import(sig),
+        visibility,
         sig.name,
         params_str.join(", "),
         ret_str,
+        options.body_or("    ", "    todo!()\n"),
         main_body,
         tests_code
     );
 
-    let metadata = ProjectMetadata::new(
+    let mut metadata = ProjectMetadata::new(
         challenge.name.clone(),
         Language::Rs,
         difficulty,
         sig.name.clone(),
-        Some(chrono::Local::now().to_rfc3339()),
+        options.initialized_at(),
         challenge.difficulty,
     );
+    metadata.challenge_index = options.challenge_index;
     let metadata_content = metadata_json(&metadata);
 
     let setup_sh = format!(
         r#"#!/bin/bash
 set -e
 
+echo "[codle] step: checking required tools"
 {}
 
-cargo init --name "{}"
+echo "[codle] step: creating Cargo project"
+# `cargo init` refuses to run over an existing package, so a re-run or a
+# leftover Cargo.toml keeps the package and only rewrites src/main.rs.
+if [ ! -f Cargo.toml ]; then
+    cargo init --vcs none --name "{}"
+fi
+mkdir -p src
 
+echo "[codle] step: writing src/main.rs"
 cat > src/main.rs << 'SOLUTION'
 {}
 SOLUTION
 
+echo "[codle] step: writing .codle.json"
 cat > .codle.json << 'METADATA'
 {}
 METADATA
 
 echo "Run: cargo run"
 echo "Test: cargo test"
-"#,
-        require_commands(&["cargo"]),
-        sig.name,
+{}"#,
+        require_commands(Language::Rs.required_commands()),
+        build_tool_name(&sig.name),
         escape_for_heredoc(&main_rs),
-        metadata_content
+        metadata_content,
+        bench_section(challenge, sig, options)
     );
 
     write_setup_script(output_dir, &setup_sh)
 }
 
+/// The manifest `cargo init` writes for the scaffold's package.
+pub(super) fn cargo_toml(sig: &FunctionSignature) -> String {
+    format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+        build_tool_name(&sig.name)
+    )
+}
+
+fn bench_section(challenge: &Challenge, sig: &FunctionSignature, options: &ScaffoldOptions) -> String {
+    if !options.with_bench {
+        return String::new();
+    }
+
+    format!(
+        r#"
+echo "[codle] step: adding criterion to Cargo.toml"
+if ! grep -q '^\[\[bench\]\]' Cargo.toml; then
+    cat >> Cargo.toml << 'CARGO'
+
+[dev-dependencies]
+criterion = "0.5"
+
+[[bench]]
+name = "solution"
+harness = false
+CARGO
+fi
+
+echo "[codle] step: writing benches/solution.rs"
+mkdir -p benches
+cat > benches/solution.rs << 'BENCH'
+{}
+BENCH
+
+echo "Bench: cargo bench"
+"#,
+        escape_for_heredoc(&generate_rust_bench(challenge, sig))
+    )
+}
+
+/// Criterion benchmark of the solution on the largest test input. Inputs are
+/// rebuilt outside the timed section for every batch, so solutions that
+/// consume or mutate them are measured fairly.
+pub(super) fn generate_rust_bench(challenge: &Challenge, sig: &FunctionSignature) -> String {
+    let inputs = largest_test_inputs(challenge);
+    let mut setup = Vec::new();
+    let mut bindings = Vec::new();
+    let mut args = Vec::new();
+    for p in &sig.params {
+        let inner = unwrap_ref(&p.ty);
+        let value = inputs.and_then(|i| i.get(&p.name)).cloned().unwrap_or(Value::Null);
+        setup.push(super::render_value(&value, inner, Language::Rs));
+        if matches!(&p.ty, RustType::MutRef(_)) {
+            bindings.push(format!("mut {}", p.name));
+            args.push(format!("black_box(&mut {})", p.name));
+        } else {
+            bindings.push(p.name.clone());
+            args.push(format!("black_box({})", call_argument(&p.name, &p.ty)));
+        }
+    }
+
+    let tuple = |items: &[String]| match items.len() {
+        1 => format!("({},)", items[0]),
+        _ => format!("({})", items.join(", ")),
+    };
+
+    format!(
+        r#"{map_import}use std::hint::black_box;
+
+use criterion::{{criterion_group, criterion_main, BatchSize, Criterion}};
+
+// Compiles src/main.rs as a module so the benchmark calls your solution directly.
+#[path = "../src/main.rs"]
+#[allow(dead_code)]
+mod solution;
+
+fn bench_{name}(c: &mut Criterion) {{
+    c.bench_function("{name}", |b| {{
+        b.iter_batched(
+            || {setup},
+            |{bindings}| solution::{name}({args}),
+            BatchSize::SmallInput,
+        )
+    }});
+}}
+
+criterion_group!(benches, bench_{name});
+criterion_main!(benches);"#,
+        map_import = map_import(sig),
+        name = sig.name,
+        setup = tuple(&setup),
+        bindings = tuple(&bindings),
+        args = args.join(", "),
+    )
+}
+
 pub(super) fn generate_rust_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
     let mut test_fns = Vec::new();
 
     for (i, test) in tests.iter().enumerate() {
-        let test_num = i + 1;
+        let test_name = super::test_fn_suffix(test, i);
         let mut body = String::new();
 
         if let Some(inputs) = test.input.as_object() {
@@ -211,23 +361,11 @@ pub(super) fn generate_rust_tests(sig: &FunctionSignature, tests: &[TestCase]) -
                 let call_args: Vec<String> = sig
                     .params
                     .iter()
-                    .map(|p| {
-                        if matches!(&p.ty, RustType::MutRef(_)) {
-                            format!("&mut {}", p.name)
-                        } else {
-                            p.name.clone()
-                        }
-                    })
+                    .map(|p| call_argument(&p.name, &p.ty))
                     .collect();
                 body.push_str(&format!("        {}({});\n", sig.name, call_args.join(", ")));
-                if let Some(p) = sig
-                    .params
-                    .iter()
-                    .find(|p| matches!(&p.ty, RustType::MutRef(_)))
-                {
-                    let inner = unwrap_mut_ref(&p.ty);
-                    let expected = super::render_value(&test.expected, inner, Language::Rs);
-                    body.push_str(&format!("        assert_eq!({}, {});\n", p.name, expected));
+                for (p, output) in super::mut_ref_outputs(sig, test) {
+                    body.push_str(&result_assertion(&p.name, &output, unwrap_ref(&p.ty)));
                 }
             } else {
                 let mut args = Vec::new();
@@ -238,7 +376,7 @@ pub(super) fn generate_rust_tests(sig: &FunctionSignature, tests: &[TestCase]) -
                             p.name,
                             super::render_value(val, &p.ty, Language::Rs)
                         ));
-                        args.push(p.name.clone());
+                        args.push(call_argument(&p.name, &p.ty));
                     }
                 }
                 body.push_str(&format!(
@@ -246,8 +384,7 @@ pub(super) fn generate_rust_tests(sig: &FunctionSignature, tests: &[TestCase]) -
                     sig.name,
                     args.join(", ")
                 ));
-                let expected = super::render_value(&test.expected, &sig.return_type, Language::Rs);
-                body.push_str(&format!("        assert_eq!(result, {});\n", expected));
+                body.push_str(&result_assertion("result", test, &sig.return_type));
             }
         }
 
@@ -255,7 +392,7 @@ pub(super) fn generate_rust_tests(sig: &FunctionSignature, tests: &[TestCase]) -
             r#"    #[test]
     fn test_{}() {{
 {}    }}"#,
-            test_num, body
+            test_name, body
         ));
     }
 
@@ -271,6 +408,71 @@ mod tests {{
     )
 }
 
+/// Assertions that `actual` matches the expected value: NaN by `is_nan`,
+/// floats within `FLOAT_EPSILON`, and an unordered `Vec` by sorting copies of
+/// both sides first. `validate_challenge` only lets through elements that
+/// `partial_cmp` orders totally, except floats, which sort by `total_cmp` so
+/// NaN can't panic.
+fn result_assertion(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    let mut code = String::new();
+    let mut actual = actual.to_string();
+    let mut expected = super::render_value(&test.expected, ty, Language::Rs);
+    if super::compares_unordered(test, ty) {
+        let compare = if super::is_float_vec(ty) { "a.total_cmp(b)" } else { "a.partial_cmp(b).unwrap()" };
+        code.push_str(&format!(
+            r#"        let mut actual = {actual}.clone();
+        actual.sort_by(|a, b| {compare});
+        let mut expected: {ty} = {expected};
+        expected.sort_by(|a, b| {compare});
+"#,
+            actual = actual,
+            compare = compare,
+            ty = translate_type_rs(ty),
+            expected = expected,
+        ));
+        actual = "actual".to_string();
+        expected = "expected".to_string();
+    }
+
+    if super::expects_nan(ty, &test.expected) {
+        code.push_str(&format!("        assert!({}.is_nan());\n", actual));
+    } else if super::is_float_vec(ty) {
+        code.push_str(&float_vec_assertion(&actual, &expected, ty));
+    } else if super::compares_approximately(ty, &test.expected) {
+        code.push_str(&float_assertion(&actual, &expected, ty));
+    } else {
+        code.push_str(&format!("        assert_eq!({}, {});\n", actual, expected));
+    }
+    code
+}
+
+fn float_assertion(actual: &str, expected: &str, ty: &RustType) -> String {
+    format!(
+        r#"        let expected: {ty} = {expected};
+        assert!({actual} == expected || ({actual} - expected).abs() < {eps}, "{{}} != {{}}", {actual}, expected);
+"#,
+        ty = ty,
+        expected = expected,
+        actual = actual,
+        eps = super::float_epsilon(ty),
+    )
+}
+
+fn float_vec_assertion(actual: &str, expected: &str, ty: &RustType) -> String {
+    format!(
+        r#"        let expected: {ty} = {expected};
+        assert_eq!({actual}.len(), expected.len(), "{{:?}} != {{:?}}", {actual}, expected);
+        for (a, b) in {actual}.iter().zip(&expected) {{
+            assert!(a == b || (a - b).abs() < {eps}, "{{:?}} != {{:?}}", {actual}, expected);
+        }}
+"#,
+        ty = ty,
+        expected = expected,
+        actual = actual,
+        eps = super::float_epsilon(ty),
+    )
+}
+
 pub(super) fn parse_rust_output(_stdout: &str, _stderr: &str, combined: &str) -> Result<super::TestSummary, String> {
     let mut passed = 0;
     let mut failed = 0;
@@ -308,5 +510,7 @@ pub(super) fn parse_rust_output(_stdout: &str, _stderr: &str, combined: &str) ->
         failed,
         total: passed + failed,
         output: combined.to_string(),
+        diagnosis: None,
+        compile_error: None,
     })
 }
@@ -0,0 +1,265 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{
+    validate_challenge, Challenge, Difficulty, FunctionSignature, Language, ProjectMetadata, metadata_json,
+};
+use super::unwrap_ref;
+
+/// Placeholders available to custom templates:
+///
+/// - `{{challenge_name}}`: the challenge title, e.g. `Two Sum`
+/// - `{{function_name}}`: the function to implement, e.g. `two_sum`
+/// - `{{params}}`: the parameter list in the target language's syntax
+/// - `{{return_type}}`: the return type in the target language's syntax
+/// - `{{tests}}`: the test file the built-in generator would write
+pub const PLACEHOLDERS: &[&str] = &[
+    "challenge_name",
+    "function_name",
+    "params",
+    "return_type",
+    "tests",
+];
+
+/// Renders every file under `<template_dir>/<extension>/` into `output_dir`,
+/// keeping relative paths, in place of the built-in generator for `lang`.
+pub fn generate_from_template(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    lang: Language,
+    difficulty: Difficulty,
+    template_dir: &Path,
+    output_dir: &Path,
+) -> Result<(), String> {
+    validate_challenge(challenge, sig)?;
+    let lang_dir = template_dir.join(lang.extension());
+    if !lang_dir.is_dir() {
+        return Err(format!(
+            "Template directory '{}' has no '{}' folder for {}",
+            template_dir.display(),
+            lang.extension(),
+            lang.display_name()
+        ));
+    }
+
+    let values = placeholder_values(challenge, sig, lang);
+    for path in template_files(&lang_dir)? {
+        let relative = path.strip_prefix(&lang_dir).unwrap_or(&path);
+        let source = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))?;
+        let rendered = render_template(&source, &values)
+            .map_err(|e| format!("{}: {}", relative.display(), e))?;
+
+        if relative == Path::new("setup.sh") {
+            super::write_setup_script(output_dir, &rendered)?;
+            continue;
+        }
+
+        let dest = output_dir.join(relative);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&dest, rendered)
+            .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    }
+
+    let metadata_path = output_dir.join(crate::models::project::METADATA_FILE);
+    if !metadata_path.exists() {
+        let metadata = ProjectMetadata::new(
+            challenge.name.clone(),
+            lang,
+            difficulty,
+            sig.name.clone(),
+            Some(chrono::Local::now().to_rfc3339()),
+            challenge.difficulty,
+        );
+        fs::write(&metadata_path, metadata_json(&metadata))
+            .map_err(|e| format!("Failed to write {}: {}", metadata_path.display(), e))?;
+    }
+
+    Ok(())
+}
+
+/// Replaces each `{{name}}` in `template` with its value. Whitespace inside the
+/// braces is ignored; an unknown or unterminated placeholder is an error rather
+/// than being copied through silently.
+pub fn render_template(template: &str, values: &[(&str, String)]) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "unterminated '{{' in template".to_string())?;
+        let name = after[..end].trim();
+        let value = values
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| {
+                format!(
+                    "unknown placeholder '{{{{{}}}}}' (expected one of: {})",
+                    name,
+                    PLACEHOLDERS.join(", ")
+                )
+            })?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn placeholder_values(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    lang: Language,
+) -> Vec<(&'static str, String)> {
+    let (params, return_type) = match lang {
+        Language::Rs => (
+            joined_params(sig, |name, ty| format!("{}: {}", name, ty), lang, false),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::Py => (
+            joined_params(sig, |name, ty| format!("{}: {}", name, ty), lang, true),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::Kt => (
+            joined_params(sig, |name, ty| format!("{}: {}", name, ty), lang, true),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::Java => (
+            joined_params(sig, |name, ty| format!("{} {}", ty, name), lang, true),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::C => (
+            super::c::c_param_list(sig, challenge.c_string_len),
+            super::c::c_return_type(sig),
+        ),
+        Language::Cpp => (
+            joined_params(sig, |name, ty| format!("{} {}", ty, name), lang, false),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        // Fortran declares argument types in the body, so `{{params}}` is
+        // just the dummy argument names.
+        Language::Fortran => (
+            super::fortran::dummy_arguments(sig).join(", "),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::Go => (
+            joined_params(sig, |name, ty| format!("{} {}", name, ty), lang, true),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        // JavaScript parameters are untyped; `{{return_type}}` is the JSDoc type.
+        Language::Js => (
+            joined_params(sig, |name, _| name.to_string(), lang, true),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::Ts => (
+            joined_params(sig, |name, ty| format!("{}: {}", name, ty), lang, true),
+            super::translate_type(&sig.return_type, lang),
+        ),
+        Language::Cs => (super::csharp::param_list(sig), super::translate_type(&sig.return_type, lang)),
+    };
+
+    vec![
+        ("challenge_name", challenge.name.clone()),
+        ("function_name", sig.name.clone()),
+        ("params", params),
+        ("return_type", return_type),
+        ("tests", super::generate_tests(challenge, sig, lang)),
+    ]
+}
+
+fn joined_params(
+    sig: &FunctionSignature,
+    format_param: impl Fn(&str, String) -> String,
+    lang: Language,
+    strip_mut_ref: bool,
+) -> String {
+    sig.params
+        .iter()
+        .map(|p| {
+            let ty = if strip_mut_ref { unwrap_ref(&p.ty) } else { &p.ty };
+            format_param(&p.name, super::translate_type(ty, lang))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn template_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = fs::read_dir(&current)
+            .map_err(|e| format!("Failed to read {}: {}", current.display(), e))?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::parse_signature;
+
+    #[test]
+    fn test_render_custom_rust_template() {
+        let root = std::env::temp_dir().join(format!("codle-template-test-{}", std::process::id()));
+        let template_dir = root.join("templates");
+        let output_dir = root.join("out");
+        fs::create_dir_all(template_dir.join("rs/src")).unwrap();
+        fs::create_dir_all(&output_dir).unwrap();
+        fs::write(
+            template_dir.join("rs/src/main.rs"),
+            "// {{ challenge_name }}\npub fn {{function_name}}({{params}}) -> {{return_type}} {\n    todo!()\n}\n{{tests}}",
+        )
+        .unwrap();
+
+        let challenge: Challenge = serde_json::from_value(json!({
+            "name": "Two Sum",
+            "difficulty": 2,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>",
+            "tests": [{"input": {"nums": [2, 7], "target": 9}, "expected": [0, 1]}],
+        }))
+        .unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        generate_from_template(&challenge, &sig, Language::Rs, Difficulty::Easy, &template_dir, &output_dir)
+            .unwrap();
+
+        let main_rs = fs::read_to_string(output_dir.join("src/main.rs")).unwrap();
+        let has_metadata = output_dir.join(".codle.json").exists();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(main_rs.starts_with(
+            "// Two Sum\npub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize> {\n"
+        ));
+        assert!(main_rs.contains("fn test_1()"));
+        assert!(!main_rs.contains("{{"));
+        assert!(has_metadata);
+    }
+
+    #[test]
+    fn test_render_rejects_unknown_placeholder() {
+        let values = vec![("function_name", "f".to_string())];
+        assert_eq!(render_template("fn {{function_name}}()", &values).unwrap(), "fn f()");
+        assert!(render_template("{{nope}}", &values).unwrap_err().contains("nope"));
+        assert!(render_template("{{function_name", &values).is_err());
+    }
+}
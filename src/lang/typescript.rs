@@ -0,0 +1,232 @@
+use crate::models::{
+    Challenge, Difficulty, FunctionSignature, Language, ProjectMetadata, RustType,
+    TestCase, metadata_json,
+};
+use super::javascript::{result_assertion, translate_type_js};
+use super::{
+    write_setup_script, require_commands, escape_for_heredoc, build_tool_name,
+    is_void_with_mut_ref, get_first_test_inputs, unwrap_ref, ScaffoldOptions,
+};
+
+/// `const` declarations of the parameters in `inputs`, annotated so an
+/// empty array or map still has the parameter's type, and the call's
+/// arguments.
+fn declarations(
+    sig: &FunctionSignature,
+    inputs: &serde_json::Map<String, serde_json::Value>,
+) -> (String, Vec<String>) {
+    let mut decls = String::new();
+    let mut args = Vec::new();
+    for p in &sig.params {
+        if let Some(val) = inputs.get(&p.name) {
+            let ty = unwrap_ref(&p.ty);
+            decls.push_str(&format!(
+                "    const {}: {} = {};\n",
+                p.name,
+                translate_type_js(ty),
+                super::render_value(val, ty, Language::Ts)
+            ));
+            args.push(p.name.clone());
+        }
+    }
+    (decls, args)
+}
+
+pub(super) fn generate_typescript(
+    challenge: &Challenge,
+    sig: &FunctionSignature,
+    difficulty: Difficulty,
+    output_dir: &std::path::Path,
+    options: &ScaffoldOptions,
+) -> Result<(), String> {
+    let params_str: Vec<String> = sig
+        .params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, translate_type_js(unwrap_ref(&p.ty))))
+        .collect();
+
+    let mut main_body = String::new();
+    if let Some(inputs) = get_first_test_inputs(challenge) {
+        let (decls, args) = declarations(sig, inputs);
+        main_body.push_str(&decls);
+        if is_void_with_mut_ref(sig) {
+            main_body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+            if let Some(p) = sig.params.iter().find(|p| matches!(&p.ty, RustType::MutRef(_))) {
+                main_body.push_str(&format!("    console.log({});\n", p.name));
+            }
+        } else if sig.return_type == RustType::Void {
+            main_body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+        } else {
+            main_body.push_str(&format!("    const result = {}({});\n", sig.name, args.join(", ")));
+            main_body.push_str("    console.log(result);\n");
+        }
+    }
+
+    let solution_ts = format!(
+        r#"export function {}({}): {} {{
+{}}}
+
+if (require.main === module) {{
+{}}}"#,
+        sig.name,
+        params_str.join(", "),
+        translate_type_js(&sig.return_type),
+        options.body_or("    ", "    throw new Error(\"TODO\");\n"),
+        main_body,
+    );
+
+    let tests_code = generate_typescript_tests(sig, &challenge.tests);
+
+    let package_json = format!(
+        r#"{{
+  "name": "{}",
+  "private": true,
+  "scripts": {{
+    "test": "jest"
+  }},
+  "jest": {{
+    "preset": "ts-jest",
+    "testEnvironment": "node"
+  }},
+  "devDependencies": {{
+    "@types/jest": "^29.5.12",
+    "@types/node": "^20.14.0",
+    "jest": "^29.7.0",
+    "ts-jest": "^29.2.5",
+    "ts-node": "^10.9.2",
+    "typescript": "^5.5.4"
+  }}
+}}"#,
+        build_tool_name(&sig.name)
+    );
+
+    let tsconfig_json = r#"{
+  "compilerOptions": {
+    "target": "es2020",
+    "module": "commonjs",
+    "strict": true,
+    "esModuleInterop": true,
+    "types": ["jest", "node"]
+  }
+}"#;
+
+    let mut metadata = ProjectMetadata::new(
+        challenge.name.clone(),
+        Language::Ts,
+        difficulty,
+        sig.name.clone(),
+        options.initialized_at(),
+        challenge.difficulty,
+    );
+    metadata.challenge_index = options.challenge_index;
+    let metadata_content = metadata_json(&metadata);
+
+    let setup_sh = format!(
+        r#"#!/bin/bash
+set -e
+
+echo "[codle] step: checking required tools"
+{}
+
+echo "[codle] step: writing package.json"
+cat > package.json << 'PACKAGE'
+{}
+PACKAGE
+
+echo "[codle] step: writing tsconfig.json"
+cat > tsconfig.json << 'TSCONFIG'
+{}
+TSCONFIG
+
+echo "[codle] step: installing jest and ts-jest"
+npm install
+
+echo "[codle] step: writing solution.ts"
+cat > solution.ts << 'SOLUTION'
+{}
+SOLUTION
+
+echo "[codle] step: writing solution.test.ts"
+cat > solution.test.ts << 'TESTS'
+{}
+TESTS
+
+echo "[codle] step: writing .codle.json"
+cat > .codle.json << 'METADATA'
+{}
+METADATA
+
+echo "Run: npx ts-node solution.ts"
+echo "Test: npx jest"
+"#,
+        require_commands(Language::Ts.required_commands()),
+        package_json,
+        tsconfig_json,
+        escape_for_heredoc(&solution_ts),
+        escape_for_heredoc(&tests_code),
+        metadata_content
+    );
+
+    write_setup_script(output_dir, &setup_sh)
+}
+
+/// The JavaScript backend's `unordered`, typed. Numbers sort by value and
+/// anything else by its JSON.
+const UNORDERED_HELPER: &str = r#"function unordered<T>(xs: T[]): T[] {
+    const compare = (a: T, b: T): number => {
+        if (typeof a === "number" && typeof b === "number") {
+            return a - b;
+        }
+        const [x, y] = [JSON.stringify(a), JSON.stringify(b)];
+        return x < y ? -1 : x > y ? 1 : 0;
+    };
+    return [...xs].sort(compare);
+}"#;
+
+/// The Jest assertion for `actual`, asserting a non-null optional result
+/// with `!` so strict mode lets it be used as the inner type.
+fn typed_assertion(actual: &str, test: &TestCase, ty: &RustType) -> String {
+    if matches!(ty, RustType::Option(_)) && !test.expected.is_null() {
+        result_assertion(&format!("{}!", actual), test, ty)
+    } else {
+        result_assertion(actual, test, ty)
+    }
+}
+
+pub(super) fn generate_typescript_tests(sig: &FunctionSignature, tests: &[TestCase]) -> String {
+    let mut test_fns = vec![format!("import {{ {} }} from \"./solution\";", sig.name)];
+    if tests.iter().any(|t| t.unordered) {
+        test_fns.push(UNORDERED_HELPER.to_string());
+    }
+
+    for (i, test) in tests.iter().enumerate() {
+        let test_name = super::test_fn_suffix(test, i);
+        let mut body = String::new();
+
+        if let Some(inputs) = test.input.as_object() {
+            let (decls, args) = declarations(sig, inputs);
+            body.push_str(&decls);
+            if is_void_with_mut_ref(sig) {
+                body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+                let outputs = super::mut_ref_outputs(sig, test);
+                for (p, output) in &outputs {
+                    let assertion = typed_assertion(&p.name, output, unwrap_ref(&p.ty));
+                    if outputs.len() > 1 {
+                        body.push_str(&super::scoped(&assertion, "    ", "    ", "{"));
+                    } else {
+                        body.push_str(&assertion);
+                    }
+                }
+            } else if sig.return_type == RustType::Void {
+                body.push_str(&format!("    {}({});\n", sig.name, args.join(", ")));
+            } else {
+                body.push_str(&format!("    const result = {}({});\n", sig.name, args.join(", ")));
+                body.push_str(&typed_assertion("result", test, &sig.return_type));
+            }
+        }
+
+        test_fns.push(format!("test(\"test_{}\", () => {{\n{}}});", test_name, body));
+    }
+
+    test_fns.join("\n\n")
+}
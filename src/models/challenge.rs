@@ -1,9 +1,13 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use chrono::{Datelike, Local};
+use std::path::{Path, PathBuf};
+use chrono::{Datelike, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use super::config;
 use super::difficulty::Difficulty;
+use super::language::Language;
+use super::signature::{FunctionSignature, Param, RustType};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Challenge {
@@ -12,16 +16,102 @@ pub struct Challenge {
     pub short_description: String,
     pub description: String,
     pub function_signature: String,
-    pub tests: Vec<TestCase>
+    pub tests: Vec<TestCase>,
+    /// Known-good function bodies keyed by language, used by
+    /// `codle init --run-tests` to check the expected values.
+    #[serde(default)]
+    pub reference_solutions: HashMap<Language, String>,
+    /// Let `Vec<char>` results be compared as joined strings in languages
+    /// where returning a string is the idiomatic answer (Python).
+    #[serde(default)]
+    pub chars_as_string: bool,
+    /// Pass each `String` parameter to C as `const char* s` plus `int s_len`,
+    /// like arrays, for challenges that walk the string by index.
+    #[serde(default)]
+    pub c_string_len: bool,
+    /// The intended approach and its complexity, shown only after a
+    /// successful `codle submit` so it can't spoil the challenge.
+    #[serde(default)]
+    pub explanation: Option<String>,
+    /// Replaces the tier's default test timeout, for challenges whose
+    /// intended solution needs longer.
+    #[serde(default)]
+    pub time_limit_ms: Option<u64>,
+    /// Nudges `codle hint` reveals one at a time, mildest first.
+    #[serde(default)]
+    pub hints: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestCase {
+    #[serde(default)]
+    pub name: Option<String>,
     pub input: Value,
+    /// The return value, or for a void function the value its `&mut`
+    /// parameter ends up with. With several `&mut` parameters this may be an
+    /// object keyed by parameter name instead; see `expected_outputs`.
     pub expected: Value,
+    /// Compare a `Vec` result as a multiset, for problems like "all subsets"
+    /// whose answer may come in any order.
+    #[serde(default)]
+    pub unordered: bool,
+}
+
+impl TestCase {
+    /// The case's optional name as a lowercase identifier fragment for its
+    /// generated test function, e.g. "Handles empty input" becomes
+    /// `handles_empty_input`.
+    pub fn identifier(&self) -> Option<String> {
+        let raw = self.name.as_deref()?;
+        let mut name = String::new();
+        for c in raw.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                name.push(c.to_ascii_lowercase());
+            } else if !name.is_empty() && !name.ends_with('_') {
+                name.push('_');
+            }
+        }
+        let name = name.trim_end_matches('_').to_string();
+        if name.is_empty() {
+            None
+        } else {
+            Some(name)
+        }
+    }
+}
+
+/// A tier directory holding this file is read as one JSON array of challenges
+/// instead of one challenge per `.json` file.
+pub const COMBINED_FILE: &str = "challenges.json";
+
+/// Points codle at a challenge set, ahead of the configured `challenges_dir`
+/// and the search below.
+pub const CHALLENGES_DIR_VAR: &str = "CODLE_CHALLENGES_DIR";
+
+/// Where challenges are looked for, in order, for errors about missing ones.
+const CHALLENGES_DIR_SEARCH: &str = "codle reads challenges from $CODLE_CHALLENGES_DIR, then the config's \
+    challenges_dir, then the pack from `codle challenges sync`, then ./challenges or a challenges directory \
+    next to the executable";
+
+/// The challenge set picked by `$CODLE_CHALLENGES_DIR` or the config's
+/// `challenges_dir`, in that order.
+pub fn configured_challenges_dir() -> Option<PathBuf> {
+    std::env::var_os(CHALLENGES_DIR_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| config::load_effective_config().challenges_dir)
 }
 
 pub fn get_challenges_dir() -> PathBuf {
+    if let Some(dir) = configured_challenges_dir() {
+        return dir;
+    }
+
+    let pack_dir = config::get_pack_dir();
+    if pack_dir.exists() {
+        return pack_dir;
+    }
+
     let exe_path = std::env::current_exe().unwrap_or_default();
     let mut path = exe_path.parent().unwrap_or(std::path::Path::new(".")).to_path_buf();
 
@@ -44,14 +134,327 @@ pub fn get_challenges_dir() -> PathBuf {
     PathBuf::from("challenges")
 }
 
-pub fn load_daily_challenge(difficulty: Difficulty) -> Result<Challenge, String> {
-    let challenges_dir = get_challenges_dir();
+/// Number of challenges for each tier under `challenges_dir`: the length of
+/// the combined file if there is one, otherwise the number of `.json` files. A
+/// missing tier directory counts as zero.
+pub fn count_challenges(challenges_dir: &Path) -> Vec<(Difficulty, usize)> {
+    Difficulty::all()
+        .iter()
+        .map(|&difficulty| {
+            let difficulty_dir = challenges_dir.join(difficulty.as_str());
+            let combined = difficulty_dir.join(COMBINED_FILE);
+            if combined.is_file() {
+                let count = fs::read_to_string(&combined)
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<Vec<Value>>(&content).ok())
+                    .map_or(0, |challenges| challenges.len());
+                return (difficulty, count);
+            }
+
+            let count = fs::read_dir(&difficulty_dir)
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| entry.ok())
+                        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                        .count()
+                })
+                .unwrap_or(0);
+            (difficulty, count)
+        })
+        .collect()
+}
+
+/// Looks up a challenge by name across every tier, in either layout. Files
+/// that fail to parse are skipped.
+pub fn find_challenge(challenges_dir: &Path, name: &str) -> Option<Challenge> {
+    Difficulty::all().iter().find_map(|difficulty| {
+        let difficulty_dir = challenges_dir.join(difficulty.as_str());
+        let combined = difficulty_dir.join(COMBINED_FILE);
+        if combined.is_file() {
+            let challenges = read_combined_file(&combined).ok()?;
+            return challenges.into_iter().find(|c| c.name == name);
+        }
+
+        fs::read_dir(&difficulty_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter_map(|content| serde_json::from_str::<Challenge>(&content).ok())
+            .find(|c| c.name == name)
+    })
+}
+
+/// Every challenge under `challenges_dir` with its tier and where it was read
+/// from. Unlike the daily loader, entries that fail to parse are kept as errors
+/// so they can be reported.
+pub fn load_all_challenges(challenges_dir: &Path) -> Vec<(Difficulty, String, Result<Challenge, String>)> {
+    let mut all = Vec::new();
+    for &difficulty in Difficulty::all() {
+        let difficulty_dir = challenges_dir.join(difficulty.as_str());
+        let combined = difficulty_dir.join(COMBINED_FILE);
+        if combined.is_file() {
+            let source = combined.display().to_string();
+            let entries = fs::read_to_string(&combined)
+                .map_err(|e| format!("failed to read: {}", e))
+                .and_then(|content| serde_json::from_str::<Vec<Value>>(&content).map_err(|e| e.to_string()));
+            match entries {
+                Ok(entries) => all.extend(entries.into_iter().enumerate().map(|(i, entry)| {
+                    let challenge = serde_json::from_value(entry).map_err(|e| e.to_string());
+                    (difficulty, format!("{} #{}", source, i + 1), challenge)
+                })),
+                Err(e) => all.push((difficulty, source, Err(e))),
+            }
+            continue;
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(&difficulty_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        all.extend(paths.into_iter().map(|path| {
+            let content = fs::read_to_string(&path).map_err(|e| format!("failed to read: {}", e));
+            let challenge = content.and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+            (difficulty, path.display().to_string(), challenge)
+        }));
+    }
+    all
+}
+
+/// The `&mut` parameters a test of void function `sig` checks, each with the
+/// value it should end up with. When `sig` has several and `expected` is an
+/// object whose keys all name them, each named parameter is checked against
+/// its entry; otherwise `expected` is the first one's value.
+pub fn expected_outputs<'s, 'v>(sig: &'s FunctionSignature, expected: &'v Value) -> Vec<(&'s Param, &'v Value)> {
+    let mut_refs: Vec<&Param> = sig.params.iter().filter(|p| matches!(p.ty, RustType::MutRef(_))).collect();
+    let keyed = mut_refs.len() > 1
+        && expected
+            .as_object()
+            .is_some_and(|obj| !obj.is_empty() && obj.keys().all(|key| mut_refs.iter().any(|p| p.name == *key)));
+    if keyed {
+        mut_refs.into_iter().filter_map(|p| Some((p, expected.get(&p.name)?))).collect()
+    } else {
+        mut_refs.into_iter().take(1).map(|p| (p, expected)).collect()
+    }
+}
+
+/// Checks that every test's input object has exactly the signature's
+/// parameter names as keys. A missing or misspelled key would otherwise
+/// generate a call with arguments left out, so each offending test is
+/// reported. Also checks that no two tests generate the same function name
+/// and that unordered tests produce a `Vec`.
+pub fn validate_challenge(challenge: &Challenge, sig: &FunctionSignature) -> Result<(), String> {
+    let mut problems = Vec::new();
+
+    for (i, test) in challenge.tests.iter().enumerate() {
+        let label = match &test.name {
+            Some(name) => format!("test {} ({})", i + 1, name),
+            None => format!("test {}", i + 1),
+        };
+        let Some(inputs) = test.input.as_object() else {
+            problems.push(format!("{}: input is not an object", label));
+            continue;
+        };
+
+        let missing: Vec<&str> = sig
+            .params
+            .iter()
+            .map(|p| p.name.as_str())
+            .filter(|name| !inputs.contains_key(*name))
+            .collect();
+        let extra: Vec<&str> = inputs
+            .keys()
+            .map(|key| key.as_str())
+            .filter(|key| !sig.params.iter().any(|p| p.name == *key))
+            .collect();
+
+        let mut issues = Vec::new();
+        if !missing.is_empty() {
+            issues.push(format!("missing {}", missing.join(", ")));
+        }
+        if !extra.is_empty() {
+            issues.push(format!("unexpected {}", extra.join(", ")));
+        }
+        if !issues.is_empty() {
+            problems.push(format!("{}: {}", label, issues.join("; ")));
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(format!(
+            "test inputs don't match the parameters of {}:\n  {}",
+            sig.name,
+            problems.join("\n  ")
+        ));
+    }
+
+    // Each test becomes a function named after it, so names that sanitize
+    // alike ("a b" and "a-b", or "2" and the unnamed second test) clash.
+    let mut names: Vec<String> = Vec::new();
+    for (i, test) in challenge.tests.iter().enumerate() {
+        let name = test.identifier().unwrap_or_else(|| (i + 1).to_string());
+        if let Some(first) = names.iter().position(|seen| *seen == name) {
+            return Err(format!(
+                "tests {} and {} would both generate test_{}; give them distinct names",
+                first + 1,
+                i + 1,
+                name
+            ));
+        }
+        names.push(name);
+    }
+
+    // Only a `Vec` has an order to ignore.
+    for test in challenge.tests.iter().filter(|t| t.unordered) {
+        let checked: Vec<&RustType> = match &sig.return_type {
+            RustType::Void => expected_outputs(sig, &test.expected)
+                .into_iter()
+                .filter_map(|(p, _)| match &p.ty {
+                    RustType::MutRef(inner) => Some(inner.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+            ty => vec![ty],
+        };
+        if checked.is_empty() || !checked.iter().all(|ty| matches!(ty, RustType::Vec(_))) {
+            return Err(format!("{} has unordered tests but doesn't produce a Vec", sig.name));
+        }
+        for ty in checked {
+            if let RustType::Vec(elem) = ty {
+                if !sortable_element(elem) {
+                    return Err(format!(
+                        "{} has unordered tests but its {} elements can't be sorted",
+                        sig.name, elem
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether an unordered `Vec` of `ty` can be sorted before comparing. Maps
+/// have no order, and a float is only ordered (NaN included) on its own,
+/// not inside another element.
+fn sortable_element(ty: &RustType) -> bool {
+    fn unorderable(ty: &RustType) -> bool {
+        match ty {
+            RustType::Map(..) | RustType::F32 | RustType::F64 => true,
+            RustType::Vec(inner) | RustType::Option(inner) => unorderable(inner),
+            RustType::Tuple(elems) => elems.iter().any(unorderable),
+            _ => false,
+        }
+    }
+    matches!(ty, RustType::F32 | RustType::F64) || !unorderable(ty)
+}
+
+/// Overrides today's date (`YYYY-MM-DD`) for the daily challenge and streaks,
+/// so a given day can be reproduced.
+pub const DATE_OVERRIDE_VAR: &str = "CODLE_DATE";
+
+/// The local date, or the `CODLE_DATE` override if it's set to a valid date.
+pub fn today() -> NaiveDate {
+    std::env::var(DATE_OVERRIDE_VAR)
+        .ok()
+        .and_then(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok())
+        .unwrap_or_else(|| Local::now().date_naive())
+}
+
+/// Today's daily challenge at `difficulty`, given the player's `history`.
+pub fn load_daily_challenge(difficulty: Difficulty, history: &[config::Completion]) -> Result<Challenge, String> {
+    load_daily_challenge_at(difficulty, 0, history)
+}
+
+/// Today's challenge `index` places after the daily one, for extra practice.
+/// Index 0 is the daily challenge; indices past the tier's size are an error
+/// rather than wrapping round to it again. Challenges solved before today,
+/// going by `history`, are passed over while the tier has others left.
+pub fn load_daily_challenge_at(
+    difficulty: Difficulty,
+    index: usize,
+    history: &[config::Completion],
+) -> Result<Challenge, String> {
+    let date = today();
+    let solved = solved_before(history, date);
+    load_indexed_challenge(&get_challenges_dir(), difficulty, date, index, &solved)
+}
+
+/// Names of the challenges completed before `date`. Today's completions are
+/// left out so today's pick doesn't move once it's been submitted.
+fn solved_before(history: &[config::Completion], date: NaiveDate) -> HashSet<String> {
+    history
+        .iter()
+        .filter(|c| NaiveDate::parse_from_str(&c.date, "%Y-%m-%d").is_ok_and(|day| day < date))
+        .map(|c| c.challenge_name.clone())
+        .collect()
+}
+
+fn load_indexed_challenge(
+    challenges_dir: &Path,
+    difficulty: Difficulty,
+    date: NaiveDate,
+    index: usize,
+    solved: &HashSet<String>,
+) -> Result<Challenge, String> {
+    let available = count_challenges(challenges_dir)
+        .into_iter()
+        .find(|(tier, _)| *tier == difficulty)
+        .map_or(0, |(_, count)| count);
+    if index > 0 && index >= available {
+        return Err(format!(
+            "The {} tier only has {} challenge(s), so the highest index is {}",
+            difficulty.as_str(),
+            available,
+            available.saturating_sub(1)
+        ));
+    }
+    pick_challenge(challenges_dir, difficulty, day_number(date), index, solved)
+}
+
+fn day_number(date: NaiveDate) -> usize {
+    date.num_days_from_ce() as usize
+}
+
+/// Picks the challenge `index` places into the tier's rotation for
+/// `day_number`, from the tier's combined file if it has one, falling back to
+/// the sorted per-file layout. The rotation depends only on the date, so
+/// everyone with the same pack and nothing solved gets the same challenge;
+/// challenges in `solved` move to the back of it.
+fn pick_challenge(
+    challenges_dir: &Path,
+    difficulty: Difficulty,
+    day_number: usize,
+    index: usize,
+    solved: &HashSet<String>,
+) -> Result<Challenge, String> {
     let difficulty_dir = challenges_dir.join(difficulty.as_str());
 
+    let combined = difficulty_dir.join(COMBINED_FILE);
+    if combined.is_file() {
+        let mut challenges = read_combined_file(&combined)?;
+        if challenges.is_empty() {
+            return Err(format!(
+                "No challenges found in {} difficulty",
+                difficulty.as_str()
+            ));
+        }
+        let start = day_number % challenges.len();
+        challenges.rotate_left(start);
+        return Ok(nth_unsolved_first(challenges, index, solved));
+    }
+
     if !difficulty_dir.exists() {
         return Err(format!(
-            "Challenges directory not found: {}",
-            difficulty_dir.display()
+            "Challenges directory not found: {}. {}.",
+            difficulty_dir.display(),
+            CHALLENGES_DIR_SEARCH
         ));
     }
 
@@ -69,21 +472,402 @@ pub fn load_daily_challenge(difficulty: Difficulty) -> Result<Challenge, String>
 
     if entries.is_empty() {
         return Err(format!(
-            "No challenges found in {} difficulty",
-            difficulty.as_str()
+            "No challenges found in {}. {}.",
+            difficulty_dir.display(),
+            CHALLENGES_DIR_SEARCH
         ));
     }
 
     entries.sort_by_key(|e| e.path());
-    let today = Local::now().date_naive();
-    let day_number = today.num_days_from_ce() as usize;
-    let index = day_number % entries.len();
+    let start = day_number % entries.len();
+
+    // A broken file rolls the day over to the next one rather than leaving
+    // the tier without a challenge. Files are only read until the pick is
+    // among the unsolved ones.
+    let mut candidates = Vec::new();
+    let mut unsolved = 0;
+    let mut errors = Vec::new();
+    for offset in 0..entries.len() {
+        let path = entries[(start + offset) % entries.len()].path();
+        match read_challenge_file(&path) {
+            Ok(challenge) => {
+                if !solved.contains(&challenge.name) {
+                    unsolved += 1;
+                }
+                candidates.push(challenge);
+                if unsolved > index {
+                    break;
+                }
+            }
+            Err(e) => {
+                eprintln!("Skipping {}", e);
+                errors.push(e);
+            }
+        }
+    }
+    if !candidates.is_empty() {
+        return Ok(nth_unsolved_first(candidates, index, solved));
+    }
 
-    let chosen = &entries[index];
+    Err(format!(
+        "No valid challenges in {} difficulty:\n  {}",
+        difficulty.as_str(),
+        errors.join("\n  ")
+    ))
+}
+
+/// The `index`th of `candidates` once those in `solved` are moved to the
+/// end, keeping the order otherwise, and wrapping round past the last.
+fn nth_unsolved_first(candidates: Vec<Challenge>, index: usize, solved: &HashSet<String>) -> Challenge {
+    let (mut ordered, repeats): (Vec<Challenge>, Vec<Challenge>) =
+        candidates.into_iter().partition(|c| !solved.contains(&c.name));
+    ordered.extend(repeats);
+    let pick = index % ordered.len();
+    ordered.swap_remove(pick)
+}
+
+fn read_challenge_file(path: &Path) -> Result<Challenge, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("{}: failed to read: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
 
-    let content = fs::read_to_string(chosen.path())
+/// The challenges in a combined file. Entries that don't deserialize are
+/// skipped with a warning; only a file that isn't a JSON array is an error.
+fn read_combined_file(path: &Path) -> Result<Vec<Challenge>, String> {
+    let content = fs::read_to_string(path)
         .map_err(|e| format!("Failed to read challenges: {}", e))?;
+    let entries: Vec<Value> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to deserialize {}: {}", path.display(), e))?;
+
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, entry)| match serde_json::from_value(entry) {
+            Ok(challenge) => Some(challenge),
+            Err(e) => {
+                eprintln!("Skipping challenge {} in {}: {}", i + 1, path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::parse_signature;
+
+    #[test]
+    fn test_count_challenges_per_tier() {
+        let dir = std::env::temp_dir().join(format!("codle-count-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (tier, files) in [("easy", 3), ("medium", 1), ("hard", 0)] {
+            fs::create_dir_all(dir.join(tier)).unwrap();
+            for i in 0..files {
+                fs::write(dir.join(tier).join(format!("c{}.json", i)), "{}").unwrap();
+            }
+        }
+        fs::write(dir.join("easy").join("notes.md"), "").unwrap();
+
+        let counts = count_challenges(&dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            counts,
+            vec![
+                (Difficulty::Easy, 3),
+                (Difficulty::Medium, 1),
+                (Difficulty::Hard, 0),
+                (Difficulty::Extreme, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_tier_error_explains_search() {
+        let dir = std::env::temp_dir().join(format!("codle-missing-tier-test-{}", std::process::id()));
+        let err = load_challenge_for_day(&dir, Difficulty::Easy, 0).unwrap_err();
+        assert!(err.starts_with("Challenges directory not found: "));
+        assert!(err.contains("$CODLE_CHALLENGES_DIR, then the config's challenges_dir"));
+    }
+
+    #[test]
+    fn test_combined_file_selection() {
+        let dir = std::env::temp_dir().join(format!("codle-combined-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("hard")).unwrap();
+        let challenges: Vec<Value> = ["Alpha", "Beta", "Gamma"]
+            .iter()
+            .map(|name| {
+                serde_json::json!({
+                    "name": name,
+                    "difficulty": 3,
+                    "short_description": "",
+                    "description": "",
+                    "function_signature": "fn f() -> i32",
+                    "tests": [],
+                })
+            })
+            .collect();
+        fs::write(dir.join("hard").join(COMBINED_FILE), Value::Array(challenges).to_string()).unwrap();
+        // Per-file challenges next to a combined file are ignored.
+        fs::write(dir.join("hard").join("other.json"), "{}").unwrap();
+
+        let names: Vec<String> = (0..4)
+            .map(|day| load_challenge_for_day(&dir, Difficulty::Hard, day).unwrap().name)
+            .collect();
+        let repeat = load_challenge_for_day(&dir, Difficulty::Hard, 1).unwrap().name;
+        let counts = count_challenges(&dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(names, vec!["Alpha", "Beta", "Gamma", "Alpha"]);
+        assert_eq!(repeat, "Beta");
+        assert_eq!(counts[2], (Difficulty::Hard, 3));
+    }
+
+    #[test]
+    fn test_daily_selection_depends_only_on_date() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges");
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let pick = |date: NaiveDate| {
+            load_challenge_for_day(&dir, Difficulty::Easy, day_number(date))
+                .unwrap()
+                .name
+        };
+
+        let first = pick(date);
+        assert_eq!(pick(date), first);
+        assert_ne!(pick(date.succ_opt().unwrap()), first);
+        let cycle = count_challenges(&dir)[0].1 as u64;
+        assert_eq!(pick(date + chrono::Days::new(cycle)), first);
+    }
+
+    #[test]
+    fn test_bonus_index_picks_following_challenges() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges");
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let pick = |index| load_indexed_challenge(&dir, Difficulty::Easy, date, index, &HashSet::new());
+
+        assert_eq!(pick(0).unwrap().name, load_challenge_for_date_in(&dir, date));
+        assert_eq!(pick(1).unwrap().name, load_challenge_for_date_in(&dir, date.succ_opt().unwrap()));
+        assert_ne!(pick(2).unwrap().name, pick(0).unwrap().name);
+        let available = count_challenges(&dir)[0].1;
+        assert!(pick(available).unwrap_err().contains("highest index is"));
+    }
 
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to deserialize challenges: {}", e))
-}
\ No newline at end of file
+    fn load_challenge_for_date_in(dir: &Path, date: NaiveDate) -> String {
+        load_challenge_for_day(dir, Difficulty::Easy, day_number(date)).unwrap().name
+    }
+
+    fn load_challenge_for_day(dir: &Path, difficulty: Difficulty, day_number: usize) -> Result<Challenge, String> {
+        pick_challenge(dir, difficulty, day_number, 0, &HashSet::new())
+    }
+
+    #[test]
+    fn test_solved_challenges_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("codle-solved-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let challenge = |name: &str| {
+            serde_json::json!({
+                "name": name,
+                "difficulty": 1,
+                "short_description": "",
+                "description": "",
+                "function_signature": "fn f() -> i32",
+                "tests": [],
+            })
+        };
+        fs::create_dir_all(dir.join("easy")).unwrap();
+        for (file, name) in [("a.json", "Alpha"), ("b.json", "Beta"), ("c.json", "Gamma")] {
+            fs::write(dir.join("easy").join(file), challenge(name).to_string()).unwrap();
+        }
+        fs::create_dir_all(dir.join("hard")).unwrap();
+        let combined = Value::Array(vec![challenge("Delta"), challenge("Omega")]);
+        fs::write(dir.join("hard").join(COMBINED_FILE), combined.to_string()).unwrap();
+
+        let solved = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<HashSet<String>>();
+        let pick = |tier, index, names: &[&str]| pick_challenge(&dir, tier, 0, index, &solved(names)).unwrap().name;
+        let picks = [
+            pick(Difficulty::Easy, 0, &["Alpha"]),
+            pick(Difficulty::Easy, 1, &["Alpha"]),
+            pick(Difficulty::Easy, 2, &["Alpha"]),
+            pick(Difficulty::Easy, 0, &["Alpha", "Beta", "Gamma"]),
+            pick(Difficulty::Hard, 0, &["Delta"]),
+        ];
+        let _ = fs::remove_dir_all(&dir);
+
+        // Bonus picks don't land on the daily one again, and once the whole
+        // tier is solved the plain rotation comes back.
+        assert_eq!(picks, ["Beta", "Gamma", "Alpha", "Alpha", "Omega"]);
+    }
+
+    #[test]
+    fn test_solved_before_ignores_today() {
+        let completion = |date: &str, name: &str| config::Completion {
+            date: date.to_string(),
+            challenge_name: name.to_string(),
+            language: Language::Rs,
+            difficulty: Difficulty::Easy,
+            points: 1,
+            duration_secs: None,
+            bonus: false,
+        };
+        let history = vec![completion("2026-03-13", "Alpha"), completion("2026-03-14", "Beta")];
+        let solved = solved_before(&history, NaiveDate::from_ymd_opt(2026, 3, 14).unwrap());
+        assert_eq!(solved, HashSet::from(["Alpha".to_string()]));
+    }
+
+    #[test]
+    fn test_find_challenge_by_name() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges");
+        let challenge = find_challenge(&dir, "Two Sum").unwrap();
+        assert_eq!(challenge.difficulty, 2);
+        assert!(challenge.explanation.is_some());
+        assert_eq!(challenge.hints.len(), 2);
+        assert!(find_challenge(&dir, "No Such Challenge").is_none());
+    }
+
+    #[test]
+    fn test_corrupt_files_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("codle-corrupt-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let challenge = |name: &str| {
+            format!(
+                r#"{{"name": "{}", "difficulty": 1, "short_description": "", "description": "",
+                "function_signature": "fn f() -> i32", "tests": []}}"#,
+                name
+            )
+        };
+        fs::create_dir_all(dir.join("easy")).unwrap();
+        fs::write(dir.join("easy/a.json"), challenge("Alpha")).unwrap();
+        fs::write(dir.join("easy/b.json"), "{ not json").unwrap();
+        fs::write(dir.join("easy/c.json"), challenge("Gamma")).unwrap();
+        fs::create_dir_all(dir.join("hard")).unwrap();
+        fs::write(dir.join("hard/bad.json"), r#"{"name": "Broken"}"#).unwrap();
+        fs::create_dir_all(dir.join("medium")).unwrap();
+        fs::write(
+            dir.join("medium").join(COMBINED_FILE),
+            format!("[{}, {{\"name\": \"Broken\"}}, {}]", challenge("Delta"), challenge("Omega")),
+        )
+        .unwrap();
+
+        let names: Vec<String> = (0..4)
+            .map(|day| load_challenge_for_day(&dir, Difficulty::Easy, day).unwrap().name)
+            .collect();
+        let combined: Vec<String> = (0..3)
+            .map(|day| load_challenge_for_day(&dir, Difficulty::Medium, day).unwrap().name)
+            .collect();
+        let all_broken = load_challenge_for_day(&dir, Difficulty::Hard, 0);
+        let found = find_challenge(&dir, "Gamma").map(|c| c.name);
+        let found_after_broken = find_challenge(&dir, "Omega").map(|c| c.name);
+        let _ = fs::remove_dir_all(&dir);
+
+        // Day 1 lands on the corrupt b.json and rolls over to c.json.
+        assert_eq!(names, vec!["Alpha", "Gamma", "Gamma", "Alpha"]);
+        assert_eq!(combined, vec!["Delta", "Omega", "Delta"]);
+        assert!(all_broken.unwrap_err().contains("bad.json"));
+        assert_eq!(found.as_deref(), Some("Gamma"));
+        assert_eq!(found_after_broken.as_deref(), Some("Omega"));
+    }
+
+    fn validation_fixture(tests: Value) -> (Challenge, FunctionSignature) {
+        let challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "Two Sum",
+            "difficulty": 2,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>",
+            "tests": tests,
+        }))
+        .unwrap();
+        let sig = parse_signature(&challenge.function_signature).unwrap();
+        (challenge, sig)
+    }
+
+    #[test]
+    fn test_validate_reports_missing_and_extra_keys() {
+        let (challenge, sig) = validation_fixture(serde_json::json!([
+            {"input": {"nums": [2, 7], "target": 9}, "expected": [0, 1]},
+            {"input": {"num": [2, 7], "target": 9}, "expected": [0, 1]},
+            {"name": "extra", "input": {"nums": [1], "target": 1, "k": 2}, "expected": []},
+        ]));
+        let err = validate_challenge(&challenge, &sig).unwrap_err();
+        assert!(err.contains("test 2: missing nums; unexpected num"));
+        assert!(err.contains("test 3 (extra): unexpected k"));
+        assert!(!err.contains("test 1"));
+    }
+
+    #[test]
+    fn test_validate_rejects_clashing_test_names() {
+        let (challenge, sig) = validation_fixture(serde_json::json!([
+            {"name": "a b", "input": {"nums": [1], "target": 1}, "expected": []},
+            {"name": "A-b!", "input": {"nums": [2], "target": 1}, "expected": []},
+        ]));
+        assert_eq!(
+            validate_challenge(&challenge, &sig).unwrap_err(),
+            "tests 1 and 2 would both generate test_a_b; give them distinct names"
+        );
+
+        let (challenge, sig) = validation_fixture(serde_json::json!([
+            {"name": "2", "input": {"nums": [1], "target": 1}, "expected": []},
+            {"input": {"nums": [2], "target": 1}, "expected": []},
+        ]));
+        assert!(validate_challenge(&challenge, &sig).unwrap_err().starts_with("tests 1 and 2"));
+    }
+
+    #[test]
+    fn test_unordered_needs_vec_result() {
+        let (challenge, sig) = validation_fixture(serde_json::json!([
+            {"input": {"nums": [2, 7], "target": 9}, "expected": [1, 0], "unordered": true},
+        ]));
+        assert!(challenge.tests[0].unordered);
+        assert!(validate_challenge(&challenge, &sig).is_ok());
+
+        let sig = parse_signature("fn two_sum(nums: Vec<i32>, target: i32) -> i32").unwrap();
+        assert_eq!(
+            validate_challenge(&challenge, &sig).unwrap_err(),
+            "two_sum has unordered tests but doesn't produce a Vec"
+        );
+
+        for (ret, sortable) in [
+            ("Vec<f64>", true),
+            ("Vec<Vec<String>>", true),
+            ("Vec<HashMap<char, i32>>", false),
+            ("Vec<Vec<f64>>", false),
+        ] {
+            let sig = parse_signature(&format!("fn two_sum(nums: Vec<i32>, target: i32) -> {}", ret)).unwrap();
+            assert_eq!(validate_challenge(&challenge, &sig).is_ok(), sortable, "{}", ret);
+        }
+    }
+
+    #[test]
+    fn test_shipped_challenges_are_valid() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("challenges");
+        for difficulty in Difficulty::all() {
+            for entry in fs::read_dir(dir.join(difficulty.as_str())).unwrap() {
+                let path = entry.unwrap().path();
+                let challenge: Challenge =
+                    serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+                let sig = parse_signature(&challenge.function_signature).unwrap();
+                if let Err(e) = validate_challenge(&challenge, &sig) {
+                    panic!("{}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_explanation_defaults_to_none() {
+        let challenge: Challenge = serde_json::from_value(serde_json::json!({
+            "name": "F",
+            "difficulty": 1,
+            "short_description": "",
+            "description": "",
+            "function_signature": "fn f() -> i32",
+            "tests": [],
+        }))
+        .unwrap();
+        assert_eq!(challenge.explanation, None);
+    }
+}
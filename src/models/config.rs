@@ -1,12 +1,20 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use super::difficulty::Difficulty;
+use super::language::Language;
+
+/// Bumped whenever `migrate` gains a step.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserConfig {
+    /// Configs from before versioning have no field and load as 0.
+    #[serde(default)]
+    pub schema_version: u32,
     pub difficulty: Difficulty,
     pub boss_score: u32,
     pub challenges_completed: u32,
@@ -16,17 +24,99 @@ pub struct UserConfig {
     pub current_streak: u32,
     #[serde(default)]
     pub longest_streak: u32,
+    /// Practice submissions, scored apart from the daily challenge.
+    #[serde(default)]
+    pub practice_completed: u32,
+    #[serde(default)]
+    pub practice_score: u32,
+    #[serde(default)]
+    pub challenges_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub color: Option<bool>,
+    /// Rename the function to the target language's convention, e.g.
+    /// `two_sum` becomes `twoSum` in Java and Kotlin.
+    #[serde(default)]
+    pub idiomatic_names: bool,
+    /// Seconds `codle init --run-tests` lets setup.sh run before killing it.
+    /// No limit when unset.
+    #[serde(default)]
+    pub setup_timeout_secs: Option<u64>,
+    /// Every accepted submission, oldest first.
+    #[serde(default)]
+    pub history: Vec<Completion>,
+    /// Git URL of the challenge pack `codle challenges sync` clones and pulls.
+    #[serde(default)]
+    pub challenges_repo: Option<String>,
+    /// Move `difficulty` up after a run of daily completions and down after
+    /// repeated failed submissions or skipped days.
+    #[serde(default)]
+    pub auto_difficulty: bool,
+    /// Days with a failed daily submission since the last accepted one,
+    /// counted towards an `auto_difficulty` demotion.
+    #[serde(default)]
+    pub difficulty_misses: u32,
+    /// Date, `YYYY-MM-DD`, of the last failed submission counted in
+    /// `difficulty_misses`, so each day counts at most once.
+    #[serde(default)]
+    pub last_miss_date: Option<String>,
+    /// Accepted submissions per language, practice included.
+    #[serde(default)]
+    pub language_counts: HashMap<Language, u32>,
+}
+
+/// One accepted `codle submit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Completion {
+    /// Local date of the submission, `YYYY-MM-DD`.
+    pub date: String,
+    pub challenge_name: String,
+    pub language: Language,
+    pub difficulty: Difficulty,
+    pub points: u32,
+    /// Seconds from `codle init` to the submission, if the project recorded
+    /// when it was initialized.
+    #[serde(default)]
+    pub duration_secs: Option<i64>,
+    /// A bonus challenge, which doesn't count towards streaks.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bonus: bool,
 }
 
+/// Workspace-level overrides read from `.codlerc` in the current directory.
+#[derive(Debug, Default, Deserialize)]
+pub struct LocalOverride {
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub challenges_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub color: Option<bool>,
+}
+
+const LOCAL_OVERRIDE_FILE: &str = ".codlerc";
+
 impl Default for UserConfig {
     fn default() -> Self {
         Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
             difficulty: Difficulty::Medium,
             boss_score: 0,
             challenges_completed: 0,
             last_completed_date: None,
             current_streak: 0,
             longest_streak: 0,
+            practice_completed: 0,
+            practice_score: 0,
+            challenges_dir: None,
+            color: None,
+            idiomatic_names: false,
+            setup_timeout_secs: None,
+            history: Vec::new(),
+            challenges_repo: None,
+            auto_difficulty: false,
+            difficulty_misses: 0,
+            last_miss_date: None,
+            language_counts: HashMap::new(),
         }
     }
 }
@@ -36,25 +126,245 @@ pub fn get_config_path() -> PathBuf {
     home.join(".config").join("codle").join("config.json")
 }
 
+/// Where `codle challenges sync` keeps the pack when no `challenges_dir` is
+/// configured.
+pub fn get_pack_dir() -> PathBuf {
+    get_config_path().with_file_name("challenges")
+}
+
 pub fn load_config() -> UserConfig {
-    let path = get_config_path();
+    load_config_from(&get_config_path())
+}
+
+/// Loads `path`, upgrading and rewriting it once if it predates
+/// `CURRENT_SCHEMA_VERSION`.
+fn load_config_from(path: &Path) -> UserConfig {
     if !path.exists() {
         return UserConfig::default();
     }
 
-    match fs::read_to_string(&path) {
+    let config: UserConfig = match fs::read_to_string(path) {
         Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
-        Err(_) => UserConfig::default(),
+        Err(_) => return UserConfig::default(),
+    };
+
+    if config.schema_version >= CURRENT_SCHEMA_VERSION {
+        return config;
+    }
+    let config = migrate(config);
+    if let Err(e) = save_config_to(&config, path) {
+        eprintln!("Failed to save upgraded config: {}", e);
+    }
+    config
+}
+
+/// Upgrades a config written by an older codle, one version step at a time.
+pub fn migrate(mut config: UserConfig) -> UserConfig {
+    if config.schema_version < 1 {
+        // v0 configs may predate longest_streak, which then defaulted to 0
+        // even for users in the middle of a streak.
+        config.longest_streak = config.longest_streak.max(config.current_streak);
+        config.schema_version = 1;
     }
+    config
 }
 
 pub fn save_config(config: &UserConfig) -> Result<(), std::io::Error> {
-    let path = get_config_path();
+    save_config_to(config, &get_config_path())
+}
 
+/// Writes to a sibling temp file and renames it over `path`, so a failed save
+/// leaves the previous config intact instead of a truncated file.
+fn save_config_to(config: &UserConfig, path: &Path) -> Result<(), std::io::Error> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
     let contents = serde_json::to_string_pretty(config)?;
-    fs::write(path, contents)
+    let tmp_path = path.with_extension("json.tmp");
+    if let Err(e) = fs::write(&tmp_path, contents).and_then(|_| fs::rename(&tmp_path, path)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+pub fn load_local_override(dir: &Path) -> Option<LocalOverride> {
+    let contents = fs::read_to_string(dir.join(LOCAL_OVERRIDE_FILE)).ok()?;
+    let mut local: LocalOverride = match serde_json::from_str(&contents) {
+        Ok(local) => local,
+        Err(e) => {
+            eprintln!("Ignoring {}: {}", LOCAL_OVERRIDE_FILE, e);
+            return None;
+        }
+    };
+
+    // A relative challenges_dir is relative to the .codlerc, not the caller
+    if let Some(ref challenges_dir) = local.challenges_dir {
+        if challenges_dir.is_relative() {
+            local.challenges_dir = Some(dir.join(challenges_dir));
+        }
+    }
+
+    Some(local)
+}
+
+pub fn apply_local_override(mut config: UserConfig, local: LocalOverride) -> UserConfig {
+    if let Some(difficulty) = local.difficulty {
+        config.difficulty = difficulty;
+    }
+    if local.challenges_dir.is_some() {
+        config.challenges_dir = local.challenges_dir;
+    }
+    if local.color.is_some() {
+        config.color = local.color;
+    }
+    config
+}
+
+/// The global config with any `.codlerc` in the current directory layered on
+/// top. Only use this for reading; saving it would leak the overrides into
+/// the global file.
+pub fn load_effective_config() -> UserConfig {
+    let config = load_config();
+    let local = std::env::current_dir()
+        .ok()
+        .and_then(|dir| load_local_override(&dir));
+
+    match local {
+        Some(local) => apply_local_override(config, local),
+        None => config,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_override_wins() {
+        let global = UserConfig {
+            difficulty: Difficulty::Easy,
+            challenges_dir: Some(PathBuf::from("/global/challenges")),
+            ..UserConfig::default()
+        };
+        let local: LocalOverride =
+            serde_json::from_str(r#"{"difficulty": "hard", "challenges_dir": "/repo/challenges"}"#)
+                .unwrap();
+
+        let effective = apply_local_override(global, local);
+        assert_eq!(effective.difficulty, Difficulty::Hard);
+        assert_eq!(effective.challenges_dir, Some(PathBuf::from("/repo/challenges")));
+        assert_eq!(effective.color, None);
+    }
+
+    #[test]
+    fn test_empty_override_keeps_global() {
+        let global = UserConfig {
+            difficulty: Difficulty::Extreme,
+            color: Some(false),
+            ..UserConfig::default()
+        };
+
+        let effective = apply_local_override(global, LocalOverride::default());
+        assert_eq!(effective.difficulty, Difficulty::Extreme);
+        assert_eq!(effective.color, Some(false));
+    }
+
+    #[test]
+    fn test_failed_save_keeps_previous_config() {
+        let dir = std::env::temp_dir().join(format!("codle-save-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("config.json");
+
+        let before = UserConfig {
+            boss_score: 12,
+            challenges_completed: 2,
+            ..UserConfig::default()
+        };
+        save_config_to(&before, &path).unwrap();
+
+        // A directory where the temp file should go makes the write fail
+        fs::create_dir_all(path.with_extension("json.tmp")).unwrap();
+        let after = UserConfig {
+            boss_score: 20,
+            challenges_completed: 3,
+            last_completed_date: Some("2024-01-02".to_string()),
+            ..UserConfig::default()
+        };
+        let result = save_config_to(&after, &path);
+
+        let saved: UserConfig =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(result.is_err());
+        assert_eq!(saved.boss_score, 12);
+        assert_eq!(saved.challenges_completed, 2);
+        assert_eq!(saved.last_completed_date, None);
+    }
+
+    #[test]
+    fn test_config_without_streak_fields_loads() {
+        let legacy = r#"{"difficulty": "easy", "boss_score": 12, "challenges_completed": 3}"#;
+        let config: UserConfig = serde_json::from_str(legacy).unwrap();
+        assert_eq!(config.boss_score, 12);
+        assert_eq!(config.challenges_completed, 3);
+        assert_eq!(config.last_completed_date, None);
+        assert_eq!(config.current_streak, 0);
+        assert_eq!(config.longest_streak, 0);
+        assert!(config.language_counts.is_empty());
+    }
+
+    #[test]
+    fn test_language_counts_round_trip() {
+        let mut config = UserConfig::default();
+        config.language_counts.insert(Language::Cpp, 2);
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""language_counts":{"cpp":2}"#), "{}", json);
+        let loaded: UserConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.language_counts.get(&Language::Cpp), Some(&2));
+    }
+
+    #[test]
+    fn test_v0_config_migrates_to_latest() {
+        let v0 = r#"{
+            "difficulty": "hard",
+            "boss_score": 40,
+            "challenges_completed": 6,
+            "last_completed_date": "2024-03-01",
+            "current_streak": 4
+        }"#;
+        let dir = std::env::temp_dir().join(format!("codle-migrate-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        fs::write(&path, v0).unwrap();
+
+        let config = load_config_from(&path);
+        let rewritten: UserConfig =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(config.difficulty, Difficulty::Hard);
+        assert_eq!(config.boss_score, 40);
+        assert_eq!(config.current_streak, 4);
+        assert_eq!(config.longest_streak, 4);
+        assert_eq!(config.challenges_dir, None);
+        assert!(!config.idiomatic_names);
+        assert_eq!(rewritten.schema_version, CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_keeps_current_config() {
+        let config = UserConfig {
+            current_streak: 2,
+            longest_streak: 9,
+            ..UserConfig::default()
+        };
+        let migrated = migrate(config);
+        assert_eq!(migrated.longest_streak, 9);
+        assert_eq!(migrated.schema_version, CURRENT_SCHEMA_VERSION);
+    }
 }
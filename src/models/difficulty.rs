@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +13,15 @@ pub enum Difficulty {
 }
 
 impl Difficulty {
+    pub fn all() -> &'static [Difficulty] {
+        &[
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Extreme,
+        ]
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Difficulty::Easy => "easy",
@@ -29,6 +40,37 @@ impl Difficulty {
         }
     }
 
+    /// How long `codle test` lets the tests run before killing them, unless
+    /// the challenge or `--timeout` says otherwise.
+    pub fn default_test_timeout(&self) -> Duration {
+        Duration::from_secs(match self {
+            Difficulty::Easy => 5,
+            Difficulty::Medium => 10,
+            Difficulty::Hard => 20,
+            Difficulty::Extreme => 60,
+        })
+    }
+
+    /// The next tier up, or `None` at Extreme.
+    pub fn harder(&self) -> Option<Difficulty> {
+        match self {
+            Difficulty::Easy => Some(Difficulty::Medium),
+            Difficulty::Medium => Some(Difficulty::Hard),
+            Difficulty::Hard => Some(Difficulty::Extreme),
+            Difficulty::Extreme => None,
+        }
+    }
+
+    /// The next tier down, or `None` at Easy.
+    pub fn easier(&self) -> Option<Difficulty> {
+        match self {
+            Difficulty::Easy => None,
+            Difficulty::Medium => Some(Difficulty::Easy),
+            Difficulty::Hard => Some(Difficulty::Medium),
+            Difficulty::Extreme => Some(Difficulty::Hard),
+        }
+    }
+
     pub fn tier_offset(&self) -> u32 {
         match self {
             Difficulty::Easy => 0,
@@ -39,9 +81,81 @@ impl Difficulty {
     }
 }
 
-pub fn calculate_boss_score(challenge_difficulty: u8, tier: &Difficulty, streak: u32) -> u32 {
+/// Each revealed hint costs a point, though a solve always scores at least 1.
+pub fn calculate_boss_score(challenge_difficulty: u8, tier: &Difficulty, streak: u32, hints: u32) -> u32 {
     let base = challenge_difficulty as u32;
     let tier_bonus = tier.tier_offset();
     let streak_bonus = streak.min(5);
-    base + tier_bonus + streak_bonus
-}
\ No newline at end of file
+    (base + tier_bonus + streak_bonus).saturating_sub(hints).max(1)
+}
+
+/// Points for one of the day's bonus challenges: half the score without any
+/// streak bonus, but never nothing.
+pub fn bonus_boss_score(challenge_difficulty: u8, tier: &Difficulty, hints: u32) -> u32 {
+    (calculate_boss_score(challenge_difficulty, tier, 0, hints) / 2).max(1)
+}
+
+/// Step-by-step breakdown of `calculate_boss_score`, one `label: value` line
+/// per component.
+pub fn explain_boss_score(challenge_difficulty: u8, tier: &Difficulty, streak: u32, hints: u32) -> Vec<String> {
+    let base = challenge_difficulty as u32;
+    let tier_bonus = tier.tier_offset();
+    let streak_bonus = streak.min(5);
+    let total = calculate_boss_score(challenge_difficulty, tier, streak, hints);
+
+    let mut lines = vec![
+        format!("base (challenge difficulty): {}", base),
+        format!("tier bonus ({}): +{}", tier.display_name(), tier_bonus),
+        format!("raw streak: {} day(s)", streak),
+        format!("streak bonus (capped at 5): +{}", streak_bonus),
+    ];
+    if hints == 0 {
+        lines.push(format!("total: {} + {} + {} = {}", base, tier_bonus, streak_bonus, total));
+    } else {
+        lines.push(format!("hints revealed: -{}", hints));
+        lines.push(format!(
+            "total: {} + {} + {} - {} = {} (at least 1)",
+            base, tier_bonus, streak_bonus, hints, total
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_matches_score() {
+        let lines = explain_boss_score(7, &Difficulty::Hard, 9, 0);
+        assert_eq!(
+            lines,
+            vec![
+                "base (challenge difficulty): 7",
+                "tier bonus (Hard): +2",
+                "raw streak: 9 day(s)",
+                "streak bonus (capped at 5): +5",
+                "total: 7 + 2 + 5 = 14",
+            ]
+        );
+        assert_eq!(calculate_boss_score(7, &Difficulty::Hard, 9, 0), 14);
+    }
+
+    #[test]
+    fn test_hints_reduce_score() {
+        assert_eq!(calculate_boss_score(3, &Difficulty::Medium, 2, 2), 4);
+        assert_eq!(calculate_boss_score(1, &Difficulty::Easy, 0, 5), 1);
+        let lines = explain_boss_score(3, &Difficulty::Medium, 2, 2);
+        assert_eq!(lines[4], "hints revealed: -2");
+        assert_eq!(lines[5], "total: 3 + 1 + 2 - 2 = 4 (at least 1)");
+    }
+
+    #[test]
+    fn test_default_timeout_grows_with_tier() {
+        let timeouts: Vec<u64> = Difficulty::all()
+            .iter()
+            .map(|d| d.default_test_timeout().as_secs())
+            .collect();
+        assert_eq!(timeouts, vec![5, 10, 20, 60]);
+    }
+}
@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+use super::signature::RustType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
     Rs,
@@ -10,9 +14,30 @@ pub enum Language {
     Java,
     C,
     Cpp,
+    Fortran,
+    Go,
+    Js,
+    Cs,
+    Ts,
 }
 
 impl Language {
+    pub fn all() -> &'static [Language] {
+        &[
+            Language::Rs,
+            Language::Py,
+            Language::Kt,
+            Language::Java,
+            Language::C,
+            Language::Cpp,
+            Language::Fortran,
+            Language::Go,
+            Language::Js,
+            Language::Cs,
+            Language::Ts,
+        ]
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Language::Rs => "Rust",
@@ -21,6 +46,11 @@ impl Language {
             Language::Java => "Java",
             Language::C => "C",
             Language::Cpp => "C++",
+            Language::Fortran => "Fortran",
+            Language::Go => "Go",
+            Language::Js => "JavaScript",
+            Language::Cs => "C#",
+            Language::Ts => "TypeScript",
         }
     }
 
@@ -32,6 +62,93 @@ impl Language {
             Language::Java => "java",
             Language::C => "c",
             Language::Cpp => "cpp",
+            Language::Fortran => "f90",
+            Language::Go => "go",
+            Language::Js => "js",
+            Language::Cs => "cs",
+            Language::Ts => "ts",
+        }
+    }
+
+    /// Path of the file holding the solution, relative to the project root
+    /// that setup.sh populates.
+    pub fn solution_filename(&self) -> &'static str {
+        match self {
+            Language::Rs => "src/main.rs",
+            Language::Py => "solution.py",
+            Language::Kt => "app/src/main/kotlin/codle/App.kt",
+            Language::Java => "app/src/main/java/codle/App.java",
+            Language::C => "solution.c",
+            Language::Cpp => "solution.cpp",
+            Language::Fortran => "solution.f90",
+            Language::Go => "solution.go",
+            Language::Js => "solution.js",
+            Language::Cs => "Solution/Program.cs",
+            Language::Ts => "solution.ts",
+        }
+    }
+
+    /// Shell command that builds and runs the solution's `main`, from the
+    /// project root after setup.sh.
+    pub fn run_command(&self) -> &'static str {
+        match self {
+            Language::Rs => "cargo run",
+            Language::Py => "source venv/bin/activate && python solution.py",
+            Language::Kt | Language::Java => "./gradlew run",
+            Language::C | Language::Cpp | Language::Fortran => "make && ./solution",
+            Language::Go => "go run .",
+            Language::Js => "node solution.js",
+            Language::Cs => "dotnet run --project Solution",
+            Language::Ts => "npx ts-node solution.ts",
+        }
+    }
+
+    /// Path of the generated test file, relative to the project root. Rust
+    /// keeps its tests in a module at the bottom of the solution file.
+    pub fn test_filename(&self) -> &'static str {
+        match self {
+            Language::Rs => "src/main.rs",
+            Language::Py => "test_solution.py",
+            Language::Kt => "app/src/test/kotlin/codle/AppTest.kt",
+            Language::Java => "app/src/test/java/codle/AppTest.java",
+            Language::C => "test_solution.c",
+            Language::Cpp => "test_solution.cpp",
+            Language::Fortran => "test_solution.f90",
+            Language::Go => "solution_test.go",
+            Language::Js => "solution.test.js",
+            Language::Cs => "Tests/SolutionTests.cs",
+            Language::Ts => "solution.test.ts",
+        }
+    }
+
+    /// Compiles the tests without running them, so the test timeout only
+    /// covers the run. `None` when there is nothing to compile.
+    pub fn build_command(&self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            Language::Rs => Some(("cargo", &["test", "--no-run"])),
+            Language::Py | Language::Js => None,
+            Language::Kt | Language::Java => Some(("./gradlew", &["clean", "testClasses"])),
+            Language::C | Language::Cpp | Language::Fortran => Some(("make", &["test_runner"])),
+            // Compiles the package and its tests but matches no test to run.
+            Language::Go => Some(("go", &["test", "-run", "^$", "./..."])),
+            Language::Cs => Some(("dotnet", &["build"])),
+            Language::Ts => Some(("npx", &["tsc", "--noEmit"])),
+        }
+    }
+
+    /// Tools that must be installed before setup.sh can scaffold the
+    /// project; it checks for each one first.
+    pub fn required_commands(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rs => &["cargo"],
+            Language::Py => &["python3", "pip"],
+            Language::Kt | Language::Java => &["gradle"],
+            Language::C => &["gcc", "make"],
+            Language::Cpp => &["g++", "make"],
+            Language::Fortran => &["gfortran", "make"],
+            Language::Go => &["go"],
+            Language::Js | Language::Ts => &["node", "npm"],
+            Language::Cs => &["dotnet"],
         }
     }
 
@@ -39,10 +156,172 @@ impl Language {
         match self {
             Language::Rs => ("cargo", &["test"]),
             Language::Py => ("pytest", &["test_solution.py", "-v"]),
-            Language::Kt => ("./gradlew", &["clean", "test"]),
-            Language::Java => ("./gradlew", &["clean", "test"]),
+            Language::Kt => ("./gradlew", &["test"]),
+            Language::Java => ("./gradlew", &["test"]),
             Language::C => ("make", &["test"]),
             Language::Cpp => ("make", &["test"]),
+            Language::Fortran => ("make", &["test"]),
+            // `-v` prints a line per passing test; `-count=1` skips cached results.
+            Language::Go => ("go", &["test", "-v", "-count=1", "./..."]),
+            Language::Js | Language::Ts => ("npx", &["jest"]),
+            Language::Cs => ("dotnet", &["test"]),
         }
     }
+
+    /// Time the test command takes to start up even once `build_command` has
+    /// compiled the project: a cold Gradle daemon, `dotnet test`'s rebuild
+    /// check and test host, ts-jest compiling on the fly. It's added to a
+    /// tier's or challenge's time limit so that only counts the tests.
+    pub fn test_startup_allowance(&self) -> Duration {
+        Duration::from_secs(match self {
+            Language::Kt | Language::Java => 30,
+            Language::Cs | Language::Ts => 15,
+            Language::Js | Language::Go => 5,
+            Language::Rs | Language::Py => 2,
+            Language::C | Language::Cpp | Language::Fortran => 0,
+        })
+    }
+
+    /// Whether the backend can express `ty` in generated code.
+    pub fn supports(&self, ty: &RustType) -> bool {
+        match (self, ty) {
+            // C arrays are passed as pointer + length, so only one level of
+            // nesting works (see `supports_param` for matrix parameters), and
+            // scalars can't be mutated through a value param.
+            (Language::C, RustType::Vec(inner)) => {
+                !matches!(inner.as_ref(), RustType::Vec(_)) && self.supports(inner)
+            }
+            (Language::C, RustType::MutRef(inner)) => {
+                matches!(inner.as_ref(), RustType::Vec(_)) && self.supports(inner)
+            }
+            // Fortran arrays are one-dimensional here, and an array of strings
+            // would need a fixed element length.
+            (Language::Fortran, RustType::Vec(inner)) => {
+                !matches!(inner.as_ref(), RustType::Vec(_) | RustType::String)
+                    && self.supports(inner)
+            }
+            // C, Fortran and Go have no value that means "absent" for every type.
+            (Language::C | Language::Fortran | Language::Go, RustType::Option(_)) => false,
+            (Language::C | Language::Fortran, RustType::Map(..)) => false,
+            // Neither has an unsigned 64-bit type, so a value past `i64::MAX`
+            // would be a literal that doesn't compile.
+            (Language::Java | Language::Fortran, RustType::U64) => false,
+            // A JavaScript `number` is exact only up to 2^53.
+            (Language::Js | Language::Ts, RustType::I64 | RustType::U64) => false,
+            // `Map.of` rejects null values, and arrays in a map compare by identity.
+            (Language::Java, RustType::Map(_, value))
+                if matches!(value.as_ref(), RustType::Vec(_) | RustType::Option(_)) =>
+            {
+                false
+            }
+            // Only Rust and Python have tuples. Java returns one as a `Pair` or
+            // `Triple` record, which can't be nested or taken as a parameter.
+            (Language::Rs | Language::Py, RustType::Tuple(elems)) => elems.iter().all(|e| self.supports(e)),
+            (Language::Java, RustType::Tuple(elems)) => {
+                elems.len() <= 3 && elems.iter().all(|e| !has_tuple(e) && self.supports(e))
+            }
+            (Language::Java, _) if has_tuple(ty) => false,
+            (_, RustType::Tuple(_)) => false,
+            (_, RustType::Map(key, value)) => self.supports(key) && self.supports(value),
+            (_, RustType::Vec(inner))
+            | (_, RustType::Ref(inner))
+            | (_, RustType::MutRef(inner))
+            | (_, RustType::Option(inner)) => self.supports(inner),
+            _ => true,
+        }
+    }
+
+    /// Whether the backend can take `ty` as a parameter. C can take a matrix
+    /// as row pointers plus lengths, but can't return or mutate one; Java
+    /// can return a tuple but not take one.
+    pub fn supports_param(&self, ty: &RustType) -> bool {
+        match (self, ty) {
+            (Language::Java, _) if has_tuple(ty) => false,
+            (Language::C, RustType::Vec(inner)) => match inner.as_ref() {
+                RustType::Vec(elem) => {
+                    !matches!(elem.as_ref(), RustType::Vec(_) | RustType::String) && self.supports(elem)
+                }
+                _ => self.supports(ty),
+            },
+            (_, RustType::Ref(inner)) => self.supports_param(inner),
+            _ => self.supports(ty),
+        }
+    }
+}
+
+fn has_tuple(ty: &RustType) -> bool {
+    match ty {
+        RustType::Tuple(_) => true,
+        RustType::Vec(inner) | RustType::Option(inner) | RustType::Ref(inner) | RustType::MutRef(inner) => {
+            has_tuple(inner)
+        }
+        RustType::Map(key, value) => has_tuple(key) || has_tuple(value),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_rejects_nested_vec() {
+        let grid = RustType::Vec(Box::new(RustType::Vec(Box::new(RustType::I32))));
+        assert!(!Language::C.supports(&grid));
+        assert!(Language::C.supports_param(&grid));
+        assert!(!Language::C.supports_param(&RustType::MutRef(Box::new(grid.clone()))));
+        assert!(Language::Rs.supports(&grid));
+        assert!(Language::Py.supports(&grid));
+    }
+
+    #[test]
+    fn test_c_supports_flat_vec() {
+        let nums = RustType::MutRef(Box::new(RustType::Vec(Box::new(RustType::I32))));
+        assert!(Language::C.supports(&nums));
+        assert!(!Language::C.supports(&RustType::MutRef(Box::new(RustType::I32))));
+    }
+
+    #[test]
+    fn test_solution_filenames() {
+        let paths: Vec<(Language, &str)> = Language::all()
+            .iter()
+            .map(|lang| (*lang, lang.solution_filename()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (Language::Rs, "src/main.rs"),
+                (Language::Py, "solution.py"),
+                (Language::Kt, "app/src/main/kotlin/codle/App.kt"),
+                (Language::Java, "app/src/main/java/codle/App.java"),
+                (Language::C, "solution.c"),
+                (Language::Cpp, "solution.cpp"),
+                (Language::Fortran, "solution.f90"),
+                (Language::Go, "solution.go"),
+                (Language::Js, "solution.js"),
+                (Language::Cs, "Solution/Program.cs"),
+                (Language::Ts, "solution.ts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tuple_support() {
+        let pair = RustType::Tuple(vec![RustType::I32, RustType::Vec(Box::new(RustType::I32))]);
+        assert!(Language::Rs.supports(&pair));
+        assert!(Language::Py.supports_param(&RustType::Vec(Box::new(pair.clone()))));
+        assert!(Language::Java.supports(&pair));
+        assert!(!Language::Java.supports_param(&pair));
+        assert!(!Language::Java.supports(&RustType::Vec(Box::new(pair.clone()))));
+        assert!(!Language::Java.supports(&RustType::Tuple(vec![RustType::I32; 4])));
+        assert!(!Language::Kt.supports(&pair));
+        assert!(!Language::C.supports(&RustType::Option(Box::new(pair))));
+    }
+
+    #[test]
+    fn test_fortran_rejects_string_arrays() {
+        let words = RustType::Vec(Box::new(RustType::String));
+        assert!(!Language::Fortran.supports(&words));
+        assert!(Language::Fortran.supports(&RustType::Vec(Box::new(RustType::F64))));
+    }
 }
@@ -5,9 +5,12 @@ pub mod language;
 pub mod project;
 pub mod signature;
 
-pub use challenge::{Challenge, TestCase, load_daily_challenge};
+pub use challenge::{
+    Challenge, TestCase, configured_challenges_dir, count_challenges, expected_outputs, find_challenge, get_challenges_dir,
+    load_all_challenges, load_daily_challenge, load_daily_challenge_at, today, validate_challenge,
+};
 // config is accessed as crate::models::config::{load_config, save_config, ...}
-pub use difficulty::{Difficulty, calculate_boss_score};
+pub use difficulty::{Difficulty, bonus_boss_score, calculate_boss_score, explain_boss_score};
 pub use language::Language;
 pub use project::{ProjectMetadata, metadata_json};
-pub use signature::{FunctionSignature, RustType, parse_signature};
+pub use signature::{FunctionSignature, Param, RustType, parse_signature};
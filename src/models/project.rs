@@ -3,10 +3,11 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use super::challenge::{find_challenge, Challenge};
 use super::difficulty::Difficulty;
 use super::language::Language;
 
-const METADATA_FILE: &str = ".codle.json";
+pub const METADATA_FILE: &str = ".codle.json";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectMetadata {
@@ -18,6 +19,21 @@ pub struct ProjectMetadata {
     pub initialized_at: Option<String>,
     #[serde(default)]
     pub challenge_difficulty: u8,
+    /// Which of the day's challenges this is: 0 for the daily one, N for a
+    /// bonus challenge from `codle init --index N`.
+    #[serde(default, skip_serializing_if = "is_daily")]
+    pub challenge_index: usize,
+    /// Hints shown by `codle hint`; each costs a BOSS point on submit.
+    #[serde(default, skip_serializing_if = "no_hints")]
+    pub hints_revealed: u32,
+}
+
+fn is_daily(challenge_index: &usize) -> bool {
+    *challenge_index == 0
+}
+
+fn no_hints(hints_revealed: &u32) -> bool {
+    *hints_revealed == 0
 }
 
 impl ProjectMetadata {
@@ -36,8 +52,29 @@ impl ProjectMetadata {
             function_name,
             initialized_at,
             challenge_difficulty,
+            challenge_index: 0,
+            hints_revealed: 0,
         }
     }
+
+    /// Seconds since `codle init` wrote this project, if it recorded when.
+    pub fn seconds_since_init(&self) -> Option<i64> {
+        let initialized_at = chrono::DateTime::parse_from_rfc3339(self.initialized_at.as_deref()?).ok()?;
+        Some(chrono::Local::now().signed_duration_since(initialized_at).num_seconds())
+    }
+
+    /// The challenge this project was scaffolded from, looked up by name so
+    /// it's found whichever day's challenge it was.
+    pub fn challenge(&self, challenges_dir: &Path) -> Result<Challenge, String> {
+        find_challenge(challenges_dir, &self.challenge_name).ok_or_else(|| {
+            format!(
+                "The challenge '{}' in {} isn't in {}",
+                self.challenge_name,
+                METADATA_FILE,
+                challenges_dir.display()
+            )
+        })
+    }
 }
 
 pub fn load(dir: &Path) -> Result<ProjectMetadata, String> {
@@ -1,16 +1,84 @@
+use std::fmt;
+use std::str::FromStr;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RustType {
+    I8,
+    I16,
     I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
     F64,
     Usize,
     Bool,
     String,
+    /// The `str` of a `&str` parameter. Only the Rust scaffold keeps the
+    /// borrow; every other language takes its ordinary string type.
+    Str,
     Char,
     Vec(Box<RustType>),
+    Option(Box<RustType>),
+    /// `HashMap<K, V>`; its test values are JSON objects.
+    Map(Box<RustType>, Box<RustType>),
+    /// A shared borrow, `&T`, of a parameter.
+    Ref(Box<RustType>),
     MutRef(Box<RustType>),
+    /// `(A, B)`; its test values are JSON arrays with one item per element.
+    Tuple(Vec<RustType>),
     Void,
 }
 
+/// Writes the type as it appears in a challenge signature, so
+/// `ty.to_string().parse()` gives back `ty`.
+impl fmt::Display for RustType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RustType::I8 => write!(f, "i8"),
+            RustType::I16 => write!(f, "i16"),
+            RustType::I32 => write!(f, "i32"),
+            RustType::I64 => write!(f, "i64"),
+            RustType::U8 => write!(f, "u8"),
+            RustType::U16 => write!(f, "u16"),
+            RustType::U32 => write!(f, "u32"),
+            RustType::U64 => write!(f, "u64"),
+            RustType::F32 => write!(f, "f32"),
+            RustType::F64 => write!(f, "f64"),
+            RustType::Usize => write!(f, "usize"),
+            RustType::Bool => write!(f, "bool"),
+            RustType::String => write!(f, "String"),
+            RustType::Str => write!(f, "str"),
+            RustType::Char => write!(f, "char"),
+            RustType::Vec(inner) => write!(f, "Vec<{}>", inner),
+            RustType::Option(inner) => write!(f, "Option<{}>", inner),
+            RustType::Map(key, value) => write!(f, "HashMap<{}, {}>", key, value),
+            RustType::Ref(inner) => write!(f, "&{}", inner),
+            RustType::MutRef(inner) => write!(f, "&mut {}", inner),
+            RustType::Tuple(elems) => {
+                let elems: Vec<std::string::String> = elems.iter().map(|e| e.to_string()).collect();
+                write!(f, "({})", elems.join(", "))
+            }
+            RustType::Void => write!(f, "()"),
+        }
+    }
+}
+
+impl FromStr for RustType {
+    type Err = std::string::String;
+
+    /// Like the types in a signature, plus `()` for `Void` so that every
+    /// `Display` output parses back.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "()" {
+            return Ok(RustType::Void);
+        }
+        parse_type(s)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Param {
     pub name: std::string::String,
@@ -27,16 +95,20 @@ pub struct FunctionSignature {
 pub fn parse_signature(sig: &str) -> Result<FunctionSignature, std::string::String> {
     let sig = sig.trim();
 
-    // Strip leading "fn "
+    // The leading "fn" is optional: `two_sum(nums: Vec<i32>)` is accepted too.
     let rest = sig
-        .strip_prefix("fn ")
-        .ok_or_else(|| "Signature must start with 'fn '".to_string())?;
+        .strip_prefix("fn")
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .map_or(sig, str::trim_start);
 
     // Split name from params
     let paren_open = rest
         .find('(')
         .ok_or_else(|| "Missing opening parenthesis".to_string())?;
     let name = rest[..paren_open].trim().to_string();
+    if !is_identifier(&name) {
+        return Err(format!("Invalid function name: '{}'", name));
+    }
 
     // Find matching closing paren
     let paren_close = find_matching_paren(rest, paren_open)?;
@@ -46,8 +118,16 @@ pub fn parse_signature(sig: &str) -> Result<FunctionSignature, std::string::Stri
 
     // Parse return type
     let after_parens = rest[paren_close + 1..].trim();
-    let return_type = if after_parens.starts_with("->") {
-        let ty_str = after_parens[2..].trim();
+    let return_type = if let Some(ty_str) = after_parens.strip_prefix("->") {
+        let ty_str = ty_str.trim();
+        // Other languages can't return a borrow, and a Rust stub would need a
+        // lifetime tied to a parameter, so challenges return owned values.
+        if ty_str.contains('&') {
+            return Err(format!(
+                "Reference return type '{}' is not supported; return an owned value instead",
+                ty_str
+            ));
+        }
         parse_type(ty_str)?
     } else {
         RustType::Void
@@ -60,6 +140,12 @@ pub fn parse_signature(sig: &str) -> Result<FunctionSignature, std::string::Stri
     })
 }
 
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 fn find_matching_paren(s: &str, open: usize) -> Result<usize, std::string::String> {
     let mut depth = 0;
     for (i, c) in s[open..].char_indices() {
@@ -83,13 +169,18 @@ fn parse_params(params_str: &str) -> Result<Vec<Param>, std::string::String> {
         return Ok(Vec::new());
     }
 
-    let parts = split_respecting_angle_brackets(trimmed);
+    let parts = split_top_level(trimmed);
+    let last = parts.len() - 1;
     let mut params = Vec::new();
 
-    for part in parts {
+    for (i, part) in parts.into_iter().enumerate() {
         let part = part.trim();
+        // A trailing comma is valid Rust; an empty slot anywhere else is a typo.
         if part.is_empty() {
-            continue;
+            if i == last {
+                continue;
+            }
+            return Err(format!("Empty parameter in '({})': stray ','", trimmed));
         }
 
         let colon_pos = part
@@ -97,6 +188,9 @@ fn parse_params(params_str: &str) -> Result<Vec<Param>, std::string::String> {
             .ok_or_else(|| format!("Missing ':' in parameter: '{}'", part))?;
 
         let name = part[..colon_pos].trim().to_string();
+        if params.iter().any(|p: &Param| p.name == name) {
+            return Err(format!("Duplicate parameter name: '{}'", name));
+        }
         let ty_str = part[colon_pos + 1..].trim();
         let ty = parse_type(ty_str)?;
 
@@ -106,15 +200,16 @@ fn parse_params(params_str: &str) -> Result<Vec<Param>, std::string::String> {
     Ok(params)
 }
 
-fn split_respecting_angle_brackets(s: &str) -> Vec<&str> {
+/// `s` split at the commas that aren't inside `<>` or a tuple's `()`.
+fn split_top_level(s: &str) -> Vec<&str> {
     let mut parts = Vec::new();
     let mut depth = 0;
     let mut start = 0;
 
     for (i, c) in s.char_indices() {
         match c {
-            '<' => depth += 1,
-            '>' => depth -= 1,
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
             ',' if depth == 0 => {
                 parts.push(&s[start..i]);
                 start = i + 1;
@@ -127,32 +222,108 @@ fn split_respecting_angle_brackets(s: &str) -> Vec<&str> {
     parts
 }
 
+/// The `T` of `<wrapper><T>`, or `None` if `ty_str` isn't a `wrapper` type.
+fn generic_argument<'a>(ty_str: &'a str, wrapper: &str) -> Option<Result<&'a str, std::string::String>> {
+    let rest = ty_str.strip_prefix(wrapper)?.strip_prefix('<')?;
+    Some(
+        rest.strip_suffix('>')
+            .map(str::trim)
+            .ok_or_else(|| format!("Unclosed {}<> in type: '{}'", wrapper, ty_str)),
+    )
+}
+
+/// A type inside a `Vec`, `Option`, `HashMap`, tuple or reference. Test values are
+/// built in place, so only a parameter itself can be borrowed; `&str` is the
+/// exception, since a string literal already is one.
+fn parse_owned_type(ty_str: &str) -> Result<RustType, std::string::String> {
+    let ty = parse_type(ty_str)?;
+    let is_str = matches!(&ty, RustType::Ref(inner) if **inner == RustType::Str);
+    if matches!(ty, RustType::Ref(_) | RustType::MutRef(_)) && !is_str {
+        return Err(format!("Nested reference '{}' is not supported; only a parameter can be borrowed", ty_str.trim()));
+    }
+    Ok(ty)
+}
+
 fn parse_type(ty_str: &str) -> Result<RustType, std::string::String> {
     let ty_str = ty_str.trim();
 
     // Handle &mut T
     if let Some(inner) = ty_str.strip_prefix("&mut ") {
-        let inner_type = parse_type(inner.trim())?;
+        let inner_type = parse_owned_type(inner.trim())?;
         return Ok(RustType::MutRef(Box::new(inner_type)));
     }
 
-    // Handle Vec<T>
-    if let Some(rest) = ty_str.strip_prefix("Vec<") {
-        let inner = rest
-            .strip_suffix('>')
-            .ok_or_else(|| format!("Unclosed Vec<> in type: '{}'", ty_str))?;
-        let inner_type = parse_type(inner.trim())?;
-        return Ok(RustType::Vec(Box::new(inner_type)));
+    // Handle &str, &[T] (a borrowed `Vec`) and &T
+    if let Some(inner) = ty_str.strip_prefix('&') {
+        let inner = inner.trim();
+        if inner == "str" {
+            return Ok(RustType::Ref(Box::new(RustType::Str)));
+        }
+        if let Some(elem) = inner.strip_prefix('[') {
+            let elem = elem
+                .strip_suffix(']')
+                .ok_or_else(|| format!("Unclosed [] in type: '{}'", ty_str))?;
+            return Ok(RustType::Ref(Box::new(RustType::Vec(Box::new(parse_owned_type(elem)?)))));
+        }
+        return Ok(RustType::Ref(Box::new(parse_owned_type(inner)?)));
+    }
+
+    // Handle Vec<T>, Option<T> and HashMap<K, V>
+    if let Some(inner) = generic_argument(ty_str, "Vec") {
+        return Ok(RustType::Vec(Box::new(parse_owned_type(inner?)?)));
+    }
+    if let Some(inner) = generic_argument(ty_str, "Option") {
+        return Ok(RustType::Option(Box::new(parse_owned_type(inner?)?)));
+    }
+    if let Some(inner) = generic_argument(ty_str, "HashMap") {
+        let inner = inner?;
+        return match split_top_level(inner).as_slice() {
+            [key, value] => {
+                let key = parse_owned_type(key)?;
+                // Test values are JSON objects, whose keys are strings.
+                if matches!(key, RustType::F32 | RustType::F64 | RustType::Vec(_) | RustType::Option(_) | RustType::Map(..) | RustType::Tuple(_)) {
+                    return Err(format!(
+                        "HashMap key '{}' is not supported; use an integer, bool, char or String",
+                        key
+                    ));
+                }
+                Ok(RustType::Map(Box::new(key), Box::new(parse_owned_type(value)?)))
+            }
+            _ => Err(format!("HashMap needs a key and a value type: '{}'", ty_str)),
+        };
+    }
+
+    // Handle (A, B, ...), which may end in a comma. `()` and `(A,)` hold
+    // nothing worth pairing.
+    if let Some(inner) = ty_str.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')) {
+        let mut parts = split_top_level(inner);
+        if parts.last().is_some_and(|part| part.trim().is_empty()) {
+            parts.pop();
+        }
+        let elems = parts.into_iter().map(parse_owned_type).collect::<Result<Vec<_>, _>>()?;
+        if elems.len() < 2 {
+            return Err(format!("Tuple needs at least two element types: '{}'", ty_str));
+        }
+        return Ok(RustType::Tuple(elems));
     }
 
     // Primitive types
     match ty_str {
+        "i8" => Ok(RustType::I8),
+        "i16" => Ok(RustType::I16),
         "i32" => Ok(RustType::I32),
+        "i64" => Ok(RustType::I64),
+        "u8" => Ok(RustType::U8),
+        "u16" => Ok(RustType::U16),
+        "u32" => Ok(RustType::U32),
+        "u64" => Ok(RustType::U64),
+        "f32" => Ok(RustType::F32),
         "f64" => Ok(RustType::F64),
         "usize" => Ok(RustType::Usize),
         "bool" => Ok(RustType::Bool),
         "String" => Ok(RustType::String),
         "char" => Ok(RustType::Char),
+        "str" => Err("Type 'str' must be borrowed: use '&str' or 'String'".to_string()),
         _ => Err(format!("Unknown type: '{}'", ty_str)),
     }
 }
@@ -173,6 +344,18 @@ mod tests {
         assert_eq!(sig.return_type, RustType::Vec(Box::new(RustType::Usize)));
     }
 
+    #[test]
+    fn test_fn_keyword_optional() {
+        let with_fn = parse_signature("fn two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>").unwrap();
+        let without_fn = parse_signature("two_sum(nums: Vec<i32>, target: i32) -> Vec<usize>").unwrap();
+        assert_eq!(format!("{:?}", with_fn), format!("{:?}", without_fn));
+        assert_eq!(parse_signature("fnord(x: i32)").unwrap().name, "fnord");
+
+        assert_eq!(parse_signature("fn (x: i32)").unwrap_err(), "Invalid function name: ''");
+        assert_eq!(parse_signature("let f(x: i32)").unwrap_err(), "Invalid function name: 'let f'");
+        assert!(parse_signature("two_sum nums: Vec<i32>").is_err());
+    }
+
     #[test]
     fn test_reverse_string() {
         let sig = parse_signature("fn reverse_string(s: &mut Vec<char>)").unwrap();
@@ -215,4 +398,215 @@ mod tests {
         );
         assert_eq!(sig.params[2].name, "nums2");
     }
+
+    #[test]
+    fn test_type_display_round_trip() {
+        let types = [
+            "i8",
+            "i16",
+            "i32",
+            "i64",
+            "u8",
+            "u16",
+            "u32",
+            "u64",
+            "f32",
+            "f64",
+            "usize",
+            "bool",
+            "String",
+            "char",
+            "()",
+            "Vec<i32>",
+            "Vec<Vec<char>>",
+            "Vec<Vec<Vec<String>>>",
+            "Option<i32>",
+            "Vec<Option<String>>",
+            "HashMap<char, i32>",
+            "HashMap<String, Vec<i32>>",
+            "HashMap<i32, HashMap<String, bool>>",
+            "&Vec<i32>",
+            "&String",
+            "&str",
+            "&mut Vec<i32>",
+            "&mut Vec<Vec<char>>",
+            "(i32, i32)",
+            "(String, Vec<i64>, bool)",
+            "Vec<(char, usize)>",
+            "Option<(i32, (i32, i32))>",
+        ];
+        for ty_str in types {
+            let ty: RustType = ty_str.parse().unwrap();
+            assert_eq!(ty.to_string(), ty_str);
+            assert_eq!(ty.to_string().parse::<RustType>().unwrap(), ty);
+        }
+        assert!("Vec<i128>".parse::<RustType>().is_err());
+    }
+
+    #[test]
+    fn test_f32_type() {
+        let sig = parse_signature("fn scale(v: Vec<f32>, k: f32) -> Vec<f32>").unwrap();
+        assert_eq!(sig.params[0].ty, RustType::Vec(Box::new(RustType::F32)));
+        assert_eq!(sig.params[1].ty, RustType::F32);
+        assert_eq!(sig.return_type, RustType::Vec(Box::new(RustType::F32)));
+        assert!(parse_signature("fn f(m: HashMap<f32, i32>)").is_err());
+    }
+
+    #[test]
+    fn test_64_bit_integers() {
+        let sig = parse_signature("fn prefix_sums(nums: Vec<i64>, limit: u64) -> i64").unwrap();
+        assert_eq!(sig.params[0].ty, RustType::Vec(Box::new(RustType::I64)));
+        assert_eq!(sig.params[1].ty, RustType::U64);
+        assert_eq!(sig.return_type, RustType::I64);
+    }
+
+    #[test]
+    fn test_fixed_width_integers() {
+        let sig = parse_signature("fn pack(bytes: Vec<u8>, shift: i8, mask: u16, seed: i16) -> u32").unwrap();
+        assert_eq!(sig.params[0].ty, RustType::Vec(Box::new(RustType::U8)));
+        assert_eq!(sig.params[1].ty, RustType::I8);
+        assert_eq!(sig.params[2].ty, RustType::U16);
+        assert_eq!(sig.params[3].ty, RustType::I16);
+        assert_eq!(sig.return_type, RustType::U32);
+        assert!(parse_signature("fn f(x: HashMap<u8, i16>)").is_ok());
+        assert!(parse_signature("fn f(x: u128)").is_err());
+    }
+
+    #[test]
+    fn test_option_type() {
+        let sig = parse_signature("fn f(x: i32) -> Option<i32>").unwrap();
+        assert_eq!(sig.params[0].ty, RustType::I32);
+        assert_eq!(sig.return_type, RustType::Option(Box::new(RustType::I32)));
+        assert!(parse_signature("fn f(x: i32) -> Option<i32").is_err());
+    }
+
+    #[test]
+    fn test_tuple_type() {
+        let sig = parse_signature("fn min_max(nums: Vec<i32>) -> (i32, i32)").unwrap();
+        assert_eq!(sig.return_type, RustType::Tuple(vec![RustType::I32, RustType::I32]));
+
+        let sig = parse_signature("fn f(pairs: Vec<(String, Vec<i32>)>, k: usize) -> bool").unwrap();
+        assert_eq!(sig.params.len(), 2);
+        assert_eq!(
+            sig.params[0].ty,
+            RustType::Vec(Box::new(RustType::Tuple(vec![
+                RustType::String,
+                RustType::Vec(Box::new(RustType::I32)),
+            ])))
+        );
+
+        assert!(parse_signature("fn f(x: i32) -> (i32, i32,)").is_ok());
+        assert_eq!(
+            parse_signature("fn f(x: i32) -> (i32,)").unwrap_err(),
+            "Tuple needs at least two element types: '(i32,)'"
+        );
+        assert!(parse_signature("fn f(x: (i32))").is_err());
+        assert!(parse_signature("fn f(x: (i32, &i32))").is_err());
+        assert!(parse_signature("fn f(x: (i32, , i32))").is_err());
+    }
+
+    #[test]
+    fn test_map_type() {
+        let sig = parse_signature("fn counts(s: String) -> HashMap<char, i32>").unwrap();
+        assert_eq!(
+            sig.return_type,
+            RustType::Map(Box::new(RustType::Char), Box::new(RustType::I32))
+        );
+        assert!(parse_signature("fn f(m: HashMap<i32>)").is_err());
+        assert!(parse_signature("fn f(m: HashMap<i32, i32, i32>)").is_err());
+        assert!(parse_signature("fn f(m: HashMap<f64, i32>)").is_err());
+    }
+
+    #[test]
+    fn test_shared_reference_params() {
+        let sig = parse_signature("fn contains(haystack: &Vec<i32>, needle: &String) -> bool").unwrap();
+        assert_eq!(
+            sig.params[0].ty,
+            RustType::Ref(Box::new(RustType::Vec(Box::new(RustType::I32))))
+        );
+        assert_eq!(sig.params[1].ty, RustType::Ref(Box::new(RustType::String)));
+
+        let sig = parse_signature("fn contains(haystack: &[i32], needle: i32) -> bool").unwrap();
+        assert_eq!(
+            sig.params[0].ty,
+            RustType::Ref(Box::new(RustType::Vec(Box::new(RustType::I32))))
+        );
+        assert!(parse_signature("fn f(grid: &[Vec<char>])").is_ok());
+        assert!(parse_signature("fn f(xs: &[i32)").is_err());
+        assert_eq!(
+            parse_signature("fn f(xs: Vec<&i32>)").unwrap_err(),
+            "Nested reference '&i32' is not supported; only a parameter can be borrowed"
+        );
+        assert!(parse_signature("fn f(xs: Option<&mut Vec<i32>>)").is_err());
+        assert!(parse_signature("fn f(xs: &&String)").is_err());
+    }
+
+    #[test]
+    fn test_str_params() {
+        let sig = parse_signature("fn count(s: &str, words: Vec<&str>) -> usize").unwrap();
+        assert_eq!(sig.params[0].ty, RustType::Ref(Box::new(RustType::Str)));
+        assert_eq!(
+            sig.params[1].ty,
+            RustType::Vec(Box::new(RustType::Ref(Box::new(RustType::Str))))
+        );
+        assert_eq!(sig.params[0].ty.to_string(), "&str");
+
+        assert_eq!(
+            parse_signature("fn f(s: str)").unwrap_err(),
+            "Type 'str' must be borrowed: use '&str' or 'String'"
+        );
+        assert!(parse_signature("fn f(s: &mut str)").is_err());
+        assert!(parse_signature("fn f(s: String) -> &str").is_err());
+        assert!(parse_signature("fn f(s: String) -> Vec<&str>").is_err());
+    }
+
+    #[test]
+    fn test_reference_return_rejected() {
+        let err = parse_signature("fn first(nums: &mut Vec<i32>) -> &i32").unwrap_err();
+        assert_eq!(
+            err,
+            "Reference return type '&i32' is not supported; return an owned value instead"
+        );
+        assert!(parse_signature("fn grow(nums: &mut Vec<i32>) -> &mut Vec<i32>").is_err());
+
+        let sig = parse_signature("fn first(nums: &mut Vec<i32>) -> i32").unwrap();
+        assert_eq!(sig.return_type, RustType::I32);
+    }
+
+    #[test]
+    fn test_param_whitespace_and_trailing_comma() {
+        for sig in [
+            "fn f(x: i32, y: Vec<i32>,)",
+            "fn f(x: i32 , y: Vec<i32>)",
+            "fn f(  x:  i32,   y:   Vec<i32>  )",
+            "fn f(x:\ti32,\ty: Vec<i32>)",
+            "fn f(\n    x: i32,\n    y: Vec<i32>,\n)",
+        ] {
+            let parsed = parse_signature(sig).unwrap();
+            let params: Vec<(&str, &RustType)> =
+                parsed.params.iter().map(|p| (p.name.as_str(), &p.ty)).collect();
+            assert_eq!(
+                params,
+                vec![("x", &RustType::I32), ("y", &RustType::Vec(Box::new(RustType::I32)))],
+                "{}",
+                sig
+            );
+        }
+    }
+
+    #[test]
+    fn test_stray_comma_rejected() {
+        assert_eq!(
+            parse_signature("fn f(, x: i32)").unwrap_err(),
+            "Empty parameter in '(, x: i32)': stray ','"
+        );
+        assert!(parse_signature("fn f(x: i32,, y: i32)").is_err());
+        assert!(parse_signature("fn f(,)").is_err());
+    }
+
+    #[test]
+    fn test_duplicate_param_rejected() {
+        let err = parse_signature("fn f(x: i32, x: i32) -> i32").unwrap_err();
+        assert_eq!(err, "Duplicate parameter name: 'x'");
+    }
 }